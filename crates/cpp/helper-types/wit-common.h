@@ -1,62 +1,336 @@
 #pragma once
 
+#include <array>
 #include <assert.h>
+#include <iterator> // forward_iterator_tag
+#include <limits.h> // CHAR_BIT
 #include <map>
+#include <memory> // unique_ptr
 #include <optional>
 #include <stddef.h> // size_t
 #include <stdint.h>
+#include <stdlib.h> // abort
+#include <string.h> // memcpy
+#include <string>
+#include <string_view>
+#include <type_traits>
+#include <utility> // std::move
 #if __cplusplus > 202001L
 #include <span>
 #else
 #include <vector>
 #endif
 
+#if __cpp_lib_unreachable >= 202202L
+#define WIT_UNREACHABLE() std::unreachable()
+#elif defined(_MSC_VER)
+#define WIT_UNREACHABLE() __assume(false)
+#else
+#define WIT_UNREACHABLE() __builtin_unreachable()
+#endif
+
+// called on malformed values from the other side of the canonical ABI, like a
+// discriminant of 2 for an option, define it before including the bindings
+// to use another handler
+#ifndef WIT_TRAP
+#define WIT_TRAP() abort()
+#endif
+
+// the integer type of string and list lengths in the view and owned types,
+// defined by bindings generated with `--length-type`
+#ifndef WIT_LENGTH_TYPE
+#define WIT_LENGTH_TYPE size_t
+#endif
+
+// marks the accessors returning views into an owned string or list, defined
+// as `[[clang::lifetimebound]]` by bindings generated with `--lifetimebound`
+// so Clang warns about views outliving their owner
+#ifndef WIT_LIFETIMEBOUND
+#define WIT_LIFETIMEBOUND
+#endif
+
 namespace wit {
+typedef WIT_LENGTH_TYPE length_type;
+static_assert(sizeof(length_type) >= sizeof(uint32_t),
+              "lengths of the canonical ABI need at least 32 bits");
+// strings are UTF-8 bytes accessed both as char and as uint8_t
+static_assert(CHAR_BIT == 8, "UTF-8 strings need 8 bit chars");
+static_assert(std::is_same<uint8_t, unsigned char>::value,
+              "string bytes alias char through uint8_t");
+
 #if __cplusplus > 202001L
 using std::span;
 #else
 /// Minimal span (vector view) implementation for older C++ environments
 template <class T> class span {
   T const *address;
-  size_t length;
+  length_type length;
 
 public:
   T const *data() const { return address; }
-  size_t size() const { return length; }
+  length_type size() const { return length; }
 
   typedef T const *const_iterator;
 
   const_iterator begin() const { return address; }
   const_iterator end() const { return address + length; }
-  T const &operator[](size_t index) { return address[index]; }
-  // create from any compatible vector (borrows data!)
-  template <class U>
+  T const &operator[](size_t index) const { return address[index]; }
+  span(T const *a, length_type l) : address(a), length(l) {}
+  // create from any compatible vector (borrows data!), constrained to keep
+  // overloads on different spans unambiguous
+  template <class U, class = typename std::enable_if<
+                         std::is_convertible<U const *, T const *>::value>::type>
   span(std::vector<U> const &vec) : address(vec.data()), length(vec.size()) {}
 };
 #endif
 
+/// @brief The canonical NaN for any NaN, other values are unchanged
+///
+/// The canonical ABI only passes NaNs with the bits 0x7fc00000 (f32) and
+/// 0x7ff8000000000000 (f64).
+inline float canonicalize_nan(float f) {
+  if (f != f) {
+    uint32_t bits = 0x7fc00000u;
+    memcpy(&f, &bits, sizeof(f));
+  }
+  return f;
+}
+inline double canonicalize_nan(double d) {
+  if (d != d) {
+    uint64_t bits = 0x7ff8000000000000ull;
+    memcpy(&d, &bits, sizeof(d));
+  }
+  return d;
+}
+
+/// @brief The error of a `result<T>` without error payload
+struct Void {};
+
+/// @brief A lifted value was malformed, returned by the `Try` wrappers
+/// instead of trapping (see `--try-call`)
+struct decode_error {
+  // the part which failed to decode, e.g. "discriminant"
+  char const *what;
+};
+
+/// @brief The owning pointer to a resource (its `Owned` type)
+///
+/// Converts to a const reference, so an owned resource can be passed
+/// where a borrow (`std::reference_wrapper<const R>`) is expected.
+template <class R, class D> class owned_ptr : public std::unique_ptr<R, D> {
+  typedef std::unique_ptr<R, D> base;
+
+public:
+  using base::base;
+  using base::operator=;
+  operator R const &() const { return **this; }
+};
+
+/// @brief Iterates the set bits of a flags value, lowest first
+///
+/// Each step yields a flags value with exactly one bit set.
+template <class F> class flags_iterator {
+  typedef typename std::underlying_type<F>::type repr;
+  repr remaining;
+
+public:
+  flags_iterator() : remaining(0) {}
+  explicit flags_iterator(F f) : remaining(repr(f)) {}
+  F operator*() const { return F(repr(remaining & (~remaining + 1))); }
+  flags_iterator &operator++() {
+    remaining = repr(remaining & (remaining - 1));
+    return *this;
+  }
+  bool operator==(flags_iterator const &b) const {
+    return remaining == b.remaining;
+  }
+  bool operator!=(flags_iterator const &b) const {
+    return remaining != b.remaining;
+  }
+};
+
+/// @brief Decodes the code points of valid UTF-8, one per step
+///
+/// Strings crossing the canonical ABI are always valid UTF-8.
+class code_point_iterator {
+  uint8_t const *ptr;
+
+  // the bytes of the sequence starting with `lead`
+  static size_t sequence_length(uint8_t lead) {
+    return lead < 0x80 ? 1 : lead < 0xe0 ? 2 : lead < 0xf0 ? 3 : 4;
+  }
+
+public:
+  typedef std::forward_iterator_tag iterator_category;
+  typedef char32_t value_type;
+  typedef ptrdiff_t difference_type;
+  typedef char32_t const *pointer;
+  typedef char32_t reference;
+
+  code_point_iterator() : ptr(nullptr) {}
+  explicit code_point_iterator(char const *p) : ptr((uint8_t const *)p) {}
+  char32_t operator*() const {
+    size_t len = sequence_length(ptr[0]);
+    if (len == 1) {
+      return ptr[0];
+    }
+    // the lead byte keeps 7 - len payload bits, each continuation 6
+    char32_t result = ptr[0] & (0x7f >> len);
+    for (size_t i = 1; i < len; ++i) {
+      result = (result << 6) | (ptr[i] & 0x3f);
+    }
+    return result;
+  }
+  code_point_iterator &operator++() {
+    ptr += sequence_length(ptr[0]);
+    return *this;
+  }
+  code_point_iterator operator++(int) {
+    code_point_iterator result = *this;
+    ++*this;
+    return result;
+  }
+  bool operator==(code_point_iterator const &b) const { return ptr == b.ptr; }
+  bool operator!=(code_point_iterator const &b) const { return ptr != b.ptr; }
+};
+
+/// @brief The code points of a UTF-8 buffer, for range based for loops
+class code_points {
+  char const *first;
+  char const *last;
+
+public:
+  code_points(char const *data, size_t size)
+      : first(data), last(data + size) {}
+  code_point_iterator begin() const { return code_point_iterator(first); }
+  code_point_iterator end() const { return code_point_iterator(last); }
+};
+
+/// @brief The UTF-8 of a wide string (see `--host-string-type=wstring`)
+///
+/// `wchar_t` holds UTF-16 where it has 16 bits (Windows), UTF-32 otherwise.
+/// Unpaired surrogates and other invalid code points become U+FFFD, as the
+/// canonical ABI only passes valid UTF-8.
+inline std::string to_utf8(std::wstring_view w) {
+  std::string result;
+  result.reserve(w.size());
+  for (size_t i = 0; i < w.size(); ++i) {
+    char32_t c = char32_t(w[i]);
+    if (sizeof(wchar_t) == 2 && c >= 0xd800 && c < 0xdc00 && i + 1 < w.size() &&
+        char32_t(w[i + 1]) >= 0xdc00 && char32_t(w[i + 1]) < 0xe000) {
+      c = 0x10000 + ((c - 0xd800) << 10) + (char32_t(w[++i]) - 0xdc00);
+    } else if ((c >= 0xd800 && c < 0xe000) || c > 0x10ffff) {
+      c = 0xfffd;
+    }
+    if (c < 0x80) {
+      result += char(c);
+    } else if (c < 0x800) {
+      result += char(0xc0 | (c >> 6));
+      result += char(0x80 | (c & 0x3f));
+    } else if (c < 0x10000) {
+      result += char(0xe0 | (c >> 12));
+      result += char(0x80 | ((c >> 6) & 0x3f));
+      result += char(0x80 | (c & 0x3f));
+    } else {
+      result += char(0xf0 | (c >> 18));
+      result += char(0x80 | ((c >> 12) & 0x3f));
+      result += char(0x80 | ((c >> 6) & 0x3f));
+      result += char(0x80 | (c & 0x3f));
+    }
+  }
+  return result;
+}
+
+/// @brief The wide string of valid UTF-8, the inverse of `to_utf8`
+inline std::wstring to_wstring(std::string_view s) {
+  std::wstring result;
+  result.reserve(s.size());
+  for (char32_t c : code_points(s.data(), s.size())) {
+    if (sizeof(wchar_t) == 2 && c >= 0x10000) {
+      result += wchar_t(0xd800 + ((c - 0x10000) >> 10));
+      result += wchar_t(0xdc00 + ((c - 0x10000) & 0x3ff));
+    } else {
+      result += wchar_t(c);
+    }
+  }
+  return result;
+}
+
+/// @brief A core wasm type of the flattened canonical ABI representation
+enum class flat_type { i32, i64, f32, f64 };
+
+/// @brief Canonical ABI layout of a generated type (see `--layouts`)
+///
+/// Specializations provide `size`, `align` and the flattened
+/// core wasm types in `flat`.
+template <class T> struct layout;
+
+#if defined(__BYTE_ORDER__) && __BYTE_ORDER__ == __ORDER_BIG_ENDIAN__
+constexpr bool little_endian = false;
+#else
+constexpr bool little_endian = true;
+#endif
+
+/// @brief Whether a list of T can be lowered with a single memcpy
+///
+/// T has to be stored exactly like the canonical ABI element of
+/// the given size, the generated code falls back to a loop otherwise.
+template <class T, size_t size>
+constexpr bool bitwise_lowerable =
+    std::is_trivially_copyable<T>::value && sizeof(T) == size && little_endian;
+
+/// @brief Runs a cleanup function when leaving the scope
+///
+/// Used by the generated code to release temporary allocations,
+/// this also happens if an exception unwinds the stack.
+template <class F> class scope_guard {
+  F func;
+  bool active;
+
+public:
+  scope_guard(F &&f) : func(std::move(f)), active(true) {}
+  scope_guard(scope_guard &&b) : func(std::move(b.func)), active(b.active) {
+    b.active = false;
+  }
+  scope_guard(scope_guard const &) = delete;
+  scope_guard &operator=(scope_guard const &) = delete;
+  scope_guard &operator=(scope_guard &&) = delete;
+  ~scope_guard() {
+    if (active) {
+      func();
+    }
+  }
+  // don't run the cleanup function
+  void dismiss() { active = false; }
+};
+
 /// @brief Helper class to map between IDs and resources
 /// @tparam R Type of the Resource
 template <class R> class ResourceTable {
-  static std::map<int32_t, R> resources;
+  // function local static: initialized on first use, so resources can be
+  // created from static constructors in any translation unit
+  static std::map<int32_t, R> &resources() {
+    static std::map<int32_t, R> table;
+    return table;
+  }
 
 public:
   static R *lookup_resource(int32_t id) {
-    auto result = resources.find(id);
-    return result == resources.end() ? nullptr : &result->second;
+    auto result = resources().find(id);
+    return result == resources().end() ? nullptr : &result->second;
   }
   static int32_t store_resource(R &&value) {
-    auto last = resources.rbegin();
-    int32_t id = last == resources.rend() ? 0 : last->first + 1;
-    resources.insert(std::pair<int32_t, R>(id, std::move(value)));
+    auto last = resources().rbegin();
+    int32_t id = last == resources().rend() ? 0 : last->first + 1;
+    resources().insert(std::pair<int32_t, R>(id, std::move(value)));
     return id;
   }
   static std::optional<R> remove_resource(int32_t id) {
-    auto iter = resources.find(id);
+    auto iter = resources().find(id);
     std::optional<R> result;
-    if (iter != resources.end()) {
+    if (iter != resources().end()) {
       result = std::move(iter->second);
-      resources.erase(iter);
+      resources().erase(iter);
     }
     return std::move(result);
   }
@@ -0,0 +1,127 @@
+#pragma once
+
+#include <optional>
+#include <ostream>
+#include <string_view>
+#include <tuple>
+#include <type_traits>
+#include <utility> // std::declval
+
+namespace wit {
+/// @brief Printing of values for debugging (see `--debug-dump`)
+///
+/// Records and variants get a generated `dump` member, everything else is
+/// printed by `wit::dump` depending on what the type provides.
+namespace dump_detail {
+template <class T, class = void> struct has_dump : std::false_type {};
+template <class T>
+struct has_dump<T, std::void_t<decltype(std::declval<T const &>().dump(
+                       std::declval<std::ostream &>(), 0))>> : std::true_type {
+};
+
+template <class T, class = void> struct has_view : std::false_type {};
+template <class T>
+struct has_view<T, std::void_t<decltype(std::declval<T const &>().get_view())>>
+    : std::true_type {};
+
+template <class T, class = void> struct is_range : std::false_type {};
+template <class T>
+struct is_range<T, std::void_t<decltype(std::declval<T const &>().begin()),
+                               decltype(std::declval<T const &>().end())>>
+    : std::true_type {};
+
+// `std::expected` and `wit::expected`
+template <class T, class = void> struct is_expected : std::false_type {};
+template <class T>
+struct is_expected<T, std::void_t<typename T::value_type,
+                                  typename T::error_type,
+                                  decltype(std::declval<T const &>().error())>>
+    : std::true_type {};
+
+template <class T> struct is_optional : std::false_type {};
+template <class T> struct is_optional<std::optional<T>> : std::true_type {};
+
+template <class T> struct is_tuple : std::false_type {};
+template <class... T> struct is_tuple<std::tuple<T...>> : std::true_type {};
+} // namespace dump_detail
+
+inline void dump_indent(std::ostream &os, int indent) {
+  for (int i = 0; i < indent; ++i) {
+    os << "  ";
+  }
+}
+
+template <class T> void dump(std::ostream &os, T const &v, int indent = 0) {
+  using namespace dump_detail;
+  if constexpr (has_dump<T>::value) {
+    v.dump(os, indent);
+  } else if constexpr (std::is_same<T, bool>::value) {
+    os << (v ? "true" : "false");
+  } else if constexpr (std::is_arithmetic<T>::value) {
+    // `+` prints 8 bit integers as numbers
+    os << +v;
+  } else if constexpr (std::is_enum<T>::value) {
+    os << +static_cast<std::underlying_type_t<T>>(v);
+  } else if constexpr (std::is_convertible<T const &, std::string_view>::value) {
+    os << '"' << std::string_view(v) << '"';
+  } else if constexpr (has_view<T>::value) {
+    // `wit::string` and `wit::vector`
+    dump(os, v.get_view(), indent);
+  } else if constexpr (is_optional<T>::value) {
+    if (v) {
+      os << "some(";
+      dump(os, *v, indent);
+      os << ")";
+    } else {
+      os << "none";
+    }
+  } else if constexpr (is_expected<T>::value) {
+    if (v.has_value()) {
+      os << "ok";
+      if constexpr (!std::is_void<typename T::value_type>::value) {
+        os << "(";
+        dump(os, *v, indent);
+        os << ")";
+      }
+    } else {
+      os << "err(";
+      dump(os, v.error(), indent);
+      os << ")";
+    }
+  } else if constexpr (is_tuple<T>::value) {
+    os << "(";
+    std::apply(
+        [&os, indent](auto const &...elem) {
+          int n = 0;
+          ((os << (n++ ? ", " : ""), dump(os, elem, indent)), ...);
+        },
+        v);
+    os << ")";
+  } else if constexpr (is_range<T>::value) {
+    if (v.begin() == v.end()) {
+      os << "[]";
+      return;
+    }
+    os << "[\n";
+    for (auto const &elem : v) {
+      dump_indent(os, indent + 1);
+      dump(os, elem, indent + 1);
+      os << ",\n";
+    }
+    dump_indent(os, indent);
+    os << "]";
+  } else {
+    // resources and other handles
+    os << "<opaque>";
+  }
+}
+
+/// @brief One `name: value` line of a generated record `dump`
+template <class T>
+void dump_field(std::ostream &os, int indent, char const *name, T const &v) {
+  dump_indent(os, indent);
+  os << name << ": ";
+  dump(os, v, indent);
+  os << ",\n";
+}
+} // namespace wit
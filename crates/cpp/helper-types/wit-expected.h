@@ -0,0 +1,128 @@
+#pragma once
+
+#if __cplusplus > 202002L
+#include <expected>
+
+namespace wit {
+using std::expected;
+using std::unexpected;
+} // namespace wit
+#else
+#include "wit-common.h" // WIT_TRAP
+#include <type_traits>
+#include <utility> // std::move
+#include <variant>
+
+namespace wit {
+/// @brief The error of an `expected`, like `std::unexpected`
+template <class E> class unexpected {
+  E err;
+
+public:
+  constexpr explicit unexpected(E e) : err(std::move(e)) {}
+  constexpr E &error() & { return err; }
+  constexpr E const &error() const & { return err; }
+  constexpr E &&error() && { return std::move(err); }
+};
+template <class E> unexpected(E) -> unexpected<E>;
+
+template <class T> struct is_unexpected : std::false_type {};
+template <class E> struct is_unexpected<unexpected<E>> : std::true_type {};
+
+/// @brief Minimal `std::expected` for environments older than C++23
+/// (see `--std`)
+///
+/// Accessing the value of an error traps instead of throwing.
+template <class T, class E> class expected {
+  std::variant<T, unexpected<E>> v;
+
+public:
+  typedef T value_type;
+  typedef E error_type;
+
+  expected() = default;
+  template <class U = T,
+            class = std::enable_if_t<
+                !std::is_same<std::decay_t<U>, expected>::value &&
+                !is_unexpected<std::decay_t<U>>::value &&
+                std::is_constructible<T, U &&>::value>>
+  expected(U &&value) : v(std::in_place_index<0>, std::forward<U>(value)) {}
+  template <class G>
+  expected(unexpected<G> err)
+      : v(std::in_place_index<1>, E(std::move(err).error())) {}
+
+  bool has_value() const { return v.index() == 0; }
+  explicit operator bool() const { return has_value(); }
+
+  T &operator*() & { return *std::get_if<0>(&v); }
+  T const &operator*() const & { return *std::get_if<0>(&v); }
+  T &&operator*() && { return std::move(*std::get_if<0>(&v)); }
+  T *operator->() { return std::get_if<0>(&v); }
+  T const *operator->() const { return std::get_if<0>(&v); }
+
+  T &value() & {
+    if (!has_value()) {
+      WIT_TRAP();
+    }
+    return **this;
+  }
+  T const &value() const & {
+    if (!has_value()) {
+      WIT_TRAP();
+    }
+    return **this;
+  }
+  T &&value() && {
+    if (!has_value()) {
+      WIT_TRAP();
+    }
+    return std::move(**this);
+  }
+  template <class U> T value_or(U &&other) const & {
+    return has_value() ? **this : static_cast<T>(std::forward<U>(other));
+  }
+  template <class U> T value_or(U &&other) && {
+    return has_value() ? std::move(**this)
+                       : static_cast<T>(std::forward<U>(other));
+  }
+
+  E &error() & { return std::get_if<1>(&v)->error(); }
+  E const &error() const & { return std::get_if<1>(&v)->error(); }
+  E &&error() && { return std::move(*std::get_if<1>(&v)).error(); }
+
+  template <class... A> T &emplace(A &&...a) {
+    return v.template emplace<0>(std::forward<A>(a)...);
+  }
+};
+
+/// @brief An `expected` without a value, only telling about success
+template <class E> class expected<void, E> {
+  std::variant<std::monostate, unexpected<E>> v;
+
+public:
+  typedef void value_type;
+  typedef E error_type;
+
+  expected() = default;
+  template <class G>
+  expected(unexpected<G> err)
+      : v(std::in_place_index<1>, E(std::move(err).error())) {}
+
+  bool has_value() const { return v.index() == 0; }
+  explicit operator bool() const { return has_value(); }
+
+  void operator*() const {}
+  void value() const {
+    if (!has_value()) {
+      WIT_TRAP();
+    }
+  }
+
+  E &error() & { return std::get_if<1>(&v)->error(); }
+  E const &error() const & { return std::get_if<1>(&v)->error(); }
+  E &&error() && { return std::move(*std::get_if<1>(&v)).error(); }
+
+  void emplace() { v.template emplace<0>(); }
+};
+} // namespace wit
+#endif
@@ -1,3 +1,5 @@
+#pragma once
+
 #include "wit-common.h"
 #include <malloc.h>
 #include <memory> // unique_ptr
@@ -5,91 +7,420 @@
 #include <string>
 #include <string_view>
 #include <string.h> // memcpy
+#include <vector>
+
+// the allocation functions of strings and vectors, they have to be
+// compatible with the `malloc` used by the canonical ABI
+extern "C" void *wit_malloc(size_t size);
+extern "C" void wit_free(void *ptr);
+// to replace the weak defaults (e.g. to count allocations) define both
+// within a translation unit defining WIT_CUSTOM_ALLOCATOR
+#ifndef WIT_CUSTOM_ALLOCATOR
+extern "C" __attribute__((__weak__)) void *wit_malloc(size_t size) {
+  return malloc(size);
+}
+extern "C" __attribute__((__weak__)) void wit_free(void *ptr) { free(ptr); }
+#endif
+
+// the number of elements a vector stores inline, defined by bindings
+// generated with `--sbo`
+#ifndef WIT_VECTOR_INLINE
+#define WIT_VECTOR_INLINE 0
+#endif
 
 namespace wit {
 /// A string in linear memory, freed unconditionally using free
 ///
 /// A normal C++ string makes no guarantees about where the characters
 /// are stored and how this is freed.
+///
+/// An empty string owns no memory, its pointer may be null or dangling
+/// (see `--empty-ptr`), so it is never freed.
 class string {
   uint8_t const *data_;
-  size_t length;
+  length_type length;
+  // allocated bytes, only exceeds length after appending
+  length_type capacity;
+
+  void release() {
+    if (data_ && capacity > 0) {
+      wit_free(const_cast<uint8_t *>(data_));
+    }
+  }
 
 public:
   string(string const &) = delete;
-  string(string &&b) : data_(b.data_), length(b.length) { b.data_ = nullptr; }
+  string(string &&b) noexcept
+      : data_(b.data_), length(b.length), capacity(b.capacity) {
+    b.data_ = nullptr;
+  }
   string &operator=(string const &) = delete;
-  string &operator=(string &&b) {
-    if (data_) {
-      free(const_cast<uint8_t *>(data_));
-    }
+  string &operator=(string &&b) noexcept {
+    release();
     data_ = b.data_;
     length = b.length;
+    capacity = b.capacity;
     b.data_ = nullptr;
     return *this;
   }
-  string(char const *d, size_t l) : data_((uint8_t const *)d), length(l) {}
-  char const *data() const { return (char const *)data_; }
-  size_t size() const { return length; }
-  ~string() {
-    if (data_) {
-      free(const_cast<uint8_t *>(data_));
+  string(char const *d, length_type l)
+      : data_((uint8_t const *)d), length(l), capacity(l) {}
+  char const *data() const WIT_LIFETIMEBOUND { return (char const *)data_; }
+  // the byte at `n`, unchecked
+  uint8_t operator[](size_t n) const { return data_[n]; }
+  // the byte at `n`, traps if it is past the end
+  uint8_t at(size_t n) const {
+    if (n >= length) {
+      WIT_TRAP();
+    }
+    return data_[n];
+  }
+  length_type size() const { return length; }
+  length_type size_bytes() const { return length; }
+  // number of code points, i.e. the bytes starting a UTF-8 sequence
+  size_t size_codepoints() const {
+    size_t count = 0;
+    for (size_t i = 0; i < length; ++i) {
+      if ((data_[i] & 0xc0) != 0x80) {
+        ++count;
+      }
     }
+    return count;
+  }
+  // iterate the characters instead of the bytes
+  wit::code_points code_points() const WIT_LIFETIMEBOUND {
+    return wit::code_points(data(), length);
   }
+  ~string() { release(); }
   // leak the memory
   void leak() { data_ = nullptr; }
   // typically called by post
-  static void drop_raw(void *ptr) { free(ptr); }
-  std::string_view get_view() const {
+  static void drop_raw(void *ptr) { wit_free(ptr); }
+  std::string_view get_view() const WIT_LIFETIMEBOUND {
     return std::string_view((const char *)data_, length);
   }
+  // borrows `len` bytes (at most up to the end) starting at byte `offset`,
+  // which must not be past the end
+  std::string_view substr(size_t offset,
+                          size_t len = std::string_view::npos) const
+      WIT_LIFETIMEBOUND {
+    assert(offset <= length);
+    return get_view().substr(offset, len);
+  }
   std::string to_string() const {
     return std::string((const char *)data_, length);
   }
+#if __cplusplus > 202001L
+  // lexicographic comparison on the characters (enables record ordering)
+  bool operator==(string const &b) const { return get_view() == b.get_view(); }
+  auto operator<=>(string const &b) const {
+    return get_view() <=> b.get_view();
+  }
+  // also covers `std::string` and `char const*`, in either order
+  bool operator==(std::string_view b) const { return get_view() == b; }
+  auto operator<=>(std::string_view b) const { return get_view() <=> b; }
+#else
+  friend bool operator==(string const &a, std::string_view b) {
+    return a.get_view() == b;
+  }
+  friend bool operator!=(string const &a, std::string_view b) {
+    return a.get_view() != b;
+  }
+  friend bool operator==(std::string_view a, string const &b) {
+    return a == b.get_view();
+  }
+  friend bool operator!=(std::string_view a, string const &b) {
+    return a != b.get_view();
+  }
+#endif
   static string from_view(std::string_view v) {
-    char* addr = (char*)malloc(v.size());
+    if (v.empty()) {
+      return string(nullptr, 0);
+    }
+    char* addr = (char*)wit_malloc(v.size());
     memcpy(addr, v.data(), v.size());
     return string(addr, v.size());
   }
+  // a std::string can't give up its buffer, so the characters are copied
+  // with a single allocation and the source is released immediately
+  static string from_string(std::string &&s) {
+    string result = from_view(s);
+    std::string().swap(s);
+    return result;
+  }
+  // append the characters of `v` (which may be part of this string), the
+  // storage at least doubles when it grows
+  string &operator+=(std::string_view v) {
+    if (v.empty()) {
+      return *this;
+    }
+    length_type n = length + v.size();
+    if (n > capacity) {
+      length_type cap = capacity * 2 > n ? capacity * 2 : n;
+      uint8_t *addr = (uint8_t *)wit_malloc(cap);
+      if (length > 0) {
+        memcpy(addr, data_, length);
+      }
+      memcpy(addr + length, v.data(), v.size());
+      release();
+      data_ = addr;
+      capacity = cap;
+    } else {
+      memcpy(const_cast<uint8_t *>(data_) + length, v.data(), v.size());
+    }
+    length = n;
+    return *this;
+  }
+  string &operator+=(string const &b) { return *this += b.get_view(); }
+  // a new string with a single allocation
+  friend string operator+(string const &a, std::string_view b) {
+    string result(nullptr, 0);
+    if (a.size() + b.size() > 0) {
+      result.data_ = (uint8_t *)wit_malloc(a.size() + b.size());
+      result.capacity = a.size() + b.size();
+    }
+    result += a.get_view();
+    result += b;
+    return result;
+  }
+  // appends to the temporary, so chains grow a single string
+  friend string operator+(string &&a, std::string_view b) {
+    a += b;
+    return std::move(a);
+  }
 };
 
 /// A vector in linear memory, freed unconditionally using free
 ///
 /// You can't detach the data memory from a vector, nor create one
 /// in a portable way from a buffer and lenght without copying.
+///
+/// Like `string` an empty vector owns no memory.
+///
+/// With `WIT_VECTOR_INLINE` lists of up to that many elements are stored
+/// within the vector, moving it moves the elements.
 template <class T> class vector {
   T *data_;
-  size_t length;
+  length_type length;
+  // allocated elements, only exceeds length after appending
+  length_type capacity;
+#if WIT_VECTOR_INLINE > 0
+  alignas(T) unsigned char inline_[WIT_VECTOR_INLINE * sizeof(T)];
+  T *inline_data() { return reinterpret_cast<T *>(inline_); }
+#else
+  T *inline_data() { return nullptr; }
+#endif
 
-public:
-  vector(vector const &) = delete;
-  vector(vector &&b) : data_(b.data_), length(b.length) { b.data_ = nullptr; }
-  vector &operator=(vector const &) = delete;
-  vector &operator=(vector &&b) {
+  bool is_inline() { return data_ != nullptr && data_ == inline_data(); }
+  // inline if it fits, `capacity` has to be set by the caller
+  T *storage(length_type cap) {
+    return cap == 0                   ? nullptr
+           : cap <= WIT_VECTOR_INLINE ? inline_data()
+                                      : (T *)wit_malloc(sizeof(T) * cap);
+  }
+  // move the elements of `b` into our inline storage
+  void adopt_inline(vector &b) {
+    data_ = inline_data();
+    for (size_t i = 0; i < length; ++i) {
+      new ((void *)(data_ + i)) T(std::move(b.data_[i]));
+      b.data_[i].~T();
+    }
+  }
+
+  // elements own their memory as well (e.g. nested lists)
+  void destroy() {
     if (data_) {
-      free(const_cast<uint8_t *>(data_));
+      for (size_t i = 0; i < length; ++i) {
+        data_[i].~T();
+      }
+      if (capacity > 0 && !is_inline()) {
+        wit_free(data_);
+      }
+    }
+  }
+
+  struct uninitialized {};
+  // storage for `cap` elements of which the first `len` are constructed by
+  // the caller, a prvalue so that inline storage is never moved
+  vector(uninitialized, length_type cap, length_type len)
+      : data_(nullptr), length(len), capacity(cap) {
+    data_ = storage(cap);
+    if (is_inline()) {
+      capacity = WIT_VECTOR_INLINE;
+    }
+  }
+
+public:
+  // a single allocation, length counts the copied elements so that the
+  // destructor of the delegated-to object only destroys these on a throw
+  vector(vector const &b) : vector(uninitialized{}, b.length, 0) {
+    for (; length < b.length; ++length) {
+      new ((void *)(data_ + length)) T(b.data_[length]);
+    }
+  }
+  vector(vector &&b) noexcept
+      : data_(b.data_), length(b.length), capacity(b.capacity) {
+    if (b.is_inline()) {
+      adopt_inline(b);
     }
+    b.data_ = nullptr;
+  }
+  // copy and swap, *this is unchanged if copying an element throws
+  vector &operator=(vector const &b) {
+    vector copy(b);
+    swap(copy);
+    return *this;
+  }
+  vector &operator=(vector &&b) noexcept {
+    destroy();
     data_ = b.data_;
     length = b.length;
+    capacity = b.capacity;
+    if (b.is_inline()) {
+      adopt_inline(b);
+    }
     b.data_ = nullptr;
     return *this;
   }
-  vector(T *d, size_t l) : data_(d), length(l) {}
-  T const *data() const { return data_; }
-  T *data() { return data_; }
+  vector(T *d, length_type l) : data_(d), length(l), capacity(l) {}
+  // adopt `len` constructed elements in storage for `cap` elements, which
+  // has to come from wit_malloc (the allocator of cabi_realloc): the vector
+  // destroys the elements and frees the storage with wit_free, or passes it
+  // to the other side of the canonical ABI without copying
+  static vector<T> from_parts(T *d, length_type len, length_type cap) {
+    assert(len <= cap && (d != nullptr || cap == 0));
+    vector<T> result(d, len);
+    result.capacity = cap;
+    return result;
+  }
+  // uninitialized storage, one element per T (so list<bool> is not bit-packed)
+  static vector<T> allocate(length_type len) {
+    return vector<T>(uninitialized{}, len, len);
+  }
+  // construct an element within allocated storage
+  void initialize(size_t n, T &&elem) { new ((void *)(data_ + n)) T(std::move(elem)); }
+  // storage for `cap` elements without any constructed, so that it can be
+  // destroyed at any point while it is being filled
+  static vector<T> with_capacity(length_type cap) {
+    return vector<T>(uninitialized{}, cap, 0);
+  }
+  // construct the next element, within the capacity
+  void push_back_unchecked(T &&elem) {
+    new ((void *)(data_ + length++)) T(std::move(elem));
+  }
+  // the contiguous elements, e.g. for C APIs taking a pointer and size(),
+  // valid until the vector is appended to, moved from or destroyed
+  T const *data() const WIT_LIFETIMEBOUND { return data_; }
+  T *data() WIT_LIFETIMEBOUND { return data_; }
   T &operator[](size_t n) { return data_[n]; }
   T const &operator[](size_t n) const { return data_[n]; }
-  size_t size() const { return length; }
-  ~vector() {
-    if (data_) {
-      free(data_);
-    }
-  }
+  length_type size() const { return length; }
+  ~vector() { destroy(); }
   // leak the memory
   void leak() { data_ = nullptr; }
+  // leak the memory to pass it to the other side of the canonical ABI,
+  // inline elements are moved to the heap first
+  T *release() {
+    if (is_inline()) {
+      T *d = length > 0 ? (T *)wit_malloc(sizeof(T) * length) : nullptr;
+      for (size_t i = 0; i < length; ++i) {
+        new ((void *)(d + i)) T(std::move(data_[i]));
+        data_[i].~T();
+      }
+      data_ = d;
+    }
+    T *result = data_;
+    data_ = nullptr;
+    return result;
+  }
   // typically called by post
-  static void drop_raw(void *ptr) { free(ptr); }
-  wit::span<T> get_view() const { return wit::span<T>(data_, length); }
+  static void drop_raw(void *ptr) { wit_free(ptr); }
+  wit::span<T> get_view() const WIT_LIFETIMEBOUND {
+    return wit::span<T>(data_, length);
+  }
+  // append copies of the elements of `range` (anything with size, begin and
+  // end, e.g. a span), the storage at least doubles when it grows
+  template <class R> void append_range(R const &range) {
+    length_type n = length + length_type(range.size());
+    T *d = data_;
+    length_type cap = capacity;
+    if (n > capacity) {
+      cap = capacity * 2 > n ? capacity * 2 : n;
+      d = storage(cap);
+      if (d == inline_data()) {
+        cap = WIT_VECTOR_INLINE;
+      }
+    }
+    // copy first, range may refer to our own elements
+    length_type end = length;
+    for (auto const &elem : range) {
+      new ((void *)(d + end++)) T(elem);
+    }
+    if (d != data_) {
+      for (size_t i = 0; i < length; ++i) {
+        new ((void *)(d + i)) T(std::move(data_[i]));
+      }
+      destroy();
+      data_ = d;
+      capacity = cap;
+    }
+    length = n;
+  }
+  void append(vector const &b) { append_range(b.get_view()); }
+  // the elements as a std::vector, which can't adopt this storage: each
+  // element is moved (or copied, if trivial) once and the storage is freed
+  std::vector<T> into_std() && {
+    std::vector<T> result;
+    if constexpr (std::is_trivially_copyable<T>::value) {
+      result.assign(data_, data_ + length);
+    } else {
+      result.reserve(length);
+      for (size_t i = 0; i < length; ++i) {
+        result.push_back(std::move(data_[i]));
+      }
+    }
+    destroy();
+    data_ = nullptr;
+    length = 0;
+    capacity = 0;
+    return result;
+  }
+  void swap(vector &b) noexcept {
+    if (is_inline() || b.is_inline()) {
+      vector tmp(std::move(b));
+      b = std::move(*this);
+      *this = std::move(tmp);
+      return;
+    }
+    std::swap(data_, b.data_);
+    std::swap(length, b.length);
+    std::swap(capacity, b.capacity);
+  }
+  friend void swap(vector &a, vector &b) noexcept { a.swap(b); }
+};
+
+/// @brief Storage reused by the lowering of repeated calls
+///
+/// Generated for `--reuse-lowering-buffer`, one per call site. It only grows
+/// and is released when the thread exits.
+class scratch_buffer {
+  void *data_;
+  size_t capacity;
+
+public:
+  scratch_buffer() : data_(nullptr), capacity(0) {}
+  scratch_buffer(scratch_buffer const &) = delete;
+  scratch_buffer &operator=(scratch_buffer const &) = delete;
+  ~scratch_buffer() { wit_free(data_); }
+  // at least `size` bytes, the previous contents are discarded
+  void *reserve(size_t size) {
+    if (size > capacity) {
+      wit_free(data_);
+      data_ = wit_malloc(size);
+      capacity = size;
+    }
+    return data_;
+  }
 };
 
 /// @brief  A Resource defined within the guest (guest side)
@@ -115,7 +446,7 @@ public:
       }
     }
   };
-  typedef std::unique_ptr<R, Deregister> Owned;
+  typedef owned_ptr<R, Deregister> Owned;
 
 #ifdef WIT_SYMMETRIC
   typedef uint8_t *handle_t;
@@ -160,18 +491,21 @@ protected:
 
 public:
   ResourceImportBase(handle_t h = invalid) : handle(h) {}
-  ResourceImportBase(ResourceImportBase &&r) : handle(r.handle) {
+  ResourceImportBase(ResourceImportBase &&r) noexcept : handle(r.handle) {
     r.handle = invalid;
   }
   ResourceImportBase(ResourceImportBase const &) = delete;
   void set_handle(handle_t h) { handle = h; }
   handle_t get_handle() const { return handle; }
+  // false once the handle was moved out, dropped or reset
+  bool has_value() const { return handle != invalid; }
+  explicit operator bool() const { return has_value(); }
   handle_t into_handle() {
     handle_t h = handle;
     handle = invalid;
     return h;
   }
-  ResourceImportBase &operator=(ResourceImportBase &&r) {
+  ResourceImportBase &operator=(ResourceImportBase &&r) noexcept {
     assert(handle == invalid);
     handle = r.handle;
     r.handle = invalid;
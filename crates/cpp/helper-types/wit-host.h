@@ -6,6 +6,7 @@
 #include <stdint.h>
 #include <string.h>
 #include <string_view>
+#include <utility> // forward
 
 #ifndef WIT_HOST_DIRECT
 #define WIT_HOST_WAMR
@@ -149,8 +150,9 @@ public:
 #endif
     }
   }
-  guest_owned(guest_owned &&b)
-      : T(b), data_(b.data_), free_func(b.free_func)
+  guest_owned(guest_owned &&b) noexcept(
+      std::is_nothrow_move_constructible<T>::value)
+      : T(std::move(b)), data_(b.data_), free_func(b.free_func)
 #ifdef WIT_HOST_WAMR
         ,
         exec_env(b.exec_env)
@@ -190,21 +192,23 @@ protected:
   int32_t index;
 
 public:
-  ResourceExportBase() : rep(nullptr), index(-1) {}
+  ResourceExportBase() : rep(0), index(-1) {}
   ResourceExportBase(int32_t i) : rep(*lookup_resource(i)), index(i) {}
-  ResourceExportBase(ResourceExportBase &&b) : rep(b.rep), index(b.index) {
+  ResourceExportBase(ResourceExportBase &&b) noexcept
+      : rep(b.rep), index(b.index) {
     b.rep = 0;
   }
   ResourceExportBase(ResourceExportBase const &) = delete;
   ResourceExportBase &operator=(ResourceExportBase const &) = delete;
-  ResourceExportBase &operator=(ResourceExportBase &&b) {
+  ResourceExportBase &operator=(ResourceExportBase &&b) noexcept {
     assert(rep == 0);
     rep = b.rep;
     index = b.index;
     b.rep = 0;
+    return *this;
   }
   ~ResourceExportBase() {
-    if (index >= 0 && rep != nullptr) {
+    if (index >= 0 && rep != 0) {
       remove_resource(index);
     }
   }
@@ -225,7 +229,7 @@ public:
   struct Deleter {
     void operator()(R *ptr) const { R::Dtor(ptr); }
   };
-  typedef std::unique_ptr<R, Deleter> Owned;
+  typedef owned_ptr<R, Deleter> Owned;
 
   static const int32_t invalid = -1;
   ResourceImportBase() : index(this->store_resource((R *)this)) {}
@@ -235,6 +239,16 @@ public:
   ResourceImportBase &operator=(ResourceImportBase const &) = delete;
   ResourceImportBase &operator=(ResourceImportBase &&) = delete;
   int32_t get_handle() { return index; }
+  /// Called when the guest drops its handle, before the Dtor
+  ///
+  /// Hide this in R to run additional cleanup
+  void on_drop() {}
+  /// Construct a resource and return the handle of its table entry
+  ///
+  /// Uses plain new, matching the default Dtor
+  template <class... Args> static int32_t emplace(Args &&...args) {
+    return (new R(std::forward<Args>(args)...))->get_handle();
+  }
 };
 
 /// Host representation of a resource defined in another component
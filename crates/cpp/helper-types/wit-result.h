@@ -0,0 +1,86 @@
+#pragma once
+
+#include <expected>
+#include <type_traits>
+#include <utility> // std::move
+
+namespace wit {
+/// @brief The generated result type (see `--wit-result`)
+///
+/// A `std::expected` with the combinators of Rust's `Result`,
+/// `value_or` is inherited.
+template <class T, class E> class result : public std::expected<T, E> {
+  typedef std::expected<T, E> base;
+
+  // a result holding the value of calling f
+  template <class U, class F, class... A>
+  static result<U, E> from_call(F &&f, A &&...a) {
+    if constexpr (std::is_void<U>::value) {
+      std::forward<F>(f)(std::forward<A>(a)...);
+      return result<U, E>();
+    } else {
+      return result<U, E>(std::forward<F>(f)(std::forward<A>(a)...));
+    }
+  }
+
+  template <class Self, class F> static auto map_impl(Self &&self, F &&f) {
+    if constexpr (std::is_void<T>::value) {
+      typedef std::invoke_result_t<F> U;
+      if (!self.has_value()) {
+        return result<U, E>(std::unexpected<E>(std::forward<Self>(self).error()));
+      }
+      return from_call<U>(std::forward<F>(f));
+    } else {
+      typedef std::invoke_result_t<F, decltype(*std::forward<Self>(self))> U;
+      if (!self.has_value()) {
+        return result<U, E>(std::unexpected<E>(std::forward<Self>(self).error()));
+      }
+      return from_call<U>(std::forward<F>(f), *std::forward<Self>(self));
+    }
+  }
+
+  template <class Self, class F> static auto map_err_impl(Self &&self, F &&f) {
+    typedef std::invoke_result_t<F, decltype(std::forward<Self>(self).error())> G;
+    if (self.has_value()) {
+      if constexpr (std::is_void<T>::value) {
+        return result<T, G>();
+      } else {
+        return result<T, G>(*std::forward<Self>(self));
+      }
+    }
+    return result<T, G>(std::unexpected<G>(
+        std::forward<F>(f)(std::forward<Self>(self).error())));
+  }
+
+public:
+  using base::base;
+  result() = default;
+  result(base &&b) : base(std::move(b)) {}
+
+  /// @brief The error, or `other` if this holds a value
+  template <class U> E error_or(U &&other) const & {
+    return this->has_value() ? static_cast<E>(std::forward<U>(other))
+                             : this->error();
+  }
+  template <class U> E error_or(U &&other) && {
+    return this->has_value() ? static_cast<E>(std::forward<U>(other))
+                             : std::move(this->error());
+  }
+
+  /// @brief Transform the value with `f`, keeping the error
+  template <class F> auto map(F &&f) const & {
+    return map_impl(*this, std::forward<F>(f));
+  }
+  template <class F> auto map(F &&f) && {
+    return map_impl(std::move(*this), std::forward<F>(f));
+  }
+
+  /// @brief Transform the error with `f`, keeping the value
+  template <class F> auto map_err(F &&f) const & {
+    return map_err_impl(*this, std::forward<F>(f));
+  }
+  template <class F> auto map_err(F &&f) && {
+    return map_err_impl(std::move(*this), std::forward<F>(f));
+  }
+};
+} // namespace wit
@@ -0,0 +1,187 @@
+use std::fmt::Write;
+use wit_bindgen_core::wit_parser::{
+    Function, Handle, InterfaceId, Resolve, Results, Type, TypeDefKind, TypeId, WorldId, WorldItem,
+};
+
+/// Describe the types and functions of a world for tooling
+pub fn describe_world(resolve: &Resolve, world_id: WorldId) -> String {
+    let world = &resolve.worlds[world_id];
+    let mut types = Vec::new();
+    let mut functions = Vec::new();
+    for (direction, items) in [("import", &world.imports), ("export", &world.exports)] {
+        for (key, item) in items.iter() {
+            match item {
+                WorldItem::Interface { id, .. } => {
+                    let interface = resolve.name_world_key(key);
+                    describe_interface(resolve, *id, &interface, &mut types);
+                    for func in resolve.interfaces[*id].functions.values() {
+                        functions.push(describe_function(
+                            resolve,
+                            func,
+                            Some(&interface),
+                            direction,
+                        ));
+                    }
+                }
+                WorldItem::Function(func) => {
+                    functions.push(describe_function(resolve, func, None, direction));
+                }
+                WorldItem::Type(id) => types.push(describe_type(resolve, *id, None)),
+            }
+        }
+    }
+    let mut out = String::new();
+    let _ = writeln!(out, "{{");
+    let _ = writeln!(out, "  \"world\": \"{}\",", world.name);
+    let _ = writeln!(out, "  \"types\": [{}],", join_entries(&types));
+    let _ = writeln!(out, "  \"functions\": [{}]", join_entries(&functions));
+    let _ = writeln!(out, "}}");
+    out
+}
+
+fn join_entries(entries: &[String]) -> String {
+    if entries.is_empty() {
+        String::new()
+    } else {
+        format!("\n    {}\n  ", entries.join(",\n    "))
+    }
+}
+
+fn interface_field(interface: Option<&str>) -> String {
+    match interface {
+        Some(name) => format!("\"{name}\""),
+        None => "null".into(),
+    }
+}
+
+fn describe_interface(
+    resolve: &Resolve,
+    id: InterfaceId,
+    interface: &str,
+    types: &mut Vec<String>,
+) {
+    for ty in resolve.interfaces[id].types.values() {
+        types.push(describe_type(resolve, *ty, Some(interface)));
+    }
+}
+
+fn describe_type(resolve: &Resolve, id: TypeId, interface: Option<&str>) -> String {
+    let ty = &resolve.types[id];
+    let kind = match &ty.kind {
+        TypeDefKind::Record(_) => "record",
+        TypeDefKind::Resource => "resource",
+        TypeDefKind::Handle(_) => "handle",
+        TypeDefKind::Flags(_) => "flags",
+        TypeDefKind::Tuple(_) => "tuple",
+        TypeDefKind::Variant(_) => "variant",
+        TypeDefKind::Enum(_) => "enum",
+        TypeDefKind::Option(_) => "option",
+        TypeDefKind::Result(_) => "result",
+        TypeDefKind::List(_) => "list",
+        TypeDefKind::Future(_) => "future",
+        TypeDefKind::Stream(_) => "stream",
+        TypeDefKind::Type(_) => "alias",
+        TypeDefKind::Unknown => "unknown",
+    };
+    format!(
+        "{{ \"name\": \"{}\", \"interface\": {}, \"kind\": \"{kind}\" }}",
+        ty.name.as_deref().unwrap_or_default(),
+        interface_field(interface),
+    )
+}
+
+fn describe_function(
+    resolve: &Resolve,
+    func: &Function,
+    interface: Option<&str>,
+    direction: &str,
+) -> String {
+    let params = func
+        .params
+        .iter()
+        .map(|(name, ty)| {
+            format!(
+                "{{ \"name\": \"{name}\", \"type\": \"{}\" }}",
+                wit_type_name(resolve, ty)
+            )
+        })
+        .collect::<Vec<_>>();
+    let results = match &func.results {
+        Results::Named(named) => named
+            .iter()
+            .map(|(name, ty)| {
+                format!(
+                    "{{ \"name\": \"{name}\", \"type\": \"{}\" }}",
+                    wit_type_name(resolve, ty)
+                )
+            })
+            .collect::<Vec<_>>(),
+        Results::Anon(ty) => vec![format!(
+            "{{ \"type\": \"{}\" }}",
+            wit_type_name(resolve, ty)
+        )],
+    };
+    format!(
+        "{{ \"name\": \"{}\", \"interface\": {}, \"direction\": \"{direction}\", \"params\": [{}], \"results\": [{}] }}",
+        func.name,
+        interface_field(interface),
+        params.join(", "),
+        results.join(", "),
+    )
+}
+
+// the type as written in WIT
+fn wit_type_name(resolve: &Resolve, ty: &Type) -> String {
+    match ty {
+        Type::Bool => "bool".into(),
+        Type::U8 => "u8".into(),
+        Type::U16 => "u16".into(),
+        Type::U32 => "u32".into(),
+        Type::U64 => "u64".into(),
+        Type::S8 => "s8".into(),
+        Type::S16 => "s16".into(),
+        Type::S32 => "s32".into(),
+        Type::S64 => "s64".into(),
+        Type::F32 => "f32".into(),
+        Type::F64 => "f64".into(),
+        Type::Char => "char".into(),
+        Type::String => "string".into(),
+        Type::Id(id) => {
+            let ty = &resolve.types[*id];
+            if let Some(name) = &ty.name {
+                return name.clone();
+            }
+            let optional = |ty: &Option<Type>| match ty {
+                Some(ty) => wit_type_name(resolve, ty),
+                None => "_".into(),
+            };
+            match &ty.kind {
+                TypeDefKind::List(ty) => format!("list<{}>", wit_type_name(resolve, ty)),
+                TypeDefKind::Option(ty) => format!("option<{}>", wit_type_name(resolve, ty)),
+                TypeDefKind::Result(r) => {
+                    format!("result<{}, {}>", optional(&r.ok), optional(&r.err))
+                }
+                TypeDefKind::Tuple(t) => format!(
+                    "tuple<{}>",
+                    t.types
+                        .iter()
+                        .map(|ty| wit_type_name(resolve, ty))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                TypeDefKind::Handle(Handle::Own(id)) => {
+                    format!("own<{}>", wit_type_name(resolve, &Type::Id(*id)))
+                }
+                TypeDefKind::Handle(Handle::Borrow(id)) => {
+                    format!("borrow<{}>", wit_type_name(resolve, &Type::Id(*id)))
+                }
+                TypeDefKind::Future(ty) => format!("future<{}>", optional(ty)),
+                TypeDefKind::Stream(s) => {
+                    format!("stream<{}, {}>", optional(&s.element), optional(&s.end))
+                }
+                TypeDefKind::Type(ty) => wit_type_name(resolve, ty),
+                _ => "unknown".into(),
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use heck::{ToPascalCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use sha2::{Digest, Sha256};
 use std::{
     collections::{HashMap, HashSet},
     fmt::Write as FmtWrite,
@@ -11,12 +12,14 @@ use wit_bindgen_core::{
     abi::{self, AbiVariant, Bindgen, Bitcast, LiftLower, WasmSignature, WasmType},
     make_external_component, make_external_symbol, uwrite, uwriteln,
     wit_parser::{
-        AddressSize, Docs, Function, FunctionKind, Handle, Int, InterfaceId, Resolve, Results,
-        SizeAlign, Stability, Type, TypeDefKind, TypeId, TypeOwner, WorldId, WorldKey,
+        AddressSize, Docs, Enum, EnumCase, FlagsRepr, Function, FunctionKind, Handle, Int,
+        InterfaceId, Record, Resolve, Results, SizeAlign, Stability, Type, TypeDefKind, TypeId,
+        TypeOwner, Variant, WorldId, WorldItem, WorldKey,
     },
     Files, InterfaceGenerator, Source, WorldGenerator,
 };
 
+mod json;
 mod wamr;
 
 pub const RESOURCE_IMPORT_BASE_CLASS_NAME: &str = "ResourceImportBase";
@@ -25,6 +28,9 @@ pub const RESOURCE_TABLE_NAME: &str = "ResourceTable";
 pub const OWNED_CLASS_NAME: &str = "Owned";
 // these types are always defined in the non-exports namespace
 const NOT_IN_EXPORTED_NAMESPACE: bool = false;
+// larger return areas of imports go to the heap instead of the stack, those
+// of exports are static (see `return_pointer`)
+const MAX_STACK_RET_AREA: usize = 1024;
 
 type CppType = String;
 
@@ -43,6 +49,15 @@ impl Flavor {
             Flavor::InStruct => false,
         }
     }
+
+    /// The flavor of a type nested within an option, result, tuple or list,
+    /// exported arguments are only passed by rvalue reference at the top level
+    fn nested(self) -> Flavor {
+        match self {
+            Flavor::Argument(AbiVariant::GuestExport) => Flavor::InStruct,
+            flavor => flavor,
+        }
+    }
 }
 
 #[derive(Default)]
@@ -57,6 +72,8 @@ struct HighlevelSignature {
     namespace: Vec<String>,
     implicit_self: bool,
     post_return: bool,
+    /// the exception specification, if one is generated (`--noexcept`)
+    noexcept: Option<bool>,
 }
 
 // follows https://google.github.io/styleguide/cppguide.html
@@ -78,6 +95,15 @@ struct Includes {
     // needs wit types
     needs_wit: bool,
     needs_memory: bool,
+    needs_compare: bool,
+    needs_chrono: bool,
+    needs_array: bool,
+    needs_system_error: bool,
+    needs_type_traits: bool,
+    needs_dump: bool,
+    needs_absl_string_view: bool,
+    needs_absl_span: bool,
+    needs_functional: bool,
 }
 
 #[derive(Clone)]
@@ -87,6 +113,25 @@ struct HostFunction {
     host_name: String,
 }
 
+// a method of the `--flat-facade` struct, named after the function unless
+// another interface has a function of the same name
+struct FacadeMethod {
+    // snake case interface name, `exports_` prefixed for exports
+    interface: String,
+    name: String,
+    // parameters and body following the name
+    rest: String,
+}
+
+// the methods of `Instance` calling the exports of one interface
+struct InstanceProxy {
+    // snake case name of the accessor, e.g. `test_flavorful_test`
+    accessor: String,
+    interface: String,
+    // declarations and definitions
+    methods: Vec<(String, String)>,
+}
+
 #[derive(Default)]
 struct SourceWithState {
     src: Source,
@@ -110,6 +155,21 @@ struct Cpp {
     imported_interfaces: HashSet<InterfaceId>,
     user_class_files: HashMap<String, String>,
     defined_types: HashSet<(Vec<String>, String)>,
+    // type and name of the function pointer members of the ImportVTable
+    import_vtable: Vec<(String, String)>,
+    // the std::function slots of `--import-context` and their names
+    import_context: Vec<(String, String)>,
+    // the methods of the `--flat-facade` struct
+    facade: Vec<FacadeMethod>,
+    // declarations and definitions of the Instance methods
+    instance_methods: Vec<(String, String)>,
+    // declarations and definitions of the `--abi=preview1-compat` functions
+    preview1_adapters: Vec<(String, String)>,
+    instance_proxies: Vec<InstanceProxy>,
+    // declarations and loop statements of `<world>_bench.cpp`
+    bench: Vec<(String, String)>,
+    // namespace and declaration of the types for `<world>_fwd.h`
+    forward_declarations: Vec<(Vec<String>, String)>,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -165,6 +225,399 @@ impl core::fmt::Display for Ownership {
     }
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum ScalarTypes {
+    /// Always use exact-width integer types like `uint32_t`.
+    #[default]
+    Exact,
+
+    /// Use `uint_fast32_t` and friends for function parameters and
+    /// results, record fields keep their exact-width types.
+    Fast,
+}
+
+impl FromStr for ScalarTypes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(Self::Exact),
+            "fast" => Ok(Self::Fast),
+            _ => Err(format!(
+                "unrecognized scalar types: `{s}`; expected `exact` or `fast`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for ScalarTypes {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            ScalarTypes::Exact => "exact",
+            ScalarTypes::Fast => "fast",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirLayout {
+    /// Write all headers into the output directory, the namespace is
+    /// joined with dashes.
+    #[default]
+    Flat,
+
+    /// Write headers into nested directories mirroring the package path.
+    PackagePath,
+}
+
+impl FromStr for DirLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flat" => Ok(Self::Flat),
+            "package-path" => Ok(Self::PackagePath),
+            _ => Err(format!(
+                "unrecognized dir layout: `{s}`; expected `flat` or `package-path`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for DirLayout {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            DirLayout::Flat => "flat",
+            DirLayout::PackagePath => "package-path",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BareError {
+    /// `std::expected<T, wit::Void>`
+    #[default]
+    Expected,
+
+    /// `std::optional<T>`
+    Optional,
+}
+
+impl FromStr for BareError {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "expected" => Ok(Self::Expected),
+            "optional" => Ok(Self::Optional),
+            _ => Err(format!(
+                "unrecognized bare error: `{s}`; expected `expected` or `optional`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for BareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            BareError::Expected => "expected",
+            BareError::Optional => "optional",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostStringType {
+    /// `wit::string` already allocated in guest memory
+    #[default]
+    Wit,
+
+    /// `std::string`, copied into guest memory by the wrapper
+    Std,
+
+    /// `std::wstring`, converted from and to UTF-8 by the wrapper
+    Wstring,
+}
+
+impl FromStr for HostStringType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wit" => Ok(Self::Wit),
+            "std" => Ok(Self::Std),
+            "wstring" => Ok(Self::Wstring),
+            _ => Err(format!(
+                "unrecognized host string type: `{s}`; expected `wit`, `std` or `wstring`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for HostStringType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            HostStringType::Wit => "wit",
+            HostStringType::Std => "std",
+            HostStringType::Wstring => "wstring",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportStyle {
+    /// Methods of an `Instance` class owning the module instance
+    #[default]
+    Instance,
+
+    /// `static` methods of a struct named after the world, taking the
+    /// execution environment
+    StaticMethods,
+}
+
+impl FromStr for ExportStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "instance" => Ok(Self::Instance),
+            "static-methods" => Ok(Self::StaticMethods),
+            _ => Err(format!(
+                "unrecognized export style: `{s}`; expected `instance` or `static-methods`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for ExportStyle {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            ExportStyle::Instance => "instance",
+            ExportStyle::StaticMethods => "static-methods",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthType {
+    /// `size_t`, the native size
+    #[default]
+    Size,
+
+    /// `uint32_t`, the length as passed by the canonical ABI
+    U32,
+
+    /// `uint64_t`, wide enough for 64-bit memories
+    U64,
+}
+
+impl LengthType {
+    // the C++ type, None keeps the default of the helper types
+    fn cpp_type(&self) -> Option<&'static str> {
+        match self {
+            LengthType::Size => None,
+            LengthType::U32 => Some("uint32_t"),
+            LengthType::U64 => Some("uint64_t"),
+        }
+    }
+}
+
+impl FromStr for LengthType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "size" => Ok(Self::Size),
+            "u32" => Ok(Self::U32),
+            "u64" => Ok(Self::U64),
+            _ => Err(format!(
+                "unrecognized length type: `{s}`; expected `size`, `u32` or `u64`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for LengthType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            LengthType::Size => "size",
+            LengthType::U32 => "u32",
+            LengthType::U64 => "u64",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanCanon {
+    /// Replace any NaN by the canonical NaN
+    #[default]
+    On,
+
+    /// Pass the bits of the NaN unchanged
+    Off,
+}
+
+impl FromStr for NanCanon {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "on" => Ok(Self::On),
+            "off" => Ok(Self::Off),
+            _ => Err(format!(
+                "unrecognized NaN canonicalization: `{s}`; expected `on` or `off`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for NanCanon {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            NanCanon::On => "on",
+            NanCanon::Off => "off",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CppStd {
+    /// C++17, with `wit::expected` in place of `std::expected`
+    Cpp17,
+
+    /// C++20, with `wit::expected` in place of `std::expected`
+    Cpp20,
+
+    /// C++23
+    #[default]
+    Cpp23,
+}
+
+impl FromStr for CppStd {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c++17" => Ok(Self::Cpp17),
+            "c++20" => Ok(Self::Cpp20),
+            "c++23" => Ok(Self::Cpp23),
+            _ => Err(format!(
+                "unrecognized C++ standard: `{s}`; expected `c++17`, `c++20` or `c++23`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for CppStd {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            CppStd::Cpp17 => "c++17",
+            CppStd::Cpp20 => "c++20",
+            CppStd::Cpp23 => "c++23",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyPtr {
+    /// A null pointer
+    #[default]
+    Null,
+
+    /// A non-null pointer aligned for the elements, like the one returned
+    /// by `cabi_realloc` for zero bytes
+    Dangling,
+}
+
+impl FromStr for EmptyPtr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "null" => Ok(Self::Null),
+            "dangling" => Ok(Self::Dangling),
+            _ => Err(format!(
+                "unrecognized empty pointer: `{s}`; expected `null` or `dangling`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for EmptyPtr {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            EmptyPtr::Null => "null",
+            EmptyPtr::Dangling => "dangling",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containers {
+    /// `std::string_view` and `wit::span`
+    #[default]
+    Std,
+
+    /// `absl::string_view` and `absl::Span`
+    Absl,
+}
+
+impl FromStr for Containers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "std" => Ok(Self::Std),
+            "absl" => Ok(Self::Absl),
+            _ => Err(format!(
+                "unrecognized containers: `{s}`; expected `std` or `absl`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for Containers {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Containers::Std => "std",
+            Containers::Absl => "absl",
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abi {
+    /// Only the canonical ABI bindings
+    #[default]
+    Canonical,
+
+    /// Additionally preview1 style C functions for scalar imports
+    Preview1Compat,
+}
+
+impl FromStr for Abi {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "canonical" => Ok(Self::Canonical),
+            "preview1-compat" => Ok(Self::Preview1Compat),
+            _ => Err(format!(
+                "unrecognized abi: `{s}`; expected `canonical` or `preview1-compat`"
+            )),
+        }
+    }
+}
+
+impl core::fmt::Display for Abi {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Abi::Canonical => "canonical",
+            Abi::Preview1Compat => "preview1-compat",
+        })
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 #[cfg_attr(feature = "clap", derive(clap::Args))]
 pub struct Opts {
@@ -179,45 +632,398 @@ pub struct Opts {
     pub format: bool,
     /// 64bit guest
     #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
-    pub wasm64: bool,
+    pub wasm64: bool,
+
+    /// Place each interface in its own file,
+    /// this enables sharing bindings across projects
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub split_interfaces: bool,
+
+    /// Optionally prefix any export names with the specified value.
+    ///
+    /// This is useful to avoid name conflicts when testing.
+    #[cfg_attr(feature = "clap", arg(long))]
+    pub export_prefix: Option<String>,
+
+    /// Wrap all C++ classes inside a custom namespace.
+    ///
+    /// This avoids identical names across components, useful for native
+    #[cfg_attr(feature = "clap", arg(long))]
+    pub internal_prefix: Option<String>,
+
+    /// Whether to generate owning or borrowing type definitions.
+    ///
+    /// Valid values include:
+    ///
+    /// - `owning`: Generated types will be composed entirely of owning fields,
+    /// regardless of whether they are used as parameters to imports or not.
+    ///
+    /// - `borrowing`: Generated types used as parameters to imports will be
+    /// "deeply borrowing", i.e. contain references rather than owned values
+    /// when applicable.
+    ///
+    /// - `borrowing-duplicate-if-necessary`: As above, but generating distinct
+    /// types for borrowing and owning, if necessary.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = Ownership::Owning))]
+    pub ownership: Ownership,
+
+    /// Symmetric ABI, this enables to directly link components to each
+    /// other and removes the primary distinction between host and guest.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub symmetric: bool,
+
+    /// Generate a defaulted `operator<=>` for records whose fields are all
+    /// three-way comparable (requires C++20)
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub derive_ord: bool,
+
+    /// Which integer types to use for function parameters and results.
+    ///
+    /// Valid values include:
+    ///
+    /// - `exact`: Exact-width types like `uint32_t` everywhere.
+    ///
+    /// - `fast`: `uint_fast32_t` and friends for parameters and results,
+    ///   record fields always stay exact-width to match the ABI.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = ScalarTypes::Exact))]
+    pub scalar_types: ScalarTypes,
+
+    /// Rename the C++ function of a single WIT function, e.g.
+    /// `--rename-func test.flavorful.test/errno-result=check_errno`
+    #[cfg_attr(feature = "clap", arg(long, name = "FUNC=NAME", value_parser = parse_rename))]
+    pub rename_func: Vec<(String, String)>,
+
+    /// Rename the C++ type of a single WIT record, variant, enum, flags or
+    /// type alias, e.g. `--rename-type test.flavorful.test/my-errno=Errno`
+    #[cfg_attr(feature = "clap", arg(long, name = "TYPE=NAME", value_parser = parse_rename))]
+    pub rename_type: Vec<(String, String)>,
+
+    /// Emit `using OldName = NewName;` for types renamed by `--rename-type`,
+    /// so code written against the old name keeps compiling
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub emit_compat_typedefs: bool,
+
+    /// Generate `std::chrono` conversions for records shaped like a WASI
+    /// datetime (`seconds: u64, nanoseconds: u32`)
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub chrono_conversions: bool,
+
+    /// Where to place the headers of split interfaces and resources.
+    ///
+    /// Valid values include:
+    ///
+    /// - `flat`: `test-flavorful-test.h` next to the world header.
+    ///
+    /// - `package-path`: `test/flavorful/test.h`, include paths are
+    ///   relative to the output directory.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = DirLayout::Flat))]
+    pub dir_layout: DirLayout,
+
+    /// Dispatch host side imports through the function pointers of a
+    /// settable `ImportVTable` instead of calling them directly, its
+    /// `is_complete()` tells whether every import has an implementation
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub import_vtable: bool,
+
+    /// Dispatch host side imports through `std::function` slots of an
+    /// `ImportContext`, attached to the execution environment which calls
+    /// them (see `Instance::set_import_context`), so lambdas can implement
+    /// the imports of each instance
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub import_context: bool,
+
+    /// Also generate a `World` struct with a `static` method for every
+    /// function of the imported and exported interfaces, named without the
+    /// interface unless two interfaces use the same name
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub flat_facade: bool,
+
+    /// End enum and flags case lists and the record initializers of
+    /// lifted values with a comma, so adding a case or field only adds a line
+    /// to the diff of the generated code
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub trailing_commas: bool,
+
+    /// Also emit a `constexpr` array of the core export names of the world's
+    /// exported functions, indexed in the order of the world, so tools can
+    /// symbolicate core dumps
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub export_name_table: bool,
+
+    /// Pass imported resources with a single method and no constructor or
+    /// static functions (callbacks) to the implementation of exports as a
+    /// `std::function` calling that method, e.g. for registering event
+    /// handlers of the host
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub callback_resources: bool,
+
+    /// Also write a `<world>_bench.cpp` with a `main` calling each export
+    /// through the canonical ABI in a loop, to profile the lifting and
+    /// lowering overhead. Exports passing resources or more parameters than
+    /// fit into core wasm parameters are left out.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub emit_bench: bool,
+
+    /// Mark the accessors of owned strings and lists returning views into
+    /// them, like `get_view`, `substr` and `data`, with
+    /// `[[clang::lifetimebound]]`, so Clang warns about dangling views
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub lifetimebound: bool,
+
+    /// Also write a `<world>.json` describing the types and functions
+    /// of the world for tooling
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub json: bool,
+
+    /// How to represent a `result<T>` without error payload.
+    ///
+    /// Valid values include:
+    ///
+    /// - `expected`: `std::expected<T, wit::Void>`.
+    ///
+    /// - `optional`: `std::optional<T>`, an empty optional is the error.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = BareError::Expected))]
+    pub bare_error: BareError,
+
+    /// Specialize `wit::layout<T>` for each named type with its canonical
+    /// ABI size, alignment and flattened core wasm types
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub layouts: bool,
+
+    /// Generate a `constexpr` array of `{value, name}` pairs for each enum,
+    /// in declaration order, for lookups in both directions
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub enum_reflection: bool,
+
+    /// Make enums used as the error of a result usable as `std::error_code`,
+    /// with a `std::error_category` naming the cases
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub error_code: bool,
+
+    /// String parameters of the host side `Instance` methods.
+    ///
+    /// Valid values include:
+    ///
+    /// - `wit`: `wit::string` in guest memory, no copy.
+    ///
+    /// - `std`: `std::string`, copied into the guest on each call.
+    ///
+    /// - `wstring`: `std::wstring` for Windows interop, each call converts
+    ///   between its UTF-16 (UTF-32 where `wchar_t` has 32 bits) and the
+    ///   UTF-8 of the canonical ABI. A `string` result is converted as well,
+    ///   and the host implements imports with `std::wstring` parameters and
+    ///   results.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = HostStringType::Wit))]
+    pub host_string_type: HostStringType,
+
+    /// Bracket each generated file with `// NOLINTBEGIN` and `// NOLINTEND`
+    /// to silence clang-tidy in consuming projects
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub emit_nolint: bool,
+
+    /// Declare imported functions `noexcept` unless their lowering allocates,
+    /// these are marked `noexcept(false)` as the allocation can abort
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub noexcept: bool,
+
+    /// Use `wit::result<T, E>` for results, a `std::expected` with
+    /// `error_or`, `map` and `map_err` (from `wit-result.h`)
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub wit_result: bool,
+
+    /// Reference a symbol named after a hash of the world's ABI from each
+    /// file including the header, so that linking against bindings
+    /// generated from a different WIT fails
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub abi_link_check: bool,
+
+    /// Start each generated file with a `// wit-hash:` comment holding the
+    /// SHA-256 of the resolved WIT, to detect when regeneration is needed
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub emit_wit_hash: bool,
+
+    /// Add an inline overload of imported functions with `list<bool>` or
+    /// `list<string>` parameters, taking `std::vector<T> const&` for each list
+    /// and copying into the views the import expects
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub vector_overloads: bool,
+
+    /// Treat lists of `tuple<K, V>` or of records with just `key` and `value`
+    /// fields as maps, generating `find` helpers returning a pointer to the
+    /// value of the first entry with a given key
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub map_heuristic: bool,
+
+    /// C symbol of the realloc function defined by guest bindings, instead
+    /// of `cabi_realloc`, for statically linking the bindings of several
+    /// worlds. Each set of bindings adds a weak `cabi_realloc` forwarding to
+    /// its allocator, the linker keeps one of them as the canonical export
+    #[cfg_attr(feature = "clap", arg(long))]
+    pub realloc_symbol: Option<String>,
+
+    /// How the host side groups the wrappers calling exported functions.
+    ///
+    /// Valid values include:
+    ///
+    /// - `instance`: methods of an `Instance` class.
+    ///
+    /// - `static-methods`: `static` methods of a struct named after the world.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = ExportStyle::Instance))]
+    pub export_style: ExportStyle,
+
+    /// The integer type of string and list lengths in `wit::string`,
+    /// `wit::vector` and `wit::span`.
+    ///
+    /// The helper types are shared, so all bindings of a program have to
+    /// agree on it (or `WIT_LENGTH_TYPE` is defined build-wide), headers
+    /// generated with a different length type fail a `static_assert`.
+    ///
+    /// Valid values include:
+    ///
+    /// - `size`: `size_t`.
+    ///
+    /// - `u32`: `uint32_t`.
+    ///
+    /// - `u64`: `uint64_t`.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = LengthType::Size))]
+    pub length_type: LengthType,
+
+    /// Also write a `<world>_fwd.h` forward declaring the generated types,
+    /// for headers which only need to name them
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub emit_fwd_header: bool,
+
+    /// Whether NaN payloads of `f32` and `f64` values are canonicalized
+    /// when lifting and lowering, lists of floats are always copied bitwise.
+    ///
+    /// Valid values include:
+    ///
+    /// - `on`: any NaN becomes the canonical NaN.
+    ///
+    /// - `off`: the raw bits are passed unchanged.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = NanCanon::On))]
+    pub nan_canon: NanCanon,
+
+    /// Generate a `Functor` struct calling the function of imported
+    /// interfaces with exactly one function, to pass them as function objects
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub single_func_functor: bool,
 
-    /// Place each interface in its own file,
-    /// this enables sharing bindings across projects
+    /// Lower the lists passed to imported functions into a thread local
+    /// buffer per call site, which is reused by the next call instead of
+    /// allocating each time
     #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
-    pub split_interfaces: bool,
+    pub reuse_lowering_buffer: bool,
 
-    /// Optionally prefix any export names with the specified value.
+    /// The C++ standard the bindings are compiled with, older standards use
+    /// the polyfills of `wit-expected.h` (`--wit-result` still needs
+    /// `std::expected`).
     ///
-    /// This is useful to avoid name conflicts when testing.
-    #[cfg_attr(feature = "clap", arg(long))]
-    pub export_prefix: Option<String>,
-
-    /// Wrap all C++ classes inside a custom namespace.
+    /// Valid values include:
     ///
-    /// This avoids identical names across components, useful for native
-    #[cfg_attr(feature = "clap", arg(long))]
-    pub internal_prefix: Option<String>,
+    /// - `c++17`: `wit::expected` replaces `std::expected`.
+    ///
+    /// - `c++20`: `wit::expected` replaces `std::expected`.
+    ///
+    /// - `c++23`: the standard library types.
+    #[cfg_attr(feature = "clap", arg(long = "std", default_value_t = CppStd::Cpp23))]
+    pub cpp_std: CppStd,
+
+    /// Call `<world>_init()` when the bindings are loaded, e.g. as part of a
+    /// shared library, and `<world>_fini()` when unloaded. Both are weak and
+    /// empty, define them to set up and tear down state like allocators or
+    /// resources created before the first call.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub emit_init_hooks: bool,
 
-    /// Whether to generate owning or borrowing type definitions.
+    /// Exported functions returning `list<u8>` return a
+    /// `wit::span<uint8_t const>` into memory of the implementation, which
+    /// is passed to the host without copying. The bytes have to stay valid
+    /// until the host is done with them, i.e. until the next call.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub span_byte_results: bool,
+
+    /// Qualify member accesses in generated bodies with `this->`, as
+    /// required by some lint setups.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub explicit_this: bool,
+
+    /// The pointer the guest passes for empty strings and lists, whatever
+    /// the data pointer of the C++ value.
     ///
     /// Valid values include:
     ///
-    /// - `owning`: Generated types will be composed entirely of owning fields,
-    /// regardless of whether they are used as parameters to imports or not.
+    /// - `null`: a null pointer.
     ///
-    /// - `borrowing`: Generated types used as parameters to imports will be
-    /// "deeply borrowing", i.e. contain references rather than owned values
-    /// when applicable.
+    /// - `dangling`: the alignment of the elements, never dereferenced.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = EmptyPtr::Null))]
+    pub empty_ptr: EmptyPtr,
+
+    /// Generate a `dump(std::ostream&, int indent = 0)` member for records
+    /// and variants, printing the field names and values recursively for
+    /// debugging (from `wit-dump.h`)
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub debug_dump: bool,
+
+    /// The view types of strings and lists borrowed by imported functions
+    /// on the guest side, owned values stay `wit::string` and `wit::vector`
+    /// as they hold canonical ABI memory.
     ///
-    /// - `borrowing-duplicate-if-necessary`: As above, but generating distinct
-    /// types for borrowing and owning, if necessary.
-    #[cfg_attr(feature = "clap", arg(long, default_value_t = Ownership::Owning))]
-    pub ownership: Ownership,
+    /// Valid values include:
+    ///
+    /// - `std`: `std::string_view` and `wit::span`.
+    ///
+    /// - `absl`: `absl::string_view` and `absl::Span`, which views
+    ///   `absl::InlinedVector` and other contiguous containers without a copy.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = Containers::Std))]
+    pub containers: Containers,
+
+    /// Also generate a `Try<Name>` wrapper for imported functions whose
+    /// results can be malformed, returning a `wit::decode_error` instead of
+    /// trapping. Values not lifted before the error are leaked.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
+    pub try_call: bool,
 
-    /// Symmetric ABI, this enables to directly link components to each
-    /// other and removes the primary distinction between host and guest.
+    /// The number of elements a guest side `wit::vector` stores inline
+    /// instead of on the heap, e.g. for lists which are usually short. Lists
+    /// lowered to the other side are moved to the heap first.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = 0))]
+    pub sbo: usize,
+
+    /// Generate a `consteval` factory `make_<Enum>_<case>()` for each enum
+    /// case, for metaprogramming which needs immediate functions (only
+    /// declared if the compiler supports `consteval`)
     #[cfg_attr(feature = "clap", arg(long, default_value_t = bool::default()))]
-    pub symmetric: bool,
+    pub consteval_factories: bool,
+
+    /// Which C functions to generate for imported functions on the guest
+    /// side.
+    ///
+    /// Valid values include:
+    ///
+    /// - `canonical`: only the C++ functions of the canonical ABI.
+    ///
+    /// - `preview1-compat`: also an `extern "C"` function
+    ///   `<namespace>_<function>` in the style of `wasi_snapshot_preview1`
+    ///   for imports taking integers, floats, `bool`, `char` and enums, and
+    ///   returning nothing, one of these or a `result` with an enum error.
+    ///   A failed result returns the error case plus one as `int32_t`
+    ///   (an errno, zero is success) and stores the value through a trailing
+    ///   `retptr`. Other imports only have the C++ function.
+    #[cfg_attr(feature = "clap", arg(long, default_value_t = Abi::Canonical))]
+    pub abi: Abi,
+}
+
+#[cfg(feature = "clap")]
+fn parse_rename(name: &str) -> anyhow::Result<(String, String)> {
+    let mut parts = name.splitn(2, '=');
+    let to_rename = parts.next().unwrap();
+    match parts.next() {
+        Some(part) => Ok((to_rename.to_string(), part.to_string())),
+        None => anyhow::bail!(
+            "`--rename-func` and `--rename-type` options must have an `=` in it (e.g. `--rename-func a/b=c`)"
+        ),
+    }
 }
 
 impl Opts {
@@ -227,10 +1033,51 @@ impl Opts {
         Box::new(r)
     }
 
+    // after an element of a case list, `last` is only followed by the brace
+    fn separator(&self, last: bool) -> &'static str {
+        if last && !self.trailing_commas {
+            ""
+        } else {
+            ","
+        }
+    }
+
+    fn result_template(&self) -> &'static str {
+        if self.wit_result {
+            "wit::result"
+        } else if self.expected_polyfill() {
+            "wit::expected"
+        } else {
+            "std::expected"
+        }
+    }
+
+    fn unexpected_template(&self) -> &'static str {
+        if self.expected_polyfill() && !self.wit_result {
+            "wit::unexpected"
+        } else {
+            "std::unexpected"
+        }
+    }
+
+    // std::expected is new in C++23
+    fn expected_polyfill(&self) -> bool {
+        self.cpp_std != CppStd::Cpp23
+    }
+
     fn host_side(&self) -> bool {
         self.short_cut || self.host
     }
 
+    // a member of the class a generated body belongs to
+    fn member(&self, name: &str) -> String {
+        if self.explicit_this {
+            format!("this->{name}")
+        } else {
+            name.into()
+        }
+    }
+
     fn is_only_handle(&self, variant: AbiVariant) -> bool {
         self.host_side() == matches!(variant, AbiVariant::GuestExport)
     }
@@ -269,6 +1116,71 @@ impl Cpp {
         }
     }
 
+    fn forward_declare(&mut self, namespace: &[String], declaration: String) {
+        let entry = (namespace.to_vec(), declaration);
+        if self.opts.emit_fwd_header && !self.forward_declarations.contains(&entry) {
+            self.forward_declarations.push(entry);
+        }
+    }
+
+    fn forward_header(&self) -> String {
+        let mut fwd = SourceWithState::default();
+        let version = env!("CARGO_PKG_VERSION");
+        uwriteln!(
+            fwd.src,
+            "// Generated by `wit-bindgen` {version}. DO NOT EDIT!"
+        );
+        uwriteln!(fwd.src, "#pragma once");
+        uwriteln!(fwd.src, "#include <cstdint>");
+        for (namespace, declaration) in self.forward_declarations.iter() {
+            fwd.change_namespace(namespace);
+            uwriteln!(fwd.src, "{declaration}");
+        }
+        fwd.change_namespace(&Default::default());
+        fwd.src.to_string()
+    }
+
+    /// The `main` of `--emit-bench`, linked with the bindings and the
+    /// implementation of the exports
+    fn bench_harness(&self) -> String {
+        let mut bench = SourceWithState::default();
+        let version = env!("CARGO_PKG_VERSION");
+        uwriteln!(
+            bench.src,
+            "// Generated by `wit-bindgen` {version}. DO NOT EDIT!"
+        );
+        uwriteln!(bench.src, "#include <chrono>");
+        uwriteln!(bench.src, "#include <stddef.h>");
+        uwriteln!(bench.src, "#include <stdint.h>");
+        uwriteln!(bench.src, "#include <stdio.h>\n");
+        for (declarations, _) in self.bench.iter() {
+            bench.src.push_str(declarations);
+        }
+        uwriteln!(
+            bench.src,
+            "
+            #ifndef WIT_BENCH_ITERATIONS
+            #define WIT_BENCH_ITERATIONS 100000
+            #endif
+
+            template <class F> static void bench(char const* name, F f) {{
+            auto start = std::chrono::steady_clock::now();
+            for (long i = 0; i < WIT_BENCH_ITERATIONS; ++i) {{
+            f();
+            }}
+            std::chrono::duration<double, std::nano> elapsed = std::chrono::steady_clock::now() - start;
+            printf(\"%s: %.1f ns\\n\", name, elapsed.count() / WIT_BENCH_ITERATIONS);
+            }}
+
+            int main() {{"
+        );
+        for (_, statement) in self.bench.iter() {
+            uwriteln!(bench.src, "{statement}");
+        }
+        uwriteln!(bench.src, "return 0;\n}}");
+        bench.src.to_string()
+    }
+
     fn include(&mut self, s: &str) {
         self.includes.push(s.to_string());
     }
@@ -321,6 +1233,20 @@ impl Cpp {
         assert!(status.success());
     }
 
+    fn bracket_nolint(code: &mut String) {
+        // after the leading comment or include guard, if any
+        let start = if code.starts_with("//") || code.starts_with("#pragma once") {
+            code.find('\n').map_or(code.len(), |pos| pos + 1)
+        } else {
+            0
+        };
+        code.insert_str(start, "// NOLINTBEGIN\n");
+        if !code.ends_with('\n') {
+            code.push('\n');
+        }
+        code.push_str("// NOLINTEND\n");
+    }
+
     fn perform_cast(&mut self, op: &str, cast: &Bitcast) -> String {
         match cast {
             Bitcast::I32ToF32 | Bitcast::I64ToF32 => {
@@ -369,11 +1295,20 @@ impl Cpp {
             self.include("<vector>");
         }
         if self.dependencies.needs_expected {
-            self.include("<expected>");
+            if self.opts.wit_result {
+                self.include("<wit-result.h>");
+            } else if self.opts.expected_polyfill() {
+                self.include("<wit-expected.h>");
+            } else {
+                self.include("<expected>");
+            }
         }
         if self.dependencies.needs_optional {
             self.include("<optional>");
         }
+        if self.dependencies.needs_functional {
+            self.include("<functional>");
+        }
         if self.dependencies.needs_cstring {
             self.include("<cstring>");
         }
@@ -399,23 +1334,66 @@ impl Cpp {
         if self.dependencies.needs_memory {
             self.include("<memory>");
         }
+        if self.dependencies.needs_compare {
+            self.include("<compare>");
+        }
+        if self.dependencies.needs_chrono {
+            self.include("<chrono>");
+        }
+        if self.dependencies.needs_array {
+            self.include("<array>");
+        }
+        if self.dependencies.needs_system_error {
+            self.include("<system_error>");
+        }
+        if self.dependencies.needs_type_traits {
+            self.include("<type_traits>");
+        }
+        if self.dependencies.needs_dump {
+            self.include("<wit-dump.h>");
+        }
+        if self.dependencies.needs_absl_string_view {
+            self.include("<absl/strings/string_view.h>");
+        }
+        if self.dependencies.needs_absl_span {
+            self.include("<absl/types/span.h>");
+        }
     }
 
-    fn start_new_file(&mut self, condition: Option<bool>) -> FileContext {
+    fn start_new_file(&mut self, condition: Option<bool>) -> Option<FileContext> {
         if condition == Some(true) || self.opts.split_interfaces {
-            FileContext {
+            Some(FileContext {
                 includes: std::mem::replace(&mut self.includes, Default::default()),
                 src: std::mem::replace(&mut self.h_src, Default::default()),
                 dependencies: std::mem::replace(&mut self.dependencies, Default::default()),
-            }
+            })
         } else {
-            Default::default()
+            None
+        }
+    }
+
+    // headers generated with different `--length-type` can't share the
+    // helper types
+    fn length_type_check(&self, out: &mut impl std::fmt::Write) {
+        if self
+            .includes
+            .iter()
+            .any(|include| include.starts_with("<wit-"))
+        {
+            let length_type = self.opts.length_type.cpp_type().unwrap_or("size_t");
+            uwriteln!(
+                out,
+                "static_assert(std::is_same<wit::length_type, {length_type}>::value, \"bindings generated with a different --length-type\");"
+            );
         }
     }
 
-    fn finish_file(&mut self, namespace: &[String], store: FileContext) {
-        if !store.src.src.is_empty() {
-            //        self.opts.split_interfaces {
+    fn finish_file(&mut self, namespace: &[String], store: Option<FileContext>) {
+        let Some(store) = store else {
+            return;
+        };
+        let mut filename = None;
+        if !self.h_src.src.is_empty() {
             let mut header = String::default();
             self.finish_includes();
             self.h_src.change_namespace(&Default::default());
@@ -423,21 +1401,56 @@ impl Cpp {
             if self.opts.symmetric {
                 uwriteln!(header, "#define WIT_SYMMETRIC");
             }
+            if let Some(length_type) = self.opts.length_type.cpp_type() {
+                uwriteln!(
+                    header,
+                    "#ifndef WIT_LENGTH_TYPE
+                #define WIT_LENGTH_TYPE {length_type}
+                #endif"
+                );
+            }
+            if self.opts.lifetimebound && !self.opts.host_side() {
+                uwriteln!(
+                    header,
+                    "#ifdef __clang__
+                    #define WIT_LIFETIMEBOUND [[clang::lifetimebound]]
+                    #endif"
+                );
+            }
+            if self.opts.sbo > 0 && !self.opts.host_side() {
+                uwriteln!(header, "#define WIT_VECTOR_INLINE {}", self.opts.sbo);
+            }
             for include in self.includes.iter() {
                 uwriteln!(header, "#include {include}");
             }
+            self.length_type_check(&mut header);
             header.push_str(&self.h_src.src);
-            let mut filename = namespace.join("-");
-            filename.push_str(".h");
+            let mut name = match self.opts.dir_layout {
+                DirLayout::Flat => namespace.join("-"),
+                DirLayout::PackagePath => namespace.join("/"),
+            };
+            name.push_str(".h");
             if self.opts.format {
                 Self::clang_format(&mut header);
             }
-            self.user_class_files.insert(filename.clone(), header);
-
-            let _ = std::mem::replace(&mut self.includes, store.includes);
-            let _ = std::mem::replace(&mut self.h_src, store.src);
-            let _ = std::mem::replace(&mut self.dependencies, store.dependencies);
+            if self.opts.emit_nolint {
+                Self::bracket_nolint(&mut header);
+            }
+            self.user_class_files.insert(name.clone(), header);
+            filename = Some(name);
+        }
+        let includes = std::mem::replace(&mut self.includes, store.includes);
+        let _ = std::mem::replace(&mut self.h_src, store.src);
+        let _ = std::mem::replace(&mut self.dependencies, store.dependencies);
+        if let Some(filename) = filename {
             self.includes.push(String::from("\"") + &filename + "\"");
+        } else {
+            // no file of its own, the headers of nested resources go to the parent
+            for include in includes {
+                if !self.includes.contains(&include) {
+                    self.includes.push(include);
+                }
+            }
         }
     }
 }
@@ -456,22 +1469,46 @@ impl WorldGenerator for Cpp {
         self.world_id = Some(world);
         //        self.sizes.fill(resolve);
         if !self.opts.host_side() {
+            let realloc = self
+                .opts
+                .realloc_symbol
+                .as_deref()
+                .unwrap_or("cabi_realloc");
+            // a renamed allocator isn't exported itself, two of them would
+            // clash on the export name. The forwarder is weak across object
+            // files, the macro guards against a second definition when
+            // several bindings share a file.
+            let (export, forwarder) = if self.opts.realloc_symbol.is_none() {
+                ("__weak__, __export_name__(\"cabi_realloc\")", String::new())
+            } else {
+                let forwarder = format!(
+                    r#"#ifndef WIT_CABI_REALLOC_FORWARDER
+                    #define WIT_CABI_REALLOC_FORWARDER
+                    extern "C" __attribute__((__weak__, __export_name__("cabi_realloc")))
+                    void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) {{
+                        return {realloc}(ptr, old_size, align, new_size);
+                    }}
+                    #endif
+                    "#
+                );
+                ("__weak__", forwarder)
+            };
             uwriteln!(
                 self.c_src_head,
                 r#"#include "{}_cpp.h"
             #include <cstdlib> // realloc
 
-            extern "C" void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size);
+            extern "C" void *{realloc}(void *ptr, size_t old_size, size_t align, size_t new_size);
 
-            __attribute__((__weak__, __export_name__("cabi_realloc")))
-            void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) {{
+            __attribute__(({export}))
+            void *{realloc}(void *ptr, size_t old_size, size_t align, size_t new_size) {{
                 (void) old_size;
                 if (new_size == 0) return (void*) align;
                 void *ret = realloc(ptr, new_size);
                 if (!ret) abort();
                 return ret;
             }}
-
+            {forwarder}
             "#,
                 self.world.to_snake_case(),
             );
@@ -492,6 +1529,7 @@ impl WorldGenerator for Cpp {
         let mut gen = self.interface(resolve, binding, true, Some(wasm_import_module));
         gen.interface = Some(id);
         gen.types(id);
+        gen.map_helpers(id);
         let namespace = namespace(resolve, &TypeOwner::Interface(id), false, &gen.gen.opts);
 
         for (_name, func) in resolve.interfaces[id].functions.iter() {
@@ -521,6 +1559,7 @@ impl WorldGenerator for Cpp {
         let mut gen = self.interface(resolve, binding, false, Some(wasm_import_module));
         gen.interface = Some(id);
         gen.types(id);
+        gen.map_helpers(id);
         let namespace = namespace(resolve, &TypeOwner::Interface(id), true, &gen.gen.opts);
 
         for (_name, func) in resolve.interfaces[id].functions.iter() {
@@ -637,9 +1676,41 @@ impl WorldGenerator for Cpp {
         } else if self.opts.symmetric {
             uwriteln!(h_str.src, "#define WIT_SYMMETRIC");
         }
+        if let Some(length_type) = self.opts.length_type.cpp_type() {
+            uwriteln!(
+                h_str.src,
+                "#ifndef WIT_LENGTH_TYPE
+                #define WIT_LENGTH_TYPE {length_type}
+                #endif"
+            );
+        }
+        if self.opts.lifetimebound && !self.opts.host_side() {
+            uwriteln!(
+                h_str.src,
+                "#ifdef __clang__
+                #define WIT_LIFETIMEBOUND [[clang::lifetimebound]]
+                #endif"
+            );
+        }
+        if self.opts.sbo > 0 && !self.opts.host_side() {
+            uwriteln!(h_str.src, "#define WIT_VECTOR_INLINE {}", self.opts.sbo);
+        }
         for include in self.includes.iter() {
             uwriteln!(h_str.src, "#include {include}");
         }
+        self.length_type_check(&mut h_str.src);
+        let abi_symbol = format!("wit_abi_{snake}_{:016x}", abi_hash(resolve, world_id));
+        if self.opts.abi_link_check {
+            uwriteln!(
+                h_str.src,
+                "// fails to link against bindings generated from a different WIT"
+            );
+            uwriteln!(h_str.src, "extern \"C\" char const {abi_symbol};");
+            uwriteln!(
+                h_str.src,
+                "__attribute__((used)) static char const* const wit_abi_check_{snake} = &{abi_symbol};"
+            );
+        }
 
         uwriteln!(
             c_str.src,
@@ -670,6 +1741,10 @@ impl WorldGenerator for Cpp {
                  #include <wasm_c_api.h>\n\
                  #include <assert.h>"
             );
+            if self.opts.export_style != ExportStyle::StaticMethods {
+                // reporting failed instantiations
+                uwriteln!(c_str.src, "#include <stdexcept>\n#include <string.h>");
+            }
 
             if c_str.src.len() > 0 {
                 c_str.src.push_str("\n");
@@ -681,12 +1756,6 @@ impl WorldGenerator for Cpp {
                 );
             }
         }
-        if self.opts.host_side() && self.dependencies.needs_exported_resources {
-            uwriteln!(
-                c_str.src,
-                "template <class R> std::map<int32_t, R> wit::{RESOURCE_TABLE_NAME}<R>::resources;"
-            );
-        }
         if self.dependencies.needs_assert {
             uwriteln!(c_str.src, "#include <assert.h>");
         }
@@ -700,38 +1769,287 @@ impl WorldGenerator for Cpp {
         self.h_src.change_namespace(&Vec::default());
         h_str.src.push_str(&self.h_src.src);
 
+        if self.opts.abi_link_check {
+            uwriteln!(c_str.src, "extern \"C\" char const {abi_symbol} = 0;");
+        }
+
+        if self.opts.emit_init_hooks {
+            let snake = world.name.to_snake_case();
+            uwriteln!(
+                h_str.src,
+                "// run when the bindings are loaded and unloaded, define these to replace
+                // the empty defaults
+                extern \"C\" void {snake}_init();
+                extern \"C\" void {snake}_fini();"
+            );
+            // formatted first, the braces within a line would change the indentation
+            let hooks = format!(
+                "extern \"C\" __attribute__((__weak__)) void {snake}_init() {{}}
+                extern \"C\" __attribute__((__weak__)) void {snake}_fini() {{}}
+                __attribute__((constructor)) static void {snake}_entry() {{ {snake}_init(); }}
+                __attribute__((destructor)) static void {snake}_exit() {{ {snake}_fini(); }}\n"
+            );
+            c_str.src.push_str(&hooks);
+        }
+
         uwriteln!(c_str.src, "\n// Component Adapters");
 
-        if !self.opts.short_cut && self.opts.host {
+        if !self.import_vtable.is_empty() {
+            uwriteln!(h_str.src, "struct ImportVTable {{");
+            for (ty, member) in self.import_vtable.iter() {
+                uwriteln!(h_str.src, "{ty} {member};");
+            }
+            let complete = self
+                .import_vtable
+                .iter()
+                .map(|(_, member)| format!("{member} != nullptr"))
+                .collect::<Vec<_>>()
+                .join(" && ");
             uwriteln!(
                 h_str.src,
-                "extern \"C\" void register_{}();",
-                world.name.to_snake_case()
+                "// whether every import has an implementation, `static_assert` this on constexpr tables
+                constexpr bool is_complete() const {{ return {complete}; }}"
+            );
+            uwriteln!(h_str.src, "}};");
+            uwriteln!(
+                h_str.src,
+                "// has to be set before the guest calls any import
+                extern ImportVTable const* import_vtable;"
+            );
+            uwriteln!(c_str.src, "ImportVTable const* import_vtable = nullptr;");
+        }
+
+        let names = match self.opts.export_name_table {
+            true => export_names(resolve, world_id)
+                .iter()
+                .map(|name| format!("\"{name}\""))
+                .collect::<Vec<_>>(),
+            false => Vec::new(),
+        };
+        // C++ has no arrays without elements
+        if !names.is_empty() {
+            uwriteln!(
+                h_str.src,
+                "// the exported functions by their index in the world
+                constexpr char const* {snake}_export_names[] = {{ {} }};",
+                names.join(", ")
+            );
+        }
+
+        if !self.facade.is_empty() {
+            uwriteln!(
+                h_str.src,
+                "/// The functions of every imported and exported interface
+                struct World {{"
+            );
+            for method in self.facade.iter() {
+                let name = if self.facade.iter().filter(|m| m.name == method.name).count() > 1 {
+                    format!("{}_{}", method.interface, method.name)
+                } else {
+                    to_c_ident(&method.name)
+                };
+                uwriteln!(h_str.src, "static auto {name}{}", method.rest);
+            }
+            uwriteln!(h_str.src, "}};");
+        }
+
+        if !self.preview1_adapters.is_empty() {
+            uwriteln!(
+                h_str.src,
+                "// the imports in the style of `wasi_snapshot_preview1`
+                extern \"C\" {{"
+            );
+            for (declaration, definition) in self.preview1_adapters.iter() {
+                uwriteln!(h_str.src, "{declaration};");
+                uwriteln!(c_str.src, "{definition}");
+            }
+            uwriteln!(h_str.src, "}}");
+        }
+
+        if !self.import_context.is_empty() {
+            uwriteln!(
+                h_str.src,
+                "/// The implementations of the imports, used by the guest running
+                /// on the execution environment this is attached to
+                struct ImportContext {{"
+            );
+            for (ty, member) in self.import_context.iter() {
+                uwriteln!(h_str.src, "{ty} {member};");
+            }
+            let complete = self
+                .import_context
+                .iter()
+                .map(|(_, member)| format!("{member} != nullptr"))
+                .collect::<Vec<_>>()
+                .join(" && ");
+            uwriteln!(
+                h_str.src,
+                "// whether every import has an implementation
+                bool is_complete() const {{ return {complete}; }}"
+            );
+            uwriteln!(h_str.src, "}};");
+        }
+
+        if !self.opts.short_cut
+            && self.opts.host
+            && self.opts.export_style == ExportStyle::StaticMethods
+        {
+            uwriteln!(
+                h_str.src,
+                "/// The exported functions, called on the execution environment of an instance
+                struct {} {{",
+                world.name.to_pascal_case()
+            );
+            for (declaration, _) in self.instance_methods.iter() {
+                uwriteln!(h_str.src, "{declaration}");
+            }
+            uwriteln!(h_str.src, "}};");
+            for (_, definition) in self.instance_methods.iter() {
+                uwriteln!(c_str.src, "{definition}");
+            }
+        } else if !self.opts.short_cut && self.opts.host {
+            uwriteln!(
+                h_str.src,
+                "struct WASMModuleCommon;
+                struct WASMModuleInstanceCommon;
+                /// An instance of the guest module, destroyed with this object,
+                /// the methods call the exported functions
+                class Instance {{
+                    WASMModuleInstanceCommon* module_inst;
+                    WASMExecEnv* exec_env;
+                    char error_buf[128];
+                public:
+                    /// Throws `std::runtime_error` with the message of WAMR if the
+                    /// module can't be instantiated, without exceptions check `has_value()`
+                    Instance(WASMModuleCommon* module, uint32_t stack_size, uint32_t heap_size);
+                    Instance(Instance const&) = delete;
+                    Instance& operator=(Instance const&) = delete;
+                    ~Instance();
+                    bool has_value() const {{ return exec_env != nullptr; }}
+                    // why instantiating failed
+                    char const* error() const {{ return error_buf; }}
+                    WASMExecEnv* get_exec_env() const {{ return exec_env; }}"
             );
+            if !self.import_context.is_empty() {
+                uwriteln!(
+                    h_str.src,
+                    "// the imports called by this instance, has to outlive it
+                    void set_import_context(ImportContext* context);"
+                );
+            }
+            for (declaration, _) in self.instance_methods.iter() {
+                uwriteln!(h_str.src, "{declaration}");
+            }
+            for proxy in self.instance_proxies.iter() {
+                let (accessor, interface) = (&proxy.accessor, &proxy.interface);
+                let class = accessor.to_pascal_case();
+                // formatted first, the interpolation would split the lines
+                let head = format!(
+                    "/// The exports of `{interface}`
+                    class {class} {{
+                        WASMExecEnv* exec_env;
+                    public:
+                        explicit {class}(WASMExecEnv* exec_env) : exec_env(exec_env) {{}}\n"
+                );
+                h_str.src.push_str(&head);
+                for (declaration, _) in proxy.methods.iter() {
+                    uwriteln!(h_str.src, "{declaration}");
+                }
+                uwriteln!(
+                    h_str.src,
+                    "}};
+                    {class} {accessor}() const {{ return {class}(exec_env); }}"
+                );
+            }
+            uwriteln!(h_str.src, "}};");
             uwriteln!(
                 c_str.src,
-                "void register_{}() {{",
-                world.name.to_snake_case()
+                "Instance::Instance(wasm_module_t module, uint32_t stack_size, uint32_t heap_size)
+                    : module_inst(nullptr), exec_env(nullptr) {{
+                    error_buf[0] = 0;
+                    module_inst = wasm_runtime_instantiate(module, stack_size, heap_size, error_buf, sizeof(error_buf));
+                    if (module_inst) {{
+                        exec_env = wasm_runtime_create_exec_env(module_inst, stack_size);
+                        if (!exec_env) {{
+                            strncpy(error_buf, \"failed to create the execution environment\", sizeof(error_buf) - 1);
+                            wasm_runtime_deinstantiate(module_inst);
+                            module_inst = nullptr;
+                        }}
+                    }}
+                #if __cpp_exceptions
+                    if (!exec_env) {{
+                        throw std::runtime_error(error_buf);
+                    }}
+                #endif
+                }}
+                Instance::~Instance() {{
+                    if (exec_env) {{
+                        wasm_runtime_destroy_exec_env(exec_env);
+                        wasm_runtime_deinstantiate(module_inst);
+                    }}
+                }}"
             );
-            for i in self.host_functions.iter() {
+            if !self.import_context.is_empty() {
                 uwriteln!(
                     c_str.src,
-                    "  static NativeSymbol {}_funs[] = {{",
-                    i.0.replace(&[':', '.', '-', '+'], "_").to_snake_case()
+                    "void Instance::set_import_context(ImportContext* context) {{
+                        wasm_runtime_set_user_data(exec_env, context);
+                    }}"
                 );
-                for f in i.1.iter() {
+            }
+            for (_, definition) in self.instance_methods.iter() {
+                uwriteln!(c_str.src, "{definition}");
+            }
+            for proxy in self.instance_proxies.iter() {
+                for (_, definition) in proxy.methods.iter() {
+                    uwriteln!(c_str.src, "{definition}");
+                }
+            }
+        }
+
+        if !self.opts.short_cut && self.opts.host {
+            let mut modules = self.host_functions.iter().collect::<Vec<_>>();
+            modules.sort_by(|a, b| a.0.cmp(b.0));
+            let symbols = |module: &str| module.replace([':', '.', '-', '+'], "_").to_snake_case();
+            let print_natives = |src: &mut Source, module: &str, funcs: &[HostFunction]| {
+                let symbols = symbols(module);
+                uwriteln!(src, "  static NativeSymbol {symbols}_funs[] = {{");
+                for f in funcs.iter() {
                     uwriteln!(
-                        c_str.src,
+                        src,
                         "    {{ \"{}\", (void*){}, \"{}\", nullptr }},",
                         f.wasm_name,
                         f.host_name,
                         f.wamr_signature
                     );
                 }
-                uwriteln!(c_str.src, "  }};");
+                uwriteln!(src, "  }};");
+                uwriteln!(src, "  wasm_runtime_register_natives(\"{module}\", {symbols}_funs, sizeof({symbols}_funs)/sizeof(NativeSymbol));");
+            };
+            // each interface can be registered on its own
+            for (module, funcs) in modules.iter().filter(|(module, _)| *module != "$root") {
+                let symbols = symbols(module);
+                uwriteln!(h_str.src, "extern \"C\" void register_{symbols}();");
+                uwriteln!(c_str.src, "void register_{symbols}() {{");
+                print_natives(&mut c_str.src, module, funcs);
+                uwriteln!(c_str.src, "}}");
             }
-            for i in self.host_functions.iter() {
-                uwriteln!(c_str.src, "  wasm_runtime_register_natives(\"{}\", {1}_funs, sizeof({1}_funs)/sizeof(NativeSymbol));", i.0, i.0.replace(&[':','.','-','+'], "_").to_snake_case());
+            uwriteln!(
+                h_str.src,
+                "extern \"C\" void register_{}();",
+                world.name.to_snake_case()
+            );
+            uwriteln!(
+                c_str.src,
+                "void register_{}() {{",
+                world.name.to_snake_case()
+            );
+            for (module, funcs) in modules.iter() {
+                if *module == "$root" {
+                    print_natives(&mut c_str.src, module, funcs);
+                } else {
+                    uwriteln!(c_str.src, "  register_{}();", symbols(module));
+                }
             }
             uwriteln!(c_str.src, "}}");
         }
@@ -746,6 +2064,17 @@ impl WorldGenerator for Cpp {
             Self::clang_format(&mut c_str.src.as_mut_string());
             Self::clang_format(&mut h_str.src.as_mut_string());
         }
+        if self.opts.emit_nolint {
+            Self::bracket_nolint(c_str.src.as_mut_string());
+            Self::bracket_nolint(h_str.src.as_mut_string());
+        }
+        let wit_hash = if self.opts.emit_wit_hash {
+            format!("// wit-hash: {}\n", wit_hash(resolve))
+        } else {
+            String::new()
+        };
+        c_str.src.as_mut_string().insert_str(0, &wit_hash);
+        h_str.src.as_mut_string().insert_str(0, &wit_hash);
 
         if self.opts.short_cut {
             files.push(&format!("{snake}_native.cpp"), c_str.src.as_bytes());
@@ -757,6 +2086,27 @@ impl WorldGenerator for Cpp {
             files.push(&format!("{snake}_host.cpp"), c_str.src.as_bytes());
             files.push(&format!("{snake}_cpp_host.h"), h_str.src.as_bytes());
         }
+        if self.opts.json {
+            files.push(
+                &format!("{snake}.json"),
+                json::describe_world(resolve, world_id).as_bytes(),
+            );
+        }
+        if self.opts.emit_bench {
+            let mut bench = self.bench_harness();
+            if self.opts.format {
+                Self::clang_format(&mut bench);
+            }
+            files.push(&format!("{snake}_bench.cpp"), bench.as_bytes());
+        }
+        if self.opts.emit_fwd_header {
+            let mut fwd = self.forward_header();
+            if self.opts.format {
+                Self::clang_format(&mut fwd);
+            }
+            fwd.insert_str(0, &wit_hash);
+            files.push(&format!("{snake}_fwd.h"), fwd.as_bytes());
+        }
         for (name, content) in self.user_class_files.iter() {
             // if the user class file exists create an updated .template
             if std::path::Path::exists(&std::path::PathBuf::from(name)) {
@@ -781,6 +2131,96 @@ impl WorldGenerator for Cpp {
     }
 }
 
+// FNV-1a over the world's functions and their core wasm signatures
+fn abi_hash(resolve: &Resolve, world_id: WorldId) -> u64 {
+    let world = &resolve.worlds[world_id];
+    let mut description = json::describe_world(resolve, world_id);
+    for (variant, items) in [
+        (AbiVariant::GuestImport, &world.imports),
+        (AbiVariant::GuestExport, &world.exports),
+    ] {
+        for (key, item) in items.iter() {
+            let functions = match item {
+                WorldItem::Interface { id, .. } => {
+                    resolve.interfaces[*id].functions.values().collect()
+                }
+                WorldItem::Function(func) => vec![func],
+                WorldItem::Type(_) => Vec::new(),
+            };
+            for func in functions {
+                uwriteln!(
+                    description,
+                    "{} {}: {:?}",
+                    resolve.name_world_key(key),
+                    func.name,
+                    resolve.wasm_signature(variant, func)
+                );
+            }
+        }
+    }
+    description.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+// whether values of the type hold resources or handles
+fn type_has_resources(resolve: &Resolve, ty: &Type) -> bool {
+    let Type::Id(id) = ty else {
+        return false;
+    };
+    let has = |ty: &Type| type_has_resources(resolve, ty);
+    match &resolve.types[*id].kind {
+        TypeDefKind::Record(r) => r.fields.iter().any(|f| has(&f.ty)),
+        TypeDefKind::Resource => true,
+        TypeDefKind::Handle(_) => true,
+        TypeDefKind::Flags(_) => false,
+        TypeDefKind::Tuple(t) => t.types.iter().any(has),
+        TypeDefKind::Variant(v) => v.cases.iter().filter_map(|c| c.ty.as_ref()).any(has),
+        TypeDefKind::Enum(_) => false,
+        TypeDefKind::Option(ty) => has(ty),
+        TypeDefKind::Result(r) => r.ok.iter().chain(r.err.iter()).any(has),
+        TypeDefKind::List(ty) => has(ty),
+        TypeDefKind::Type(ty) => has(ty),
+        // handles as well, callers can't make them up either
+        TypeDefKind::Future(_) | TypeDefKind::Stream(_) => true,
+        TypeDefKind::Unknown => false,
+    }
+}
+
+// the core export names of the world's exported functions, in order
+fn export_names(resolve: &Resolve, world_id: WorldId) -> Vec<String> {
+    let mut names = Vec::new();
+    for (key, item) in resolve.worlds[world_id].exports.iter() {
+        match item {
+            WorldItem::Interface { id, .. } => {
+                let interface = resolve.name_world_key(key);
+                for func in resolve.interfaces[*id].functions.values() {
+                    names.push(func.core_export_name(Some(&interface)).into_owned());
+                }
+            }
+            WorldItem::Function(func) => names.push(func.name.clone()),
+            WorldItem::Type(_) => {}
+        }
+    }
+    names
+}
+
+// SHA-256 over all packages of the resolve, printed as WIT
+fn wit_hash(resolve: &Resolve) -> String {
+    let packages = resolve
+        .packages
+        .iter()
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    let wit = wit_component::WitPrinter::default()
+        .print(resolve, &packages, true)
+        .unwrap();
+    Sha256::digest(wit.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
 // determine namespace (for the lifted C++ function)
 fn namespace(resolve: &Resolve, owner: &TypeOwner, guest_export: bool, opts: &Opts) -> Vec<String> {
     let mut result = Vec::default();
@@ -959,7 +2399,17 @@ impl CppInterfaceGenerator<'_> {
                 }
             }
         } else {
-            func.name.to_pascal_case()
+            let mut wit_namespace = self::namespace(self.resolve, &owner, false, &self.gen.opts);
+            if self.gen.opts.internal_prefix.is_some() {
+                wit_namespace.remove(0);
+            }
+            let key = format!("{}/{}", wit_namespace.join("."), func.name);
+            self.gen
+                .opts
+                .rename_func
+                .iter()
+                .find(|(from, _)| *from == key)
+                .map_or_else(|| func.name.to_pascal_case(), |(_, to)| to.clone())
         };
         (namespace, func_name_h)
     }
@@ -1090,7 +2540,11 @@ impl CppInterfaceGenerator<'_> {
         self.gen.c_src.src.push_str("(");
         let mut first_arg = true;
         if self.gen.opts.host {
-            self.gen.c_src.src.push_str("wasm_exec_env_t exec_env");
+            // methods and resource intrinsics don't pass the environment on
+            self.gen
+                .c_src
+                .src
+                .push_str("[[maybe_unused]] wasm_exec_env_t exec_env");
             first_arg = false;
         }
         let mut params = Vec::new();
@@ -1118,6 +2572,15 @@ impl CppInterfaceGenerator<'_> {
             params.push("resultptr".into());
         }
         self.gen.c_src.src.push_str(")\n");
+        if self.gen.opts.emit_bench
+            && !self.gen.opts.host_side()
+            && !self.gen.opts.symmetric
+            && matches!(variant, AbiVariant::GuestExport)
+            && matches!(is_drop, SpecialMethod::None)
+        {
+            let core_name = func.core_export_name(module_name.as_deref());
+            self.bench_export(func, &core_name, &export_name, &signature);
+        }
         if self.gen.opts.host_side() {
             let signature = wamr::wamr_signature(self.resolve, func);
             let remember = HostFunction {
@@ -1134,6 +2597,116 @@ impl CppInterfaceGenerator<'_> {
         params
     }
 
+    /// The callback resource of `--callback-resources` a parameter owns
+    fn callback_param(&self, ty: &Type) -> Option<(TypeId, Function)> {
+        let Type::Id(id) = dealias(self.resolve, ty) else {
+            return None;
+        };
+        match &self.resolve.types[id].kind {
+            TypeDefKind::Handle(Handle::Own(resource)) => self.callback_method(*resource),
+            _ => None,
+        }
+    }
+
+    /// The method of an imported resource if it is a callback of
+    /// `--callback-resources`
+    fn callback_method(&self, resource: TypeId) -> Option<(TypeId, Function)> {
+        if !self.gen.opts.callback_resources || self.gen.opts.host_side() {
+            return None;
+        }
+        let Type::Id(resource) = dealias(self.resolve, &Type::Id(resource)) else {
+            return None;
+        };
+        let TypeOwner::Interface(intf) = self.resolve.types[resource].owner else {
+            return None;
+        };
+        if !self.gen.imported_interfaces.contains(&intf) {
+            return None;
+        }
+        let mut functions = self.resolve.interfaces[intf]
+            .functions
+            .values()
+            .filter(|func| match func.kind {
+                FunctionKind::Method(id)
+                | FunctionKind::Static(id)
+                | FunctionKind::Constructor(id) => id == resource,
+                FunctionKind::Freestanding => false,
+            });
+        match (functions.next(), functions.next()) {
+            (Some(func), None) if matches!(func.kind, FunctionKind::Method(_)) => {
+                Some((resource, func.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The `std::function` of a callback, typed like the imported method
+    fn callback_type(&mut self, method: &Function, from_namespace: &Vec<String>) -> String {
+        let sig = self.high_level_signature(method, AbiVariant::GuestImport, from_namespace);
+        self.gen.dependencies.needs_functional = true;
+        self.gen.dependencies.needs_memory = true;
+        let result = if sig.result.is_empty() {
+            "void"
+        } else {
+            &sig.result
+        };
+        let params = sig
+            .arguments
+            .iter()
+            .map(|(_, ty)| ty.as_str())
+            .collect::<Vec<_>>();
+        format!("std::function<{result}({})>", params.join(", "))
+    }
+
+    /// Calls an export of the `--emit-bench` harness with zeroed core
+    /// parameters, which are valid values of every type but handles
+    fn bench_export(
+        &mut self,
+        func: &Function,
+        core_name: &str,
+        symbol: &str,
+        signature: &WasmSignature,
+    ) {
+        if func
+            .params
+            .iter()
+            .map(|(_, ty)| ty)
+            .chain(func.results.iter_types())
+            .any(|ty| type_has_resources(self.resolve, ty))
+        {
+            let skipped = format!("// {core_name} passes resources, not benchmarked");
+            self.gen.bench.push((String::new(), skipped));
+            return;
+        }
+        if signature.indirect_params {
+            let skipped =
+                format!("// {core_name} passes its parameters in memory, not benchmarked");
+            self.gen.bench.push((String::new(), skipped));
+            return;
+        }
+        let types = signature
+            .params
+            .iter()
+            .map(|ty| self.gen.opts.wasm_type(*ty))
+            .collect::<Vec<_>>();
+        let result = signature
+            .results
+            .first()
+            .map_or("void", |ty| self.gen.opts.wasm_type(*ty));
+        let mut declarations = format!("extern \"C\" {result} {symbol}({});\n", types.join(", "));
+        let args = vec!["0"; types.len()].join(", ");
+        let mut call = format!("{symbol}({args})");
+        if abi::guest_export_needs_post_return(self.resolve, func) {
+            uwriteln!(
+                declarations,
+                "extern \"C\" void cabi_post_{symbol}({result});"
+            );
+            call = format!("cabi_post_{symbol}({call})");
+        }
+        let statement = format!("bench(\"{core_name}\", [] {{ {call}; }});");
+        self.gen.bench.push((declarations, statement));
+    }
+
     fn high_level_signature(
         &mut self,
         func: &Function,
@@ -1189,9 +2762,17 @@ impl CppInterfaceGenerator<'_> {
                 wit_bindgen_core::wit_parser::Results::Anon(ty) => {
                     if matches!(is_drop, SpecialMethod::Allocate) {
                         res.result = OWNED_CLASS_NAME.into();
+                    } else if self.is_span_byte_result(func, abi_variant) {
+                        self.gen.dependencies.needs_wit = true;
+                        res.result = "wit::span<uint8_t const>".into();
+                    } else if self.wide_host_import(abi_variant)
+                        && dealias(self.resolve, ty) == Type::String
+                    {
+                        self.gen.dependencies.needs_string = true;
+                        res.result = "std::wstring".into();
                     } else {
                         res.result =
-                            self.type_name(ty, from_namespace, Flavor::Result(abi_variant));
+                            self.scalar_type_name(ty, from_namespace, Flavor::Result(abi_variant));
                         if matches!(
                             is_drop,
                             SpecialMethod::Allocate | SpecialMethod::ResourceRep
@@ -1237,8 +2818,7 @@ impl CppInterfaceGenerator<'_> {
                     SpecialMethod::ResourceRep | SpecialMethod::ResourceDrop
                 )
             {
-                res.arguments
-                    .push((name.to_snake_case(), "uint8_t*".into()));
+                res.arguments.push((param_name(name), "uint8_t*".into()));
             } else if matches!(
                 (&is_drop, self.gen.opts.host_side()),
                 (SpecialMethod::Dtor, _)
@@ -1246,13 +2826,25 @@ impl CppInterfaceGenerator<'_> {
                     | (SpecialMethod::ResourceDrop, true)
             ) {
                 res.arguments.push((
-                    name.to_snake_case(),
+                    param_name(name),
                     self.type_name(param, &res.namespace, Flavor::Argument(abi_variant)) + "*",
                 ));
+            } else if let Some((_, method)) = self
+                .callback_param(param)
+                .filter(|_| matches!(abi_variant, AbiVariant::GuestExport))
+            {
+                let callback = self.callback_type(&method, &res.namespace);
+                res.arguments.push((param_name(name), callback));
+            } else if self.wide_host_import(abi_variant)
+                && dealias(self.resolve, param) == Type::String
+            {
+                self.gen.dependencies.needs_string = true;
+                res.arguments
+                    .push((param_name(name), "std::wstring".into()));
             } else {
                 res.arguments.push((
-                    name.to_snake_case(),
-                    self.type_name(param, &res.namespace, Flavor::Argument(abi_variant)),
+                    param_name(name),
+                    self.scalar_type_name(param, &res.namespace, Flavor::Argument(abi_variant)),
                 ));
             }
         }
@@ -1261,9 +2853,420 @@ impl CppInterfaceGenerator<'_> {
         if matches!(func.kind, FunctionKind::Method(_)) && import {
             res.const_member = true;
         }
+        if self.gen.opts.noexcept
+            && !self.gen.opts.host_side()
+            && matches!(abi_variant, AbiVariant::GuestImport)
+            && matches!(is_drop, SpecialMethod::None)
+        {
+            res.noexcept = Some(!lowering_allocates(self.resolve, func));
+        }
         res
     }
 
+    /// The entry, key and value types of a map shaped list (`--map-heuristic`),
+    /// and whether the entries are tuples
+    fn map_entry(&self, ty: &Type) -> Option<(Type, Type, Type, bool)> {
+        let Type::Id(id) = dealias(self.resolve, ty) else {
+            return None;
+        };
+        let TypeDefKind::List(element) = &self.resolve.types[id].kind else {
+            return None;
+        };
+        let Type::Id(entry) = dealias(self.resolve, element) else {
+            return None;
+        };
+        let (key, value, tuple) = match &self.resolve.types[entry].kind {
+            TypeDefKind::Tuple(t) if t.types.len() == 2 => (t.types[0], t.types[1], true),
+            TypeDefKind::Record(r)
+                if r.fields.len() == 2
+                    && r.fields[0].name == "key"
+                    && r.fields[1].name == "value" =>
+            {
+                (r.fields[0].ty, r.fields[1].ty, false)
+            }
+            _ => return None,
+        };
+        let comparable = match dealias(self.resolve, &key) {
+            Type::Id(id) => match &self.resolve.types[id].kind {
+                TypeDefKind::Enum(_) => true,
+                TypeDefKind::Variant(variant) => payloadless_enum(variant).is_some(),
+                _ => false,
+            },
+            Type::F32 | Type::F64 => false,
+            _ => true,
+        };
+        comparable.then_some((*element, key, value, tuple))
+    }
+
+    /// `find` helpers for the map shaped lists used by an interface, for both
+    /// borrowed (`wit::span`) and owned (`wit::vector`) lists
+    fn map_helpers(&mut self, id: InterfaceId) {
+        if !self.gen.opts.map_heuristic || self.gen.opts.host_side() {
+            return;
+        }
+        let iface = &self.resolve.interfaces[id];
+        let mut lists: Vec<Type> = iface.types.values().map(|ty| Type::Id(*ty)).collect();
+        for func in iface.functions.values() {
+            lists.extend(func.params.iter().map(|(_, ty)| *ty));
+            lists.extend(func.results.iter_types().copied());
+        }
+        let namespc = namespace(
+            self.resolve,
+            &TypeOwner::Interface(id),
+            NOT_IN_EXPORTED_NAMESPACE,
+            &self.gen.opts,
+        );
+        for list in lists {
+            let Some((element, key, value, tuple)) = self.map_entry(&list) else {
+                continue;
+            };
+            let key_arg = self.type_name(&key, &namespc, Flavor::Argument(AbiVariant::GuestImport));
+            let string_key = matches!(dealias(self.resolve, &key), Type::String);
+            for borrowed in [true, false] {
+                let flavor = if borrowed {
+                    Flavor::Argument(AbiVariant::GuestImport).nested()
+                } else {
+                    Flavor::InStruct
+                };
+                let entry = self.type_name(&element, &namespc, flavor);
+                // records have a single definition with owned fields
+                let owned_fields = !borrowed || !tuple;
+                let value = self.type_name(
+                    &value,
+                    &namespc,
+                    if owned_fields {
+                        Flavor::InStruct
+                    } else {
+                        flavor
+                    },
+                );
+                let map = if borrowed {
+                    format!("{} map", self.borrowed_list(&entry))
+                } else {
+                    let bound = if self.gen.opts.lifetimebound {
+                        " WIT_LIFETIMEBOUND"
+                    } else {
+                        ""
+                    };
+                    format!("wit::vector<{entry}> const& map{bound}")
+                };
+                let signature = format!("{value} const* find({map}, {key_arg} key)");
+                if !self.gen.is_first_definition(&namespc, &signature) {
+                    continue;
+                }
+                let (key_field, value_field) = if tuple {
+                    ("std::get<0>(map[i])", "std::get<1>(map[i])")
+                } else {
+                    ("map[i].key", "map[i].value")
+                };
+                let view = if string_key && owned_fields {
+                    ".get_view()"
+                } else {
+                    ""
+                };
+                self.gen.h_src.change_namespace(&namespc);
+                uwriteln!(self.gen.h_src.src, "inline {signature} {{");
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "for (size_t i = 0; i < map.size(); ++i) {{
+                        if ({key_field}{view} == key) {{ return &{value_field}; }}
+                    }}
+                    return nullptr;
+                    }}"
+                );
+            }
+        }
+    }
+
+    /// Inline overload of an imported function taking `std::vector` for list
+    /// parameters, only for element types a vector can't be viewed as
+    fn vector_overload(
+        &mut self,
+        func: &Function,
+        cpp_sig: &HighlevelSignature,
+        from_namespace: &Vec<String>,
+    ) {
+        let list_element = |ty: &Type| match dealias(self.resolve, ty) {
+            Type::Id(id) => match &self.resolve.types[id].kind {
+                TypeDefKind::List(element) => Some(dealias(self.resolve, element)),
+                _ => None,
+            },
+            _ => None,
+        };
+        if !func
+            .params
+            .iter()
+            .any(|(_, ty)| matches!(list_element(ty), Some(Type::Bool | Type::String)))
+        {
+            return;
+        }
+        self.gen.dependencies.needs_vector = true;
+        let mut params = Vec::new();
+        let mut copies = String::new();
+        let mut args = Vec::new();
+        for ((name, typ), (_, ty)) in cpp_sig.arguments.iter().zip(func.params.iter()) {
+            match list_element(ty) {
+                // std::vector<bool> stores bits
+                Some(Type::Bool) => {
+                    self.gen.dependencies.needs_memory = true;
+                    params.push(format!("std::vector<bool> const& {name}"));
+                    uwriteln!(
+                        copies,
+                        "std::unique_ptr<bool[]> {name}_elements(new bool[{name}.size()]);
+                        for (size_t i = 0; i < {name}.size(); ++i) {{ {name}_elements[i] = {name}[i]; }}"
+                    );
+                    args.push(format!("{typ}({name}_elements.get(), {name}.size())"));
+                }
+                Some(Type::String) => {
+                    self.gen.dependencies.needs_string = true;
+                    let view = self.borrowed_string();
+                    params.push(format!("std::vector<std::string> const& {name}"));
+                    uwriteln!(
+                        copies,
+                        "std::vector<{view}> {name}_views({name}.begin(), {name}.end());"
+                    );
+                    args.push(format!("{name}_views"));
+                }
+                // viewed without a copy
+                Some(element) => {
+                    let element = self.type_name(
+                        &element,
+                        from_namespace,
+                        Flavor::Argument(AbiVariant::GuestImport).nested(),
+                    );
+                    params.push(format!("std::vector<{element}> const& {name}"));
+                    args.push(name.clone());
+                }
+                None if typ.ends_with("&&") => {
+                    params.push(format!("{typ} {name}"));
+                    args.push(format!("std::move({name})"));
+                }
+                None => {
+                    params.push(format!("{typ} {name}"));
+                    args.push(name.clone());
+                }
+            }
+        }
+        uwriteln!(
+            self.gen.h_src.src,
+            "inline {} {}({}) {{",
+            cpp_sig.result,
+            cpp_sig.name,
+            params.join(", ")
+        );
+        self.gen.h_src.src.push_str(&copies);
+        uwriteln!(
+            self.gen.h_src.src,
+            "return {}({});
+            }}",
+            cpp_sig.name,
+            args.join(", ")
+        );
+    }
+
+    fn is_single_function(&self, func: &Function) -> bool {
+        matches!(func.kind, FunctionKind::Freestanding)
+            && self
+                .interface
+                .is_some_and(|id| self.resolve.interfaces[id].functions.len() == 1)
+    }
+
+    /// Function object forwarding to the only function of an interface, named
+    /// after the interface as inline interfaces share the world's namespace
+    fn single_func_functor(&mut self, func: &Function, cpp_sig: &HighlevelSignature) {
+        let interface = match self._name {
+            Some(WorldKey::Name(name)) => name.as_str(),
+            _ => self
+                .interface
+                .and_then(|id| self.resolve.interfaces[id].name.as_deref())
+                .unwrap_or_default(),
+        };
+        let params = cpp_sig
+            .arguments
+            .iter()
+            .map(|(name, typ)| format!("{typ} {name}"))
+            .collect::<Vec<_>>();
+        // compound values can be move-only
+        let args = cpp_sig
+            .arguments
+            .iter()
+            .zip(func.params.iter())
+            .map(|((name, typ), (_, ty))| {
+                if typ.ends_with("&&") || matches!(ty, Type::Id(_)) {
+                    format!("std::move({name})")
+                } else {
+                    name.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        uwriteln!(
+            self.gen.h_src.src,
+            "struct {}Functor {{
+            {} operator()({}) const{} {{ return {}({}); }}
+            }};",
+            interface.to_pascal_case(),
+            cpp_sig.result,
+            params.join(", "),
+            noexcept_spec(cpp_sig.noexcept),
+            cpp_sig.name,
+            args.join(", ")
+        );
+    }
+
+    /// Forward to an interface function from the `World` facade, with the
+    /// parameter types qualified from the global namespace
+    fn facade_method(&mut self, func: &Function, variant: AbiVariant) {
+        let cpp_sig = self.high_level_signature(func, variant, &Vec::new());
+        let types = func
+            .params
+            .iter()
+            .map(|(_, ty)| self.scalar_type_name(ty, &Vec::new(), Flavor::Argument(variant)))
+            .collect::<Vec<_>>();
+        let interface = &self.resolve.interfaces[self.interface.unwrap()];
+        let mut interface = interface
+            .name
+            .as_deref()
+            .unwrap_or_default()
+            .to_snake_case();
+        if matches!(variant, AbiVariant::GuestExport) {
+            interface = format!("exports_{interface}");
+        }
+        let params = cpp_sig
+            .arguments
+            .iter()
+            .zip(types.iter())
+            .map(|((name, _), typ)| format!("{typ} {name}"))
+            .collect::<Vec<_>>();
+        // compound values and the strings of exports can be move-only
+        let args = cpp_sig
+            .arguments
+            .iter()
+            .zip(func.params.iter())
+            .map(|((name, _), (_, ty))| match ty {
+                Type::Id(_) => format!("std::move({name})"),
+                Type::String if matches!(variant, AbiVariant::GuestExport) => {
+                    format!("std::move({name})")
+                }
+                _ => name.clone(),
+            })
+            .collect::<Vec<_>>();
+        // the result is named relative to the interface namespace
+        let rest = format!(
+            "({}){} {{ return ::{}::{}({}); }}",
+            params.join(", "),
+            noexcept_spec(cpp_sig.noexcept),
+            cpp_sig.namespace.join("::"),
+            cpp_sig.name,
+            args.join(", ")
+        );
+        self.gen.facade.push(FacadeMethod {
+            interface,
+            name: func.name.to_snake_case(),
+            rest,
+        });
+    }
+
+    /// The C and C++ type of a value passed by a `--abi=preview1-compat`
+    /// function, `None` outside of the supported scalars and enums
+    fn preview1_type(&mut self, ty: &Type) -> Option<(String, String)> {
+        let flavor = Flavor::Argument(AbiVariant::GuestImport);
+        match dealias(self.resolve, ty) {
+            Type::Id(id) => match &self.resolve.types[id].kind {
+                TypeDefKind::Enum(_) => {
+                    Some(("int32_t".into(), self.type_name(ty, &Vec::new(), flavor)))
+                }
+                _ => None,
+            },
+            Type::String => None,
+            Type::Char => Some(("uint32_t".into(), "uint32_t".into())),
+            _ => {
+                let name = self.type_name(ty, &Vec::new(), flavor);
+                Some((name.clone(), name))
+            }
+        }
+    }
+
+    /// Add a preview1 style C function calling the import `func`, if its
+    /// types are in the subset described at `--abi=preview1-compat`
+    fn preview1_adapter(&mut self, func: &Function, cpp_sig: &HighlevelSignature) {
+        let mut params = Vec::new();
+        let mut args = Vec::new();
+        for ((name, _), (_, ty)) in cpp_sig.arguments.iter().zip(func.params.iter()) {
+            let Some((c_type, cpp_type)) = self.preview1_type(ty) else {
+                return;
+            };
+            params.push(format!("{c_type} {name}"));
+            args.push(match c_type == cpp_type {
+                true => name.clone(),
+                false => format!("{cpp_type}({name})"),
+            });
+        }
+        let callee = format!(
+            "{}::{}",
+            cpp_sig
+                .namespace
+                .iter()
+                .map(|ns| format!("::{ns}"))
+                .collect::<String>(),
+            cpp_sig.name
+        );
+        let call = format!("{callee}({})", args.join(", "));
+        let result = match &func.results {
+            Results::Anon(ty) => Some(dealias(self.resolve, ty)),
+            Results::Named(named) if named.is_empty() => None,
+            Results::Named(_) => return,
+        };
+        let (result, body) = match result {
+            None => ("void".to_string(), format!("{call};")),
+            Some(Type::Id(id)) if matches!(self.resolve.types[id].kind, TypeDefKind::Result(_)) => {
+                let TypeDefKind::Result(r) = &self.resolve.types[id].kind else {
+                    unreachable!()
+                };
+                let ok = r.ok;
+                // the errno is the enum case
+                let enum_error = r.err.is_some_and(|err| match dealias(self.resolve, &err) {
+                    Type::Id(id) => matches!(self.resolve.types[id].kind, TypeDefKind::Enum(_)),
+                    _ => false,
+                });
+                if !enum_error {
+                    return;
+                }
+                let store = match ok {
+                    Some(ok) => {
+                        let Some((c_type, _)) = self.preview1_type(&ok) else {
+                            return;
+                        };
+                        params.push(format!("{c_type}* retptr"));
+                        format!("*retptr = {c_type}(result.value());\n")
+                    }
+                    None => String::new(),
+                };
+                (
+                    "int32_t".to_string(),
+                    format!(
+                        "auto result = {call};\n\
+                         if (!result.has_value()) {{ return int32_t(result.error()) + 1; }}\n\
+                         {store}return 0;"
+                    ),
+                )
+            }
+            Some(ty) => {
+                let Some((c_type, _)) = self.preview1_type(&ty) else {
+                    return;
+                };
+                let body = format!("return {c_type}({call});");
+                (c_type, body)
+            }
+        };
+        let declaration = format!(
+            "{result} {}({})",
+            self.import_vtable_member(func),
+            params.join(", ")
+        );
+        let definition = format!("extern \"C\" {declaration} {{\n{body}\n}}");
+        self.gen.preview1_adapters.push((declaration, definition));
+    }
+
     fn print_signature(
         &mut self,
         func: &Function,
@@ -1282,6 +3285,33 @@ impl CppInterfaceGenerator<'_> {
         {
             let from_namespace = self.gen.h_src.namespace.clone();
             let cpp_sig = self.high_level_signature(func, variant, &from_namespace);
+            if self.uses_import_vtable(func, variant) {
+                let ty = format!(
+                    "decltype(&::{}::{})",
+                    cpp_sig.namespace.join("::"),
+                    cpp_sig.name
+                );
+                let member = self.import_vtable_member(func);
+                self.gen.import_vtable.push((ty, member));
+            }
+            if self.uses_import_context(func, variant) {
+                // the struct is global, so the types come from the declaration
+                let ty = format!(
+                    "std::function<std::remove_pointer_t<decltype(&::{}::{})>>",
+                    cpp_sig.namespace.join("::"),
+                    cpp_sig.name
+                );
+                let member = self.import_vtable_member(func);
+                self.gen.dependencies.needs_functional = true;
+                self.gen.dependencies.needs_type_traits = true;
+                self.gen.import_context.push((ty, member));
+            }
+            if cpp_sig.noexcept == Some(false) {
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "// allocates during lowering, which aborts when out of memory"
+                );
+            }
             if cpp_sig.static_member {
                 self.gen.h_src.src.push_str("static ");
             }
@@ -1299,7 +3329,12 @@ impl CppInterfaceGenerator<'_> {
             self.gen.h_src.src.push_str("(");
             if
             /*import &&*/
-            self.gen.opts.host && !matches!(func.kind, FunctionKind::Method(_)) {
+            self.gen.opts.host
+                && !matches!(func.kind, FunctionKind::Method(_))
+                // the Owned deleter has no execution environment to pass
+                && !(matches!(is_special, SpecialMethod::ResourceDrop)
+                    && matches!(variant, AbiVariant::GuestImport))
+            {
                 self.gen.h_src.src.push_str("WASMExecEnv* exec_env");
                 if !cpp_sig.arguments.is_empty() {
                     self.gen.h_src.src.push_str(", ");
@@ -1317,18 +3352,21 @@ impl CppInterfaceGenerator<'_> {
             if cpp_sig.const_member {
                 self.gen.h_src.src.push_str(" const");
             }
+            self.gen.h_src.src.push_str(noexcept_spec(cpp_sig.noexcept));
             match (&is_special, self.gen.opts.host_side(), &variant) {
                 (SpecialMethod::Allocate, _, _) => {
-                    uwrite!(
+                    uwriteln!(
                         self.gen.h_src.src,
                         "{{\
                         return {OWNED_CLASS_NAME}(new {}({}));\
                     }}",
                         cpp_sig.namespace.last().unwrap(), //join("::"),
-                        cpp_sig
-                            .arguments
-                            .iter()
-                            .map(|(arg, _)| arg.clone())
+                        self.gen
+                            .opts
+                            .host
+                            .then(|| String::from("exec_env"))
+                            .into_iter()
+                            .chain(cpp_sig.arguments.iter().map(|(arg, _)| arg.clone()))
                             .collect::<Vec<_>>()
                             .join(", ")
                     );
@@ -1350,6 +3388,34 @@ impl CppInterfaceGenerator<'_> {
                 // SpecialMethod::ResourceNew => todo!(),
                 _ => self.gen.h_src.src.push_str(";\n"),
             }
+            if self.gen.opts.vector_overloads
+                && import
+                && !self.gen.opts.host_side()
+                && matches!(func.kind, FunctionKind::Freestanding)
+            {
+                self.vector_overload(func, &cpp_sig, &from_namespace);
+            }
+            if self.gen.opts.single_func_functor
+                && import
+                && !self.gen.opts.host_side()
+                && self.is_single_function(func)
+            {
+                self.single_func_functor(func, &cpp_sig);
+            }
+            if self.gen.opts.flat_facade
+                && !self.gen.opts.host_side()
+                && self.interface.is_some()
+                && matches!(func.kind, FunctionKind::Freestanding)
+            {
+                self.facade_method(func, variant);
+            }
+            if self.gen.opts.abi == Abi::Preview1Compat
+                && import
+                && !self.gen.opts.host_side()
+                && matches!(func.kind, FunctionKind::Freestanding)
+            {
+                self.preview1_adapter(func, &cpp_sig);
+            }
         }
         //        drop(cpp_sig);
 
@@ -1412,11 +3478,310 @@ impl CppInterfaceGenerator<'_> {
             if cpp_sig.const_member {
                 self.gen.c_src.src.push_str(" const");
             }
+            self.gen.c_src.src.push_str(noexcept_spec(cpp_sig.noexcept));
             self.gen.c_src.src.push_str("\n");
             params
         }
     }
 
+    /// The generated code of a function, after freeing the lists collected
+    /// during lowering
+    fn push_body(&mut self, needs_cleanup_list: bool, code: &str) {
+        if needs_cleanup_list {
+            self.gen.dependencies.needs_vector = true;
+            self.gen.dependencies.needs_wit = true;
+            uwriteln!(
+                self.gen.c_src.src,
+                "std::vector<void*> cleanup_list;
+                wit::scope_guard cleanup_guard([&cleanup_list] {{
+                    for (auto ptr : cleanup_list) {{
+                        free(ptr);
+                    }}
+                }});"
+            );
+        }
+        self.gen.c_src.src.push_str(code);
+    }
+
+    /// `Try<Name>` calling the import `func`, results which fail to lift
+    /// return a `wit::decode_error` (see `--try-call`)
+    fn try_call_wrapper(&mut self, func: &Function, owner: &TypeOwner, variant: AbiVariant) {
+        if !self.gen.opts.try_call
+            || self.gen.opts.host_side()
+            || self.gen.opts.symmetric
+            || !matches!(variant, AbiVariant::GuestImport)
+            || !matches!(func.kind, FunctionKind::Freestanding)
+            || !func.results.iter_types().any(|ty| self.lift_can_fail(ty))
+        {
+            return;
+        }
+        self.gen.dependencies.needs_expected = true;
+        self.gen.dependencies.needs_wit = true;
+        let result_template = self.gen.opts.result_template();
+        let signature = |sig: &HighlevelSignature| {
+            let args = sig
+                .arguments
+                .iter()
+                .map(|(arg, typ)| format!("{typ} {arg}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            (
+                format!("{result_template}<{}, wit::decode_error>", sig.result),
+                format!("Try{}({args}){}", sig.name, noexcept_spec(sig.noexcept)),
+            )
+        };
+
+        let h_namespace = self.gen.h_src.namespace.clone();
+        let h_sig = self.high_level_signature(func, variant, &h_namespace);
+        let (result, call) = signature(&h_sig);
+        uwriteln!(self.gen.h_src.src, "{result} {call};");
+
+        let c_namespace = self.gen.c_src.namespace.clone();
+        let c_sig = self.high_level_signature(func, variant, &c_namespace);
+        let (result, call) = signature(&c_sig);
+        uwrite!(self.gen.c_src.src, "{result} ");
+        self.gen.c_src.qualify(&c_sig.namespace);
+        uwriteln!(self.gen.c_src.src, "{call}\n{{");
+        let params = c_sig.arguments.iter().map(|(arg, _)| arg.clone()).collect();
+        let mut f = FunctionBindgen::new(self, params);
+        f.namespace = namespace(f.gen.resolve, owner, false, &f.gen.gen.opts);
+        f.variant = variant;
+        f.decode_error = Some(f.gen.gen.opts.unexpected_template());
+        abi::call(
+            f.gen.resolve,
+            variant,
+            LiftLower::LowerArgsLiftResults,
+            func,
+            &mut f,
+        );
+        let needs_cleanup_list = f.needs_cleanup_list;
+        let code = String::from(f.src);
+        self.push_body(needs_cleanup_list, &code);
+        self.gen.c_src.src.push_str("}\n");
+    }
+
+    /// Whether lifting `ty` checks discriminants, which come from the other
+    /// side of the boundary
+    fn lift_can_fail(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Id(id) => match &self.resolve.types[*id].kind {
+                TypeDefKind::Record(r) => r.fields.iter().any(|f| self.lift_can_fail(&f.ty)),
+                TypeDefKind::Tuple(t) => t.types.iter().any(|t| self.lift_can_fail(t)),
+                TypeDefKind::Option(_) | TypeDefKind::Result(_) => true,
+                TypeDefKind::Variant(v) => payloadless_enum(v).is_none(),
+                TypeDefKind::List(t) | TypeDefKind::Type(t) => self.lift_can_fail(t),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    // The return area of an export is only released by its cabi_post,
+    // so a re-entrant call of the same export has to use a fresh one.
+    fn shared_ret_area(&self, func: &Function, variant: AbiVariant) -> Option<String> {
+        if self.gen.opts.host_side()
+            || self.gen.opts.symmetric
+            || !matches!(variant, AbiVariant::GuestExport)
+            || !abi::guest_export_needs_post_return(self.resolve, func)
+            || !self.patched_wasm_signature(variant, func).retptr
+        {
+            return None;
+        }
+        let symbol = match &self.wasm_import_module {
+            Some(module_name) => make_external_symbol(module_name, &func.name, variant),
+            None => make_external_component(&func.name),
+        };
+        Some(format!("ret_area_{symbol}"))
+    }
+
+    // an export returning `list<u8>` as a view (see `--span-byte-results`)
+    fn is_span_byte_result(&self, func: &Function, variant: AbiVariant) -> bool {
+        if !self.gen.opts.span_byte_results
+            || self.gen.opts.host_side()
+            || !matches!(variant, AbiVariant::GuestExport)
+        {
+            return false;
+        }
+        match &func.results {
+            Results::Anon(ty) => match dealias(self.resolve, ty) {
+                Type::Id(id) => matches!(
+                    &self.resolve.types[id].kind,
+                    TypeDefKind::List(elem) if dealias(self.resolve, elem) == Type::U8
+                ),
+                _ => false,
+            },
+            Results::Named(_) => false,
+        }
+    }
+
+    fn uses_import_vtable(&self, func: &Function, variant: AbiVariant) -> bool {
+        self.gen.opts.import_vtable
+            && self.gen.opts.host
+            && matches!(variant, AbiVariant::GuestImport)
+            && matches!(func.kind, FunctionKind::Freestanding)
+    }
+
+    fn uses_import_context(&self, func: &Function, variant: AbiVariant) -> bool {
+        self.gen.opts.import_context
+            && !self.gen.opts.import_vtable
+            && self.gen.opts.host
+            && matches!(variant, AbiVariant::GuestImport)
+            && matches!(func.kind, FunctionKind::Freestanding)
+    }
+
+    // the host implements imports with `std::wstring` for the top level
+    // strings (see `--host-string-type=wstring`)
+    fn wide_host_import(&self, variant: AbiVariant) -> bool {
+        self.gen.opts.host
+            && !self.gen.opts.short_cut
+            && self.gen.opts.host_string_type == HostStringType::Wstring
+            && matches!(variant, AbiVariant::GuestImport)
+    }
+
+    fn import_vtable_member(&self, func: &Function) -> String {
+        let (namespace, _) = self.func_namespace_name(func, false, false);
+        let mut member = namespace.join("_");
+        member.push('_');
+        member.push_str(&func.name.to_snake_case());
+        member
+    }
+
+    /// Add a method calling the export `func` to the host side `Instance`
+    fn instance_method(&mut self, func: &Function, variant: AbiVariant) {
+        if !self.gen.opts.host
+            || self.gen.opts.short_cut
+            || !matches!(variant, AbiVariant::GuestExport)
+            || !matches!(func.kind, FunctionKind::Freestanding)
+        {
+            return;
+        }
+        // qualify all types from the global namespace
+        let global = Vec::new();
+        let cpp_sig = self.high_level_signature(func, variant, &global);
+        let mut result = match &func.results {
+            Results::Named(named) if !named.is_empty() => {
+                let types = named
+                    .iter()
+                    .map(|(_, ty)| self.type_name(ty, &global, Flavor::Result(variant)))
+                    .collect::<Vec<_>>();
+                format!("std::tuple<{}>", types.join(", "))
+            }
+            _ => cpp_sig.result.clone(),
+        };
+        if cpp_sig.post_return {
+            result = format!("wit::guest_owned<{result}>");
+        }
+        let string_type = self.gen.opts.host_string_type;
+        let resolve = self.resolve;
+        let std_string =
+            |ty: &Type| string_type != HostStringType::Wit && dealias(resolve, ty) == Type::String;
+        // the wide result is converted before the guest memory is released
+        let wide_result = string_type == HostStringType::Wstring
+            && matches!(&func.results, Results::Anon(ty) if std_string(ty));
+        if wide_result {
+            result = String::from("std::wstring");
+        }
+        let mut params = func
+            .params
+            .iter()
+            .map(|(name, ty)| {
+                let typename = if std_string(ty) {
+                    self.gen.dependencies.needs_string = true;
+                    match string_type {
+                        HostStringType::Wstring => String::from("std::wstring"),
+                        _ => String::from("std::string"),
+                    }
+                } else {
+                    self.scalar_type_name(ty, &global, Flavor::Argument(variant))
+                };
+                format!("{typename} {}", param_name(name))
+            })
+            .collect::<Vec<_>>();
+        let method = match self.interface {
+            Some(id) => format!(
+                "{}_{}",
+                self.resolve.interfaces[id]
+                    .name
+                    .as_deref()
+                    .unwrap_or_default()
+                    .to_snake_case(),
+                func.name.to_snake_case()
+            ),
+            None => func.name.to_snake_case(),
+        };
+        let mut args = vec![String::from("exec_env")];
+        args.extend(func.params.iter().map(|(name, ty)| {
+            let name = param_name(name);
+            if std_string(ty) && string_type == HostStringType::Wstring {
+                format!("wit::string::from_view(exec_env, wit::to_utf8({name}))")
+            } else if std_string(ty) {
+                format!("wit::string::from_view(exec_env, {name})")
+            } else {
+                format!("std::move({name})")
+            }
+        }));
+        let (declaration, class) = match self.gen.opts.export_style {
+            ExportStyle::Instance => (
+                format!("{result} {method}({});", params.join(", ")),
+                String::from("Instance"),
+            ),
+            ExportStyle::StaticMethods => {
+                params.insert(0, String::from("WASMExecEnv* exec_env"));
+                (
+                    format!("static {result} {method}({});", params.join(", ")),
+                    self.gen.world.to_pascal_case(),
+                )
+            }
+        };
+        let mut call = format!(
+            "::{}::{}({})",
+            cpp_sig.namespace.join("::"),
+            cpp_sig.name,
+            args.join(", ")
+        );
+        if wide_result {
+            call = format!("wit::to_wstring({call})");
+        }
+        let call = format!("{{\nreturn {call};\n}}");
+        let definition = format!("{result} {class}::{method}({}) {call}", params.join(", "));
+        self.gen.instance_methods.push((declaration, definition));
+
+        // like `exports.test_flavorful_test()` of the Rust host bindings
+        if let (Some(id), ExportStyle::Instance) = (self.interface, self.gen.opts.export_style) {
+            let iface = &self.resolve.interfaces[id];
+            let Some(name) = &iface.name else {
+                return;
+            };
+            let pkg = &self.resolve.packages[iface.package.unwrap()].name;
+            let accessor = format!(
+                "{}_{}_{}",
+                pkg.namespace.to_snake_case(),
+                pkg.name.to_snake_case(),
+                name.to_snake_case()
+            );
+            let class = accessor.to_pascal_case();
+            let method = to_c_ident(&func.name.to_snake_case());
+            let declaration = format!("{result} {method}({});", params.join(", "));
+            let definition = format!(
+                "{result} Instance::{class}::{method}({}) {call}",
+                params.join(", ")
+            );
+            let proxies = &mut self.gen.instance_proxies;
+            let index = match proxies.iter().position(|p| p.accessor == accessor) {
+                Some(index) => index,
+                None => {
+                    proxies.push(InstanceProxy {
+                        accessor,
+                        interface: self.resolve.id_of(id).unwrap(),
+                        methods: Vec::new(),
+                    });
+                    proxies.len() - 1
+                }
+            };
+            proxies[index].methods.push((declaration, definition));
+        }
+    }
+
     fn generate_function(
         &mut self,
         func: &Function,
@@ -1448,6 +3813,7 @@ impl CppInterfaceGenerator<'_> {
             AbiVariant::GuestExport => !self.gen.opts.host_side(),
         };
         let params = self.print_signature(func, variant, !export);
+        self.instance_method(func, variant);
         let special = is_special_method(func);
         if !matches!(special, SpecialMethod::Allocate) {
             self.gen.c_src.src.push_str("{\n");
@@ -1472,6 +3838,7 @@ impl CppInterfaceGenerator<'_> {
                                 params[0]
                             );
                             self.gen.dependencies.needs_assert = true;
+                            uwriteln!(self.gen.c_src.src, "(*ptr)->on_drop();");
                             self.gen.c_src.qualify(&namespace);
                             uwriteln!(self.gen.c_src.src, "Dtor(*ptr);")
                         } else {
@@ -1504,10 +3871,11 @@ impl CppInterfaceGenerator<'_> {
                                 &[WasmType::I32],
                                 &[],
                             );
+                            let handle = self.gen.opts.member("handle");
                             uwriteln!(
                                 self.gen.c_src.src,
-                                "   if (handle>=0) {{
-                                {name}(handle);
+                                "   if ({handle}>=0) {{
+                                {name}({handle});
                             }}"
                             );
                         }
@@ -1533,10 +3901,11 @@ impl CppInterfaceGenerator<'_> {
                                 &[WasmType::Pointer],
                                 &[],
                             );
+                            let handle = self.gen.opts.member("handle");
                             uwriteln!(
                                 self.gen.c_src.src,
-                                "   if (handle!=nullptr) {{
-                                {name}(handle);
+                                "   if ({handle}!=nullptr) {{
+                                {name}({handle});
                             }}"
                             );
                         }
@@ -1666,6 +4035,8 @@ impl CppInterfaceGenerator<'_> {
                         f.wamr_signature = Some(wamr::wamr_signature(&f.gen.resolve, func));
                     }
                     f.variant = variant;
+                    f.span_result = f.gen.is_span_byte_result(func, variant);
+                    f.shared_ret_area = f.gen.shared_ret_area(func, variant);
                     f.cabi_post = if matches!(variant, AbiVariant::GuestExport)
                         && f.gen.gen.opts.host_side()
                         && abi::guest_export_needs_post_return(f.gen.resolve, func)
@@ -1686,11 +4057,15 @@ impl CppInterfaceGenerator<'_> {
                         None
                     };
                     abi::call(f.gen.resolve, variant, lift_lower, func, &mut f);
+                    let needs_cleanup_list = f.needs_cleanup_list;
                     let code = String::from(f.src);
-                    self.gen.c_src.src.push_str(&code);
+                    self.push_body(needs_cleanup_list, &code);
                 }
             }
             self.gen.c_src.src.push_str("}\n");
+            if matches!(special, SpecialMethod::None) {
+                self.try_call_wrapper(func, owner, variant);
+            }
             // cabi_post
             if !self.gen.opts.host_side()
                 && matches!(variant, AbiVariant::GuestExport)
@@ -1755,9 +4130,28 @@ impl CppInterfaceGenerator<'_> {
                 }
                 self.gen.c_src.src.push_str(") {\n");
 
+                let shared_ret_area = self.shared_ret_area(func, variant);
+                let span_result = self.is_span_byte_result(func, variant);
+                if let Some(ret_area) = shared_ret_area {
+                    // released even if dropping the results throws
+                    self.gen.dependencies.needs_wit = true;
+                    uwriteln!(
+                        self.gen.c_src.src,
+                        "wit::scope_guard release([arg0] {{
+                            if (arg0 == (uint8_t*)(&{ret_area})) {{
+                                {ret_area}_busy = false;
+                            }} else {{
+                                free(arg0);
+                            }}
+                        }});"
+                    );
+                }
                 let mut f = FunctionBindgen::new(self, params.clone());
                 f.params = params;
-                abi::post_return(f.gen.resolve, func, &mut f);
+                // the implementation owns the bytes of a span
+                if !span_result {
+                    abi::post_return(f.gen.resolve, func, &mut f);
+                }
                 let FunctionBindgen { src, .. } = f;
                 self.gen.c_src.src.push_str(&src);
                 self.gen.c_src.src.push_str("}\n");
@@ -1765,50 +4159,6 @@ impl CppInterfaceGenerator<'_> {
         }
     }
 
-    pub fn type_path(&self, id: TypeId, owned: bool) -> String {
-        self.type_path_with_name(
-            id,
-            if owned {
-                self.result_name(id)
-            } else {
-                self.param_name(id)
-            },
-        )
-    }
-
-    fn type_path_with_name(&self, id: TypeId, name: String) -> String {
-        if let TypeOwner::Interface(id) = self.resolve.types[id].owner {
-            if let Some(path) = self.path_to_interface(id) {
-                return format!("{path}::{name}");
-            }
-        }
-        name
-    }
-
-    fn path_to_interface(&self, interface: InterfaceId) -> Option<String> {
-        let iface = &self.resolve.interfaces[interface];
-        let name = iface.name.as_ref().unwrap();
-        let mut full_path = String::new();
-        full_path.push_str(name);
-        Some(full_path)
-    }
-
-    fn param_name(&self, ty: TypeId) -> String {
-        self.resolve.types[ty]
-            .name
-            .as_ref()
-            .unwrap()
-            .to_upper_camel_case()
-    }
-
-    fn result_name(&self, ty: TypeId) -> String {
-        self.resolve.types[ty]
-            .name
-            .as_ref()
-            .unwrap()
-            .to_upper_camel_case()
-    }
-
     // in C this is print_optional_ty
     fn optional_type_name(
         &mut self,
@@ -1822,6 +4172,28 @@ impl CppInterfaceGenerator<'_> {
         }
     }
 
+    // std::expected doesn't allow a void error
+    fn error_type_name(
+        &mut self,
+        ty: Option<&Type>,
+        from_namespace: &Vec<String>,
+        flavor: Flavor,
+    ) -> String {
+        match ty {
+            Some(ty) => self.type_name(ty, from_namespace, flavor),
+            None => {
+                self.gen.dependencies.needs_wit = true;
+                "wit::Void".into()
+            }
+        }
+    }
+
+    fn is_optional_result(&self, result: &wit_bindgen_core::wit_parser::Result_) -> bool {
+        self.gen.opts.bare_error == BareError::Optional
+            && result.ok.is_some()
+            && result.err.is_none()
+    }
+
     fn scoped_type_name(
         &self,
         id: TypeId,
@@ -1833,11 +4205,50 @@ impl CppInterfaceGenerator<'_> {
         let mut relative = SourceWithState::default();
         relative.namespace = from_namespace.clone();
         relative.qualify(&namespc);
-        format!(
-            "{}{}",
-            relative.src.to_string(),
-            ty.name.as_ref().unwrap().to_pascal_case()
-        )
+        format!("{}{}", &*relative.src, self.type_ident(id))
+    }
+
+    /// The `--rename-type` name of a type, resources keep their class name
+    fn type_rename(&self, id: TypeId) -> Option<String> {
+        let ty = &self.resolve.types[id];
+        if matches!(ty.kind, TypeDefKind::Resource) {
+            return None;
+        }
+        let mut wit_namespace = namespace(self.resolve, &ty.owner, false, &self.gen.opts);
+        if self.gen.opts.internal_prefix.is_some() {
+            wit_namespace.remove(0);
+        }
+        let key = format!("{}/{}", wit_namespace.join("."), ty.name.as_ref()?);
+        self.gen
+            .opts
+            .rename_type
+            .iter()
+            .find(|(from, _)| *from == key)
+            .map(|(_, to)| to.clone())
+    }
+
+    /// The C++ identifier of a named type
+    fn type_ident(&self, id: TypeId) -> String {
+        self.type_rename(id).unwrap_or_else(|| {
+            self.resolve.types[id]
+                .name
+                .as_ref()
+                .unwrap()
+                .to_pascal_case()
+        })
+    }
+
+    /// `using OldName = NewName;` for a renamed type (`--emit-compat-typedefs`)
+    fn compat_typedef(&mut self, id: TypeId, name: &str) {
+        if !self.gen.opts.emit_compat_typedefs {
+            return;
+        }
+        if let Some(renamed) = self.type_rename(id) {
+            let pascal = name.to_pascal_case();
+            if pascal != renamed {
+                uwriteln!(self.gen.h_src.src, "using {pascal} = {renamed};");
+            }
+        }
     }
 
     fn type_name(&mut self, ty: &Type, from_namespace: &Vec<String>, flavor: Flavor) -> String {
@@ -1855,10 +4266,7 @@ impl CppInterfaceGenerator<'_> {
             Type::F32 => "float".into(),
             Type::F64 => "double".into(),
             Type::String => match flavor {
-                Flavor::Argument(AbiVariant::GuestImport) => {
-                    self.gen.dependencies.needs_string_view = true;
-                    "std::string_view".into()
-                }
+                Flavor::Argument(AbiVariant::GuestImport) => self.borrowed_string(),
                 Flavor::Argument(AbiVariant::GuestExport) if !self.gen.opts.host_side() => {
                     self.gen.dependencies.needs_wit = true;
                     "wit::string &&".into()
@@ -1911,7 +4319,7 @@ impl CppInterfaceGenerator<'_> {
                         if !a.is_empty() {
                             a += ", ";
                         }
-                        a + &self.type_name(b, from_namespace, flavor)
+                        a + &self.type_name(b, from_namespace, flavor.nested())
                     });
                     self.gen.dependencies.needs_tuple = true;
                     String::from("std::tuple<") + &types + ">"
@@ -1924,24 +4332,36 @@ impl CppInterfaceGenerator<'_> {
                 }
                 TypeDefKind::Option(o) => {
                     self.gen.dependencies.needs_optional = true;
-                    "std::optional<".to_string() + &self.type_name(o, from_namespace, flavor) + ">"
+                    "std::optional<".to_string()
+                        + &self.type_name(o, from_namespace, flavor.nested())
+                        + ">"
+                }
+                TypeDefKind::Result(r) if self.is_optional_result(r) => {
+                    self.gen.dependencies.needs_optional = true;
+                    "std::optional<".to_string()
+                        + &self.optional_type_name(r.ok.as_ref(), from_namespace, flavor.nested())
+                        + ">"
                 }
                 TypeDefKind::Result(r) => {
                     self.gen.dependencies.needs_expected = true;
-                    "std::expected<".to_string()
-                        + &self.optional_type_name(r.ok.as_ref(), from_namespace, flavor)
+                    self.gen.opts.result_template().to_string()
+                        + "<"
+                        + &self.optional_type_name(r.ok.as_ref(), from_namespace, flavor.nested())
                         + ", "
-                        + &self.optional_type_name(r.err.as_ref(), from_namespace, flavor)
+                        + &self.error_type_name(r.err.as_ref(), from_namespace, flavor.nested())
                         + ">"
                 }
                 TypeDefKind::List(ty) => {
-                    let inner = self.type_name(ty, from_namespace, flavor);
+                    // borrowed elements name the exported resource
+                    let inner_flavor = if self.is_borrow(ty) {
+                        flavor
+                    } else {
+                        flavor.nested()
+                    };
+                    let inner = self.type_name(ty, from_namespace, inner_flavor);
                     match flavor {
                         //self.gen.dependencies.needs_vector = true;
-                        Flavor::Argument(AbiVariant::GuestImport) => {
-                            self.gen.dependencies.needs_wit = true;
-                            format!("wit::span<{inner} const>")
-                        }
+                        Flavor::Argument(AbiVariant::GuestImport) => self.borrowed_list(&inner),
                         Flavor::Argument(AbiVariant::GuestExport) if !self.gen.opts.host => {
                             self.gen.dependencies.needs_wit = true;
                             format!("wit::vector<{inner}>&&")
@@ -1964,6 +4384,70 @@ impl CppInterfaceGenerator<'_> {
         }
     }
 
+    /// The view of a string passed to an import (see `--containers`)
+    fn borrowed_string(&mut self) -> String {
+        match self.gen.opts.containers {
+            Containers::Absl if !self.gen.opts.host_side() => {
+                self.gen.dependencies.needs_absl_string_view = true;
+                "::absl::string_view".into()
+            }
+            _ => {
+                self.gen.dependencies.needs_string_view = true;
+                "std::string_view".into()
+            }
+        }
+    }
+
+    /// The view of a list passed to an import (see `--containers`)
+    fn borrowed_list(&mut self, element: &str) -> String {
+        match self.gen.opts.containers {
+            Containers::Absl if !self.gen.opts.host_side() => {
+                self.gen.dependencies.needs_absl_span = true;
+                format!("::absl::Span<{element} const>")
+            }
+            _ => {
+                self.gen.dependencies.needs_wit = true;
+                format!("wit::span<{element} const>")
+            }
+        }
+    }
+
+    fn is_borrow(&self, ty: &Type) -> bool {
+        match dealias(self.resolve, ty) {
+            Type::Id(id) => matches!(
+                self.resolve.types[id].kind,
+                TypeDefKind::Handle(Handle::Borrow(_))
+            ),
+            _ => false,
+        }
+    }
+
+    /// Like type_name, but maps top level integers according to `--scalar-types`
+    fn scalar_type_name(
+        &mut self,
+        ty: &Type,
+        from_namespace: &Vec<String>,
+        flavor: Flavor,
+    ) -> String {
+        if self.gen.opts.scalar_types == ScalarTypes::Fast {
+            let fast = match dealias(self.resolve, ty) {
+                Type::U8 => Some("uint_fast8_t"),
+                Type::S8 => Some("int_fast8_t"),
+                Type::U16 => Some("uint_fast16_t"),
+                Type::S16 => Some("int_fast16_t"),
+                Type::U32 => Some("uint_fast32_t"),
+                Type::S32 => Some("int_fast32_t"),
+                Type::U64 => Some("uint_fast64_t"),
+                Type::S64 => Some("int_fast64_t"),
+                _ => None,
+            };
+            if let Some(fast) = fast {
+                return fast.into();
+            }
+        }
+        self.type_name(ty, from_namespace, flavor)
+    }
+
     fn declare_import2(
         module_name: &str,
         name: &str,
@@ -2005,6 +4489,367 @@ impl CppInterfaceGenerator<'_> {
         name
     }
 
+    /// Specialize `wit::layout` for a named type (`--layouts`)
+    fn type_layout(&mut self, id: TypeId) {
+        if !self.gen.opts.layouts {
+            return;
+        }
+        let ty = Type::Id(id);
+        let mut flat = Vec::new();
+        self.resolve.push_flat(&ty, &mut flat);
+        let pointer = if self.gen.opts.wasm64 { "i64" } else { "i32" };
+        let flat = flat
+            .iter()
+            .map(|wasm_type| {
+                let name = match wasm_type {
+                    WasmType::I32 => "i32",
+                    WasmType::I64 | WasmType::PointerOrI64 => "i64",
+                    WasmType::F32 => "f32",
+                    WasmType::F64 => "f64",
+                    WasmType::Pointer | WasmType::Length => pointer,
+                };
+                format!("flat_type::{name}")
+            })
+            .collect::<Vec<_>>();
+        let wit_namespace = vec![String::from("wit")];
+        let typename = self.type_name(&ty, &wit_namespace, Flavor::InStruct);
+        let size = self.sizes.size(&ty);
+        let align = self.sizes.align(&ty);
+        self.gen.dependencies.needs_wit = true;
+        self.gen.h_src.change_namespace(&wit_namespace);
+        uwriteln!(
+            self.gen.h_src.src,
+            "template <> struct layout<{typename}> {{"
+        );
+        uwriteln!(
+            self.gen.h_src.src,
+            "static constexpr size_t size = {size};
+            static constexpr size_t align = {align};"
+        );
+        uwriteln!(
+            self.gen.h_src.src,
+            "static constexpr std::array<flat_type, {}> flat = {{{}}};",
+            flat.len(),
+            flat.join(", ")
+        );
+        uwriteln!(self.gen.h_src.src, "}};");
+    }
+
+    // a record payload with several fields gets `fields()` returning a tuple
+    // of references, e.g. `auto [x, y] = c.fields();`
+    fn payload_fields(&mut self, ty: &Type) {
+        let Type::Id(id) = dealias(self.resolve, ty) else {
+            return;
+        };
+        let TypeDefKind::Record(record) = &self.resolve.types[id].kind else {
+            return;
+        };
+        if record.fields.len() < 2 {
+            return;
+        }
+        let fields = record
+            .fields
+            .iter()
+            .map(|field| {
+                let value = self.gen.opts.member("value");
+                format!("{value}.{}", field.name.to_snake_case())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        uwrite!(
+            self.gen.h_src.src,
+            "auto fields() {{ return std::tie({fields}); }} \
+             auto fields() const {{ return std::tie({fields}); }} "
+        );
+        self.gen.dependencies.needs_tuple = true;
+    }
+
+    /// The alignment the generated C++ type gets from its members, records
+    /// with a larger canonical ABI alignment are declared `alignas`
+    fn cpp_align(&self, ty: &Type) -> usize {
+        match ty {
+            // the host side stores guest addresses as `uint32_t`
+            Type::String if self.gen.opts.host && !self.gen.opts.short_cut => 4,
+            Type::Id(id) => match &self.resolve.types[*id].kind {
+                TypeDefKind::Record(r) => r
+                    .fields
+                    .iter()
+                    .map(|field| self.cpp_align(&field.ty))
+                    .fold(self.sizes.align(ty), usize::max),
+                TypeDefKind::Tuple(t) => t
+                    .types
+                    .iter()
+                    .map(|ty| self.cpp_align(ty))
+                    .fold(1, usize::max),
+                TypeDefKind::Flags(f) => match f.repr() {
+                    FlagsRepr::U32(n) if n > 1 => 8,
+                    _ => self.sizes.align(ty),
+                },
+                TypeDefKind::Variant(v) => v
+                    .cases
+                    .iter()
+                    .filter_map(|case| case.ty.as_ref())
+                    .map(|ty| self.cpp_align(ty))
+                    .fold(
+                        match v.tag() {
+                            Int::U8 => 1,
+                            Int::U16 => 2,
+                            Int::U32 => 4,
+                            Int::U64 => 8,
+                        },
+                        usize::max,
+                    ),
+                TypeDefKind::Option(o) => self.cpp_align(o),
+                TypeDefKind::Result(r) => [r.ok.as_ref(), r.err.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .map(|ty| self.cpp_align(ty))
+                    .fold(1, usize::max),
+                TypeDefKind::List(_) => self.cpp_align(&Type::String),
+                TypeDefKind::Type(ty) => self.cpp_align(ty),
+                // enums, resources and handles are at least as aligned
+                _ => self.sizes.align(ty),
+            },
+            _ => self.sizes.align(ty),
+        }
+    }
+
+    /// Declare the records and variants which the methods of `resource` use
+    /// but which are defined after it, e.g. a record of child resources
+    /// returned by a method of the parent
+    fn forward_declare_method_types(&mut self, resource: TypeId, intf: InterfaceId) {
+        fn collect(resolve: &Resolve, ty: &Type, ids: &mut Vec<TypeId>) {
+            let Type::Id(id) = ty else {
+                return;
+            };
+            match &resolve.types[*id].kind {
+                TypeDefKind::Record(_) | TypeDefKind::Variant(_) if !ids.contains(id) => {
+                    ids.push(*id)
+                }
+                TypeDefKind::Option(ty) | TypeDefKind::List(ty) | TypeDefKind::Type(ty) => {
+                    collect(resolve, ty, ids)
+                }
+                TypeDefKind::Result(r) => {
+                    for ty in r.ok.iter().chain(r.err.iter()) {
+                        collect(resolve, ty, ids);
+                    }
+                }
+                TypeDefKind::Tuple(t) => {
+                    for ty in t.types.iter() {
+                        collect(resolve, ty, ids);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let mut ids = Vec::new();
+        for func in self.resolve.interfaces[intf].functions.values() {
+            match func.kind {
+                FunctionKind::Method(id)
+                | FunctionKind::Static(id)
+                | FunctionKind::Constructor(id)
+                    if id == resource =>
+                {
+                    for ty in func
+                        .params
+                        .iter()
+                        .map(|(_, ty)| ty)
+                        .chain(func.results.iter_types())
+                    {
+                        collect(self.resolve, ty, &mut ids);
+                    }
+                }
+                _ => (),
+            }
+        }
+        for id in ids {
+            let ty = &self.resolve.types[id];
+            let namespc = namespace(
+                self.resolve,
+                &ty.owner,
+                NOT_IN_EXPORTED_NAMESPACE,
+                &self.gen.opts,
+            );
+            let name = ty.name.clone().unwrap_or_default();
+            if !self.gen.defined_types.contains(&(namespc.clone(), name)) {
+                let pascal = self.type_ident(id);
+                self.gen.h_src.change_namespace(&namespc);
+                uwriteln!(self.gen.h_src.src, "struct {pascal};");
+            }
+        }
+    }
+
+    /// The `dump` member of a record (see `--debug-dump`)
+    fn record_dump(&mut self, pascal: &str, record: &Record) {
+        self.gen.dependencies.needs_dump = true;
+        uwriteln!(
+            self.gen.h_src.src,
+            "void dump(std::ostream &os, int indent = 0) const {{"
+        );
+        uwriteln!(self.gen.h_src.src, "os << \"{pascal} {{\\n\";");
+        for field in record.fields.iter() {
+            let fname = field.name.to_snake_case();
+            let member = self.gen.opts.member(&fname);
+            uwriteln!(
+                self.gen.h_src.src,
+                "wit::dump_field(os, indent + 1, \"{fname}\", {member});"
+            );
+        }
+        uwriteln!(self.gen.h_src.src, "wit::dump_indent(os, indent);");
+        uwriteln!(self.gen.h_src.src, "os << \"}}\";");
+        uwriteln!(self.gen.h_src.src, "}}");
+    }
+
+    /// The `dump` member of a variant, the case and its payload
+    fn variant_dump(&mut self, pascal: &str, variant: &Variant) {
+        self.gen.dependencies.needs_dump = true;
+        let variants = self.gen.opts.member("variants");
+        uwriteln!(
+            self.gen.h_src.src,
+            "void dump(std::ostream &os, int indent = 0) const {{"
+        );
+        uwriteln!(self.gen.h_src.src, "switch ({variants}.index()) {{");
+        for (i, case) in variant.cases.iter().enumerate() {
+            let case_pascal = case.name.to_pascal_case();
+            uwrite!(
+                self.gen.h_src.src,
+                "case {i}: os << \"{pascal}::{case_pascal}"
+            );
+            if case.ty.is_some() {
+                uwrite!(
+                    self.gen.h_src.src,
+                    "(\"; wit::dump(os, std::get<{i}>({variants}).value, indent); os << \")"
+                );
+            }
+            uwriteln!(self.gen.h_src.src, "\"; break;");
+        }
+        uwriteln!(self.gen.h_src.src, "}}");
+        uwriteln!(self.gen.h_src.src, "}}");
+    }
+
+    /// Records and variants pass through `std::vector` growth without copies,
+    /// this holds as long as the guest helper types move without throwing
+    fn nothrow_move_assert(&mut self, id: TypeId, pascal: &str) {
+        if self.gen.opts.host_side() || !self.has_generated_moves(&Type::Id(id)) {
+            return;
+        }
+        self.gen.dependencies.needs_type_traits = true;
+        uwriteln!(
+            self.gen.h_src.src,
+            "static_assert(std::is_nothrow_move_constructible<{pascal}>::value);"
+        );
+    }
+
+    /// Whether some result in the world has `id` as its error type
+    fn is_error_type(&self, id: TypeId) -> bool {
+        self.resolve.types.iter().any(|(_, ty)| match &ty.kind {
+            TypeDefKind::Result(r) => {
+                r.err.map(|err| dealias(self.resolve, &err)) == Some(Type::Id(id))
+            }
+            _ => false,
+        })
+    }
+
+    fn type_error_code(
+        &mut self,
+        id: TypeId,
+        name: &str,
+        enum_: &wit_bindgen_core::wit_parser::Enum,
+    ) {
+        let pascal = self.type_ident(id);
+        self.gen.dependencies.needs_system_error = true;
+        self.gen.dependencies.needs_string = true;
+        uwriteln!(
+            self.gen.h_src.src,
+            "inline std::error_category const& {pascal}_category() {{
+                static struct : std::error_category {{"
+        );
+        uwriteln!(
+            self.gen.h_src.src,
+            "char const* name() const noexcept override {{ return \"{name}\"; }}"
+        );
+        uwriteln!(
+            self.gen.h_src.src,
+            "std::string message(int value) const override {{
+                switch (value) {{"
+        );
+        for (i, case) in enum_.cases.iter().enumerate() {
+            uwriteln!(self.gen.h_src.src, "case {i}: return \"{}\";", case.name);
+        }
+        uwriteln!(
+            self.gen.h_src.src,
+            "default: return \"unknown\";
+                }}
+              }}
+            }} category;
+            return category;
+            }}"
+        );
+        uwriteln!(
+            self.gen.h_src.src,
+            "inline std::error_code make_error_code({pascal} value) {{"
+        );
+        uwriteln!(
+            self.gen.h_src.src,
+            "return std::error_code(static_cast<int>(value), {pascal}_category());
+            }}"
+        );
+        let std_namespace = vec![String::from("std")];
+        let typename = self.type_name(&Type::Id(id), &std_namespace, Flavor::InStruct);
+        self.gen.h_src.change_namespace(&std_namespace);
+        uwriteln!(
+            self.gen.h_src.src,
+            "template <> struct is_error_code_enum<::{}> : true_type {{}};",
+            typename.trim_start_matches("::")
+        );
+    }
+
+    /// Whether moving `ty` only involves types the generator controls, the
+    /// `std::expected` behind results is up to the user (e.g. tl::expected)
+    fn has_generated_moves(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Id(id) => match &self.resolve.types[*id].kind {
+                TypeDefKind::Record(r) => r.fields.iter().all(|f| self.has_generated_moves(&f.ty)),
+                TypeDefKind::Tuple(t) => t.types.iter().all(|t| self.has_generated_moves(t)),
+                TypeDefKind::Variant(v) => v
+                    .cases
+                    .iter()
+                    .filter_map(|c| c.ty.as_ref())
+                    .all(|t| self.has_generated_moves(t)),
+                TypeDefKind::Option(t) | TypeDefKind::List(t) | TypeDefKind::Type(t) => {
+                    self.has_generated_moves(t)
+                }
+                TypeDefKind::Flags(_)
+                | TypeDefKind::Enum(_)
+                | TypeDefKind::Handle(_)
+                | TypeDefKind::Resource => true,
+                _ => false,
+            },
+            _ => true,
+        }
+    }
+
+    /// Whether a defaulted `operator<=>` works for the C++ type of `ty`
+    fn is_three_way_comparable(&self, ty: &Type) -> bool {
+        match ty {
+            // only the guest side wit::string owns and compares its characters
+            Type::String => !self.gen.opts.host_side(),
+            Type::Id(id) => match &self.resolve.types[*id].kind {
+                TypeDefKind::Record(r) => {
+                    r.fields.iter().all(|f| self.is_three_way_comparable(&f.ty))
+                }
+                TypeDefKind::Tuple(t) => t.types.iter().all(|t| self.is_three_way_comparable(t)),
+                TypeDefKind::Option(t) => self.is_three_way_comparable(t),
+                TypeDefKind::Flags(_) | TypeDefKind::Enum(_) => true,
+                TypeDefKind::Variant(v) => payloadless_enum(v).is_some(),
+                TypeDefKind::Type(t) => self.is_three_way_comparable(t),
+                _ => false,
+            },
+            _ => true,
+        }
+    }
+
     fn docs(src: &mut Source, docs: &Docs) {
         if let Some(docs) = docs.contents.as_ref() {
             for line in docs.trim().lines() {
@@ -2038,15 +4883,65 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
         if self.gen.is_first_definition(&namespc, name) {
             self.gen.h_src.change_namespace(&namespc);
             Self::docs(&mut self.gen.h_src.src, docs);
-            let pascal = name.to_pascal_case();
-            uwriteln!(self.gen.h_src.src, "struct {pascal} {{");
+            let pascal = self.type_ident(id);
+            self.gen
+                .forward_declare(&namespc, format!("struct {pascal};"));
+            let align = self.sizes.align(&Type::Id(id));
+            let natural = record
+                .fields
+                .iter()
+                .map(|field| self.cpp_align(&field.ty))
+                .fold(1, usize::max);
+            if align > natural {
+                uwriteln!(self.gen.h_src.src, "struct alignas({align}) {pascal} {{");
+            } else {
+                uwriteln!(self.gen.h_src.src, "struct {pascal} {{");
+            }
             for field in record.fields.iter() {
                 Self::docs(&mut self.gen.h_src.src, &field.docs);
                 let typename = self.type_name(&field.ty, &namespc, Flavor::InStruct);
                 let fname = field.name.to_snake_case();
                 uwriteln!(self.gen.h_src.src, "{typename} {fname};");
             }
+            if self.gen.opts.chrono_conversions && is_datetime_shaped(self.resolve, record) {
+                self.gen.dependencies.needs_chrono = true;
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "std::chrono::nanoseconds to_chrono() const {{
+                        return std::chrono::seconds(seconds) + std::chrono::nanoseconds(nanoseconds);
+                    }}"
+                );
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "static {pascal} from_chrono(std::chrono::nanoseconds d) {{"
+                );
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "auto secs = std::chrono::duration_cast<std::chrono::seconds>(d);"
+                );
+                uwriteln!(self.gen.h_src.src, "{pascal} result{{}};");
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "result.seconds = uint64_t(secs.count());
+                    result.nanoseconds = uint32_t((d - secs).count());
+                    return result;
+                    }}"
+                );
+            }
+            if self.gen.opts.derive_ord && self.is_three_way_comparable(&Type::Id(id)) {
+                self.gen.dependencies.needs_compare = true;
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "auto operator<=>({pascal} const&) const = default;"
+                );
+            }
+            if self.gen.opts.debug_dump {
+                self.record_dump(&pascal, record);
+            }
             uwriteln!(self.gen.h_src.src, "}};");
+            self.nothrow_move_assert(id, &pascal);
+            self.compat_typedef(id, name);
+            self.type_layout(id);
         }
     }
 
@@ -2081,6 +4976,7 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
                     */"#
                 );
             }
+            self.forward_declare_method_types(id, intf);
             self.gen.h_src.change_namespace(&namespc);
 
             if !definition {
@@ -2103,6 +4999,8 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
                 (true, true) => format!("wit::{RESOURCE_IMPORT_BASE_CLASS_NAME}<{pascal}>"),
             };
             let derive = format!(" : public {base_type}");
+            self.gen
+                .forward_declare(&namespc, format!("class {pascal};"));
             uwriteln!(self.gen.h_src.src, "class {pascal}{derive} {{\n");
             uwriteln!(self.gen.h_src.src, "public:\n");
             let variant = if guest_import {
@@ -2163,16 +5061,30 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
                         self.generate_function(&func2, &TypeOwner::Interface(intf), variant);
                     }
                 }
-            }
-
-            if !definition {
-                // consuming constructor from handle (bindings)
-                uwriteln!(self.gen.h_src.src, "{pascal}({base_type} &&);",);
-                uwriteln!(self.gen.h_src.src, "{pascal}({pascal}&&) = default;");
-                uwriteln!(
-                    self.gen.h_src.src,
-                    "{pascal}& operator=({pascal}&&) = default;"
-                );
+            }
+
+            if !definition {
+                // consuming constructor from handle (bindings)
+                uwriteln!(self.gen.h_src.src, "{pascal}({base_type} &&);",);
+                uwriteln!(self.gen.h_src.src, "{pascal}({pascal}&&) = default;");
+                if !self.gen.opts.host_side() {
+                    // the previous handle is dropped, so records holding
+                    // resources can be assigned to
+                    uwriteln!(
+                        self.gen.h_src.src,
+                        "{pascal}& operator=({pascal}&& b) noexcept {{ if (this != &b) {{ reset(); {base_type}::operator=(std::move(b)); }} return *this; }}"
+                    );
+                    // dropping a moved from object releases the handle once
+                    uwriteln!(
+                        self.gen.h_src.src,
+                        "void reset() {{ {pascal} dropped(std::move(*this)); }}"
+                    );
+                } else {
+                    uwriteln!(
+                        self.gen.h_src.src,
+                        "{pascal}& operator=({pascal}&&) = default;"
+                    );
+                }
                 self.gen.c_src.qualify(&namespc);
                 uwriteln!(
                     self.gen.c_src.src,
@@ -2215,6 +5127,19 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
                 };
                 self.generate_function(&func2, &TypeOwner::Interface(intf), variant);
             }
+            if !self.gen.opts.host_side() {
+                // identity, not value equality: a borrow compares equal to the
+                // own it was lent from, owned pointers and reference wrappers
+                // find these by argument dependent lookup
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "friend bool operator==({pascal} const& a, {pascal} const& b) {{ return a.get_handle() == b.get_handle(); }}"
+                );
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "friend bool operator!=({pascal} const& a, {pascal} const& b) {{ return !(a == b); }}"
+                );
+            }
             uwriteln!(self.gen.h_src.src, "}};\n");
             self.gen.finish_file(&user_filename, store);
             // if definition {
@@ -2249,14 +5174,17 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
         if self.gen.is_first_definition(&namespc, name) {
             self.gen.h_src.change_namespace(&namespc);
             Self::docs(&mut self.gen.h_src.src, docs);
-            let pascal = name.to_pascal_case();
+            let pascal = self.type_ident(id);
             let int_repr = wit_bindgen_c::int_repr(wit_bindgen_c::flags_repr(flags));
+            self.gen
+                .forward_declare(&namespc, format!("enum class {pascal} : {int_repr};"));
             uwriteln!(self.gen.h_src.src, "enum class {pascal} : {int_repr} {{");
             uwriteln!(self.gen.h_src.src, "k_None = 0,");
             for (n, field) in flags.flags.iter().enumerate() {
                 Self::docs(&mut self.gen.h_src.src, &field.docs);
                 let fname = field.name.to_pascal_case();
-                uwriteln!(self.gen.h_src.src, "k{fname} = (1ULL<<{n}),");
+                let comma = self.gen.opts.separator(n + 1 == flags.flags.len());
+                uwriteln!(self.gen.h_src.src, "k{fname} = (1ULL<<{n}){comma}");
             }
             uwriteln!(self.gen.h_src.src, "}};");
             uwriteln!(
@@ -2264,6 +5192,15 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
                 r#"static inline {pascal} operator|({pascal} a, {pascal} b) {{ return {pascal}({int_repr}(a)|{int_repr}(b)); }}
         static inline {pascal} operator&({pascal} a, {pascal} b) {{ return {pascal}({int_repr}(a)&{int_repr}(b)); }}"#
             );
+            // iterate the set flags via `for (auto f : flags)`
+            self.gen.dependencies.needs_wit = true;
+            uwriteln!(
+                self.gen.h_src.src,
+                r#"static inline wit::flags_iterator<{pascal}> begin({pascal} a) {{ return wit::flags_iterator<{pascal}>(a); }}
+        static inline wit::flags_iterator<{pascal}> end({pascal}) {{ return wit::flags_iterator<{pascal}>(); }}"#
+            );
+            self.compat_typedef(id, name);
+            self.type_layout(id);
         }
     }
 
@@ -2284,6 +5221,9 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
         variant: &wit_bindgen_core::wit_parser::Variant,
         docs: &wit_bindgen_core::wit_parser::Docs,
     ) {
+        if let Some(enum_) = payloadless_enum(variant) {
+            return self.type_enum(id, name, &enum_, docs);
+        }
         let ty = &self.resolve.types[id];
         let namespc = namespace(
             self.resolve,
@@ -2291,28 +5231,52 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
             NOT_IN_EXPORTED_NAMESPACE,
             &self.gen.opts,
         );
-        self.gen.h_src.change_namespace(&namespc);
-        Self::docs(&mut self.gen.h_src.src, docs);
-        let pascal = name.to_pascal_case();
-        uwriteln!(self.gen.h_src.src, "struct {pascal} {{");
-        let mut all_types = String::new();
-        for case in variant.cases.iter() {
-            Self::docs(&mut self.gen.h_src.src, &case.docs);
-            let case_pascal = case.name.to_pascal_case();
-            if !all_types.is_empty() {
-                all_types += ", ";
+        if self.gen.is_first_definition(&namespc, name) {
+            self.gen.h_src.change_namespace(&namespc);
+            Self::docs(&mut self.gen.h_src.src, docs);
+            let pascal = self.type_ident(id);
+            self.gen
+                .forward_declare(&namespc, format!("struct {pascal};"));
+            uwriteln!(self.gen.h_src.src, "struct {pascal} {{");
+            let mut all_types = String::new();
+            // switch the active case, destroying the previous payload
+            let mut setters = Vec::new();
+            for case in variant.cases.iter() {
+                Self::docs(&mut self.gen.h_src.src, &case.docs);
+                let case_pascal = case.name.to_pascal_case();
+                let case_snake = case.name.to_snake_case();
+                if !all_types.is_empty() {
+                    all_types += ", ";
+                }
+                all_types += &case_pascal;
+                uwrite!(self.gen.h_src.src, "struct {case_pascal} {{");
+                if let Some(ty) = case.ty.as_ref() {
+                    let typestr = self.type_name(ty, &namespc, Flavor::InStruct);
+                    uwrite!(self.gen.h_src.src, " {typestr} value; ");
+                    self.payload_fields(ty);
+                    setters.push(format!(
+                        "void set_{case_snake}({typestr} value) {{ variants.emplace<{case_pascal}>({case_pascal}{{std::move(value)}}); }}"
+                    ));
+                } else {
+                    setters.push(format!(
+                        "void set_{case_snake}() {{ variants.emplace<{case_pascal}>(); }}"
+                    ));
+                }
+                uwriteln!(self.gen.h_src.src, "}};");
+            }
+            uwriteln!(self.gen.h_src.src, "  std::variant<{all_types}> variants;");
+            for setter in setters.iter() {
+                uwriteln!(self.gen.h_src.src, "{setter}");
             }
-            all_types += &case_pascal;
-            uwrite!(self.gen.h_src.src, "struct {case_pascal} {{");
-            if let Some(ty) = case.ty.as_ref() {
-                let typestr = self.type_name(ty, &namespc, Flavor::InStruct);
-                uwrite!(self.gen.h_src.src, " {typestr} value; ")
+            if self.gen.opts.debug_dump {
+                self.variant_dump(&pascal, variant);
             }
             uwriteln!(self.gen.h_src.src, "}};");
+            self.nothrow_move_assert(id, &pascal);
+            self.gen.dependencies.needs_variant = true;
+            self.compat_typedef(id, name);
+            self.type_layout(id);
         }
-        uwriteln!(self.gen.h_src.src, "  std::variant<{all_types}> variants;");
-        uwriteln!(self.gen.h_src.src, "}};");
-        self.gen.dependencies.needs_variant = true;
     }
 
     fn type_option(
@@ -2351,19 +5315,76 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
         );
         if self.gen.is_first_definition(&namespc, name) {
             self.gen.h_src.change_namespace(&namespc);
-            let pascal = name.to_pascal_case();
+            let pascal = self.type_ident(id);
             Self::docs(&mut self.gen.h_src.src, docs);
             let int_t = wit_bindgen_c::int_repr(enum_.tag());
+            self.gen
+                .forward_declare(&namespc, format!("enum class {pascal} : {int_t};"));
             uwriteln!(self.gen.h_src.src, "enum class {pascal} : {int_t} {{");
             for (i, case) in enum_.cases.iter().enumerate() {
                 Self::docs(&mut self.gen.h_src.src, &case.docs);
+                let comma = self.gen.opts.separator(i + 1 == enum_.cases.len());
                 uwriteln!(
                     self.gen.h_src.src,
-                    " k{} = {i},",
+                    " k{} = {i}{comma}",
                     case.name.to_pascal_case(),
                 );
             }
             uwriteln!(self.gen.h_src.src, "}};\n");
+            // plain discriminant values for mapping to external protocols
+            for (i, case) in enum_.cases.iter().enumerate() {
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "static constexpr {int_t} {pascal}_{}_value = {i};",
+                    case.name.to_pascal_case(),
+                );
+            }
+            // nullopt for integers which are no case
+            self.gen.dependencies.needs_optional = true;
+            uwriteln!(
+                self.gen.h_src.src,
+                "static constexpr std::optional<{pascal}> {pascal}_from_discriminant(uint32_t d) {{
+                    if (d >= {}) {{ return std::nullopt; }}
+                    return {pascal}({int_t}(d));
+                }}
+                static constexpr uint32_t to_discriminant({pascal} e) {{ return uint32_t(e); }}",
+                enum_.cases.len()
+            );
+            if self.gen.opts.consteval_factories {
+                uwriteln!(self.gen.h_src.src, "#if __cpp_consteval >= 201811L");
+                for case in enum_.cases.iter() {
+                    uwriteln!(
+                        self.gen.h_src.src,
+                        "consteval {pascal} make_{pascal}_{}() {{ return {pascal}::k{}; }}",
+                        case.name.to_snake_case(),
+                        case.name.to_pascal_case(),
+                    );
+                }
+                uwriteln!(self.gen.h_src.src, "#endif");
+            }
+            if self.gen.opts.enum_reflection {
+                self.gen.dependencies.needs_array = true;
+                self.gen.dependencies.needs_string_view = true;
+                uwriteln!(
+                    self.gen.h_src.src,
+                    "static constexpr std::array<std::pair<{pascal}, std::string_view>, {}> {pascal}_entries = {{{{",
+                    enum_.cases.len()
+                );
+                for case in enum_.cases.iter() {
+                    uwriteln!(
+                        self.gen.h_src.src,
+                        "{{{pascal}::k{}, \"{}\"}},",
+                        case.name.to_pascal_case(),
+                        case.name
+                    );
+                }
+                uwriteln!(self.gen.h_src.src, "}}}};");
+            }
+            self.compat_typedef(id, name);
+            if self.gen.opts.error_code && self.is_error_type(id) {
+                self.type_error_code(id, name, enum_);
+            }
+            self.type_layout(id);
         }
     }
 
@@ -2382,10 +5403,11 @@ impl<'a> wit_bindgen_core::InterfaceGenerator<'a> for CppInterfaceGenerator<'a>
             &self.gen.opts,
         );
         self.gen.h_src.change_namespace(&namespc);
-        let pascal = name.to_pascal_case();
+        let pascal = self.type_ident(id);
         Self::docs(&mut self.gen.h_src.src, docs);
         let typename = self.type_name(alias_type, &namespc, Flavor::InStruct);
         uwriteln!(self.gen.h_src.src, "using {pascal} = {typename};");
+        self.compat_typedef(id, name);
     }
 
     fn type_list(
@@ -2423,14 +5445,28 @@ struct FunctionBindgen<'a, 'b> {
     // import_return_pointer_area_align: usize,
     namespace: Vec<String>,
     src: Source,
-    block_storage: Vec<wit_bindgen_core::Source>,
+    block_storage: Vec<(wit_bindgen_core::Source, Vec<String>)>,
     /// intermediate calculations for contained objects
     blocks: Vec<(String, Vec<String>)>,
     payloads: Vec<String>,
+    /// temporary allocations in the current block to free once the call returned
+    cleanup: Vec<String>,
+    needs_cleanup_list: bool,
     // caching for wasm
     wamr_signature: Option<wamr::WamrSig>,
     variant: AbiVariant,
     cabi_post: Option<CabiPostInformation>,
+    /// file scope return area of an export, released by its cabi_post
+    shared_ret_area: Option<String>,
+    /// guard giving back the shared return area, dismissed on return
+    ret_area_guard: Option<String>,
+    /// result of a host constructor, already a table handle
+    emplaced: Option<String>,
+    /// the `list<u8>` result is a view which isn't leaked
+    span_result: bool,
+    /// the `unexpected` template returning malformed values as
+    /// `wit::decode_error` instead of trapping (see `--try-call`)
+    decode_error: Option<&'static str>,
 }
 
 impl<'a, 'b> FunctionBindgen<'a, 'b> {
@@ -2446,9 +5482,16 @@ impl<'a, 'b> FunctionBindgen<'a, 'b> {
             block_storage: Default::default(),
             blocks: Default::default(),
             payloads: Default::default(),
+            cleanup: Default::default(),
+            needs_cleanup_list: false,
             wamr_signature: None,
             variant: AbiVariant::GuestImport,
             cabi_post: None,
+            shared_ret_area: None,
+            ret_area_guard: None,
+            emplaced: None,
+            span_result: false,
+            decode_error: None,
         }
     }
 
@@ -2458,6 +5501,31 @@ impl<'a, 'b> FunctionBindgen<'a, 'b> {
         ret
     }
 
+    // the pointer passed for an empty string or list (see `--empty-ptr`)
+    fn empty_ptr_value(&self, align: usize) -> String {
+        match self.gen.gen.opts.empty_ptr {
+            EmptyPtr::Null => "nullptr".into(),
+            EmptyPtr::Dangling => format!("({}){align}", self.gen.gen.opts.ptr_type()),
+        }
+    }
+
+    // replace the data pointer of an empty value
+    fn empty_ptr(&mut self, ptr: &str, len: &str, align: usize) {
+        let empty = self.empty_ptr_value(align);
+        uwriteln!(self.src, "if ({len} == 0) {ptr} = {empty};");
+    }
+
+    // borrowed elements refer to the resource of the interface this
+    // function belongs to, they are only references and drop nothing
+    fn list_element_type(&mut self, element: &Type) -> String {
+        let flavor = if self.gen.is_borrow(element) {
+            Flavor::Argument(self.variant)
+        } else {
+            Flavor::InStruct
+        };
+        self.gen.type_name(element, &self.namespace, flavor)
+    }
+
     fn tempname(&self, base: &str, idx: usize) -> String {
         format!("{base}{idx}")
     }
@@ -2466,10 +5534,6 @@ impl<'a, 'b> FunctionBindgen<'a, 'b> {
         self.src.push_str(s);
     }
 
-    fn typename_lift(&self, id: TypeId) -> String {
-        self.gen.type_path(id, true)
-    }
-
     fn let_results(&mut self, amt: usize, results: &mut Vec<String>) {
         if amt > 0 {
             let tmp = self.tmp();
@@ -2524,24 +5588,34 @@ impl<'a, 'b> FunctionBindgen<'a, 'b> {
     }
 
     fn has_resources(&self, id: &TypeId) -> bool {
-        match &self.gen.resolve.types[*id].kind {
-            TypeDefKind::Record(_) => todo!(),
-            TypeDefKind::Resource => true,
-            TypeDefKind::Handle(_) => true,
-            TypeDefKind::Flags(_) => false,
-            TypeDefKind::Tuple(_) => todo!(),
-            TypeDefKind::Variant(_) => todo!(),
-            TypeDefKind::Enum(_) => false,
-            TypeDefKind::Option(_) => todo!(),
-            TypeDefKind::Result(_) => todo!(),
-            TypeDefKind::List(_) => todo!(),
-            TypeDefKind::Future(_) => todo!(),
-            TypeDefKind::Stream(_) => todo!(),
-            TypeDefKind::Type(ty) => match ty {
-                Type::Id(id) => self.has_resources(id),
-                _ => false,
-            },
-            TypeDefKind::Unknown => todo!(),
+        type_has_resources(self.gen.resolve, &Type::Id(*id))
+    }
+
+    /// option and result discriminants are 0 or 1, anything else comes from
+    /// a malformed caller
+    fn trap_on_invalid_discriminant(&mut self, discriminant: &str) {
+        let trap = self.trap("discriminant");
+        uwriteln!(self.src, "if (uint32_t({discriminant}) > 1u) {{ {trap} }}");
+    }
+
+    /// The statement rejecting a malformed value
+    fn trap(&mut self, what: &str) -> String {
+        self.gen.gen.dependencies.needs_wit = true;
+        match self.decode_error {
+            Some(unexpected) => format!("return {unexpected}(wit::decode_error{{\"{what}\"}});"),
+            None => "WIT_TRAP();".into(),
+        }
+    }
+
+    /// floats crossing the boundary carry the canonical NaN, unless
+    /// disabled by `--nan-canon=off`
+    fn float_conversion(&mut self, cvt: &str, operand: &str) -> String {
+        match self.gen.gen.opts.nan_canon {
+            NanCanon::On => {
+                self.gen.gen.dependencies.needs_wit = true;
+                format!("(wit::canonicalize_nan({cvt}({operand})))")
+            }
+            NanCanon::Off => format!("({cvt}({operand}))"),
         }
     }
 }
@@ -2619,8 +5693,8 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
             abi::Instruction::F32Load { offset } => self.load("float", *offset, operands, results),
             abi::Instruction::F64Load { offset } => self.load("double", *offset, operands, results),
             abi::Instruction::I32Store { offset } => self.store("int32_t", *offset, operands),
-            abi::Instruction::I32Store8 { offset } => self.store("int32_t", *offset, operands),
-            abi::Instruction::I32Store16 { offset } => self.store("int32_t", *offset, operands),
+            abi::Instruction::I32Store8 { offset } => self.store("uint8_t", *offset, operands),
+            abi::Instruction::I32Store16 { offset } => self.store("uint16_t", *offset, operands),
             abi::Instruction::I64Store { offset } => self.store("int64_t", *offset, operands),
             abi::Instruction::F32Store { offset } => self.store("float", *offset, operands),
             abi::Instruction::F64Store { offset } => self.store("double", *offset, operands),
@@ -2633,8 +5707,16 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
             | abi::Instruction::I32FromU32
             | abi::Instruction::I32FromS32 => top_as("int32_t"),
             abi::Instruction::I64FromU64 | abi::Instruction::I64FromS64 => top_as("int64_t"),
-            abi::Instruction::F32FromCoreF32 => top_as("float"),
-            abi::Instruction::F64FromCoreF64 => top_as("double"),
+            abi::Instruction::F32FromCoreF32 | abi::Instruction::CoreF32FromF32 => {
+                let op = operands.pop().unwrap();
+                let result = self.float_conversion("float", &op);
+                results.push(result);
+            }
+            abi::Instruction::F64FromCoreF64 | abi::Instruction::CoreF64FromF64 => {
+                let op = operands.pop().unwrap();
+                let result = self.float_conversion("double", &op);
+                results.push(result);
+            }
             abi::Instruction::S8FromI32 => top_as("int8_t"),
             abi::Instruction::U8FromI32 => top_as("uint8_t"),
             abi::Instruction::S16FromI32 => top_as("int16_t"),
@@ -2644,32 +5726,41 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
             abi::Instruction::S64FromI64 => top_as("int64_t"),
             abi::Instruction::U64FromI64 => top_as("uint64_t"),
             abi::Instruction::CharFromI32 => top_as("uint32_t"),
-            abi::Instruction::CoreF32FromF32 => top_as("float"),
-            abi::Instruction::CoreF64FromF64 => top_as("double"),
             abi::Instruction::BoolFromI32 => top_as("bool"),
-            abi::Instruction::ListCanonLower { realloc, .. } => {
+            abi::Instruction::ListCanonLower { element, realloc } => {
                 let tmp = self.tmp();
                 let val = format!("vec{}", tmp);
                 let ptr = format!("ptr{}", tmp);
                 let len = format!("len{}", tmp);
                 // let result = format!("result{}", tmp);
                 self.push_str(&format!("auto const&{} = {};\n", val, operands[0]));
+                let leak = realloc.is_some()
+                    && !self.gen.gen.opts.host_side()
+                    && !self.gen.gen.opts.symmetric
+                    && !self.span_result;
                 if self.gen.gen.opts.host_side() {
                     self.push_str(&format!("auto {} = {}.data();\n", ptr, val));
                     self.push_str(&format!("auto {} = {}.size();\n", len, val));
                 } else {
+                    // inline elements can't be passed on
+                    let data = if leak && self.gen.gen.opts.sbo > 0 {
+                        format!("{}.release()", operands[0])
+                    } else {
+                        format!("{val}.data()")
+                    };
                     self.push_str(&format!(
-                        "auto {} = ({})({}.data());\n",
+                        "auto {} = ({})({data});\n",
                         ptr,
                         self.gen.gen.opts.ptr_type(),
-                        val
                     ));
                     self.push_str(&format!("auto {} = (size_t)({}.size());\n", len, val));
+                    let align = self.gen.sizes.align(element);
+                    self.empty_ptr(&ptr, &len, align);
                 }
                 if realloc.is_none() {
                     results.push(ptr);
                 } else {
-                    if !self.gen.gen.opts.host_side() && !self.gen.gen.opts.symmetric {
+                    if leak && self.gen.gen.opts.sbo == 0 {
                         uwriteln!(self.src, "{}.leak();\n", operands[0]);
                     }
                     results.push(ptr);
@@ -2694,6 +5785,7 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                         val
                     ));
                     self.push_str(&format!("auto {} = (size_t)({}.size());\n", len, val));
+                    self.empty_ptr(&ptr, &len, 1);
                 }
                 if realloc.is_none() {
                     results.push(ptr);
@@ -2708,10 +5800,86 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                 }
                 results.push(len);
             }
+            abi::Instruction::ListLower { element, realloc } if !self.gen.gen.opts.host_side() => {
+                let (body, _) = self.blocks.pop().unwrap();
+                let tmp = self.tmp();
+                let val = format!("vec{}", tmp);
+                let ptr = format!("ptr{}", tmp);
+                let len = format!("len{}", tmp);
+                let size = self.gen.sizes.size(element);
+                // the element size is fixed, so allocate the whole list at once
+                uwriteln!(self.src, "auto&& {val} = {};", operands[0]);
+                uwriteln!(self.src, "auto {len} = (size_t)({val}.size());");
+                // nested lists are lowered once per element, so only the
+                // outermost call site can own a buffer
+                let reuse = self.gen.gen.opts.reuse_lowering_buffer
+                    && realloc.is_none()
+                    && self.block_storage.is_empty();
+                if reuse {
+                    self.gen.gen.dependencies.needs_wit = true;
+                    uwriteln!(
+                        self.src,
+                        "static thread_local wit::scratch_buffer scratch{tmp};
+                        {} {ptr} = ({0})({len} > 0 ? scratch{tmp}.reserve({len} * {size}) : nullptr);",
+                        self.gen.gen.opts.ptr_type()
+                    );
+                } else {
+                    uwriteln!(
+                        self.src,
+                        "{} {ptr} = ({0})({len} > 0 ? malloc({len} * {size}) : nullptr);",
+                        self.gen.gen.opts.ptr_type()
+                    );
+                }
+                if realloc.is_none() && !reuse {
+                    // the callee only borrows the list, free it after the call
+                    // (even if lifting the results throws)
+                    if self.block_storage.is_empty() {
+                        self.gen.gen.dependencies.needs_wit = true;
+                        uwriteln!(
+                            self.src,
+                            "wit::scope_guard guard{tmp}([{ptr}] {{ free((void*) ({ptr})); }});"
+                        );
+                    } else {
+                        self.cleanup.push(ptr.clone());
+                    }
+                }
+                let bitwise = bitwise_lowerable(self.gen.resolve, element);
+                if bitwise {
+                    // same representation in C++ and linear memory
+                    self.gen.gen.dependencies.needs_cstring = true;
+                    uwriteln!(
+                        self.src,
+                        "if constexpr (wit::bitwise_lowerable<std::decay_t<decltype({val}[0])>, {size}>) {{"
+                    );
+                    uwriteln!(
+                        self.src,
+                        "if ({len} > 0) memcpy((void*){ptr}, {val}.data(), {len} * {size});"
+                    );
+                    uwriteln!(self.src, "}} else {{");
+                }
+                uwriteln!(self.src, "for (size_t i = 0; i < {len}; ++i) {{");
+                uwriteln!(self.src, "uint8_t* base = (uint8_t*){ptr} + i * {size};");
+                uwriteln!(self.src, "auto&& iter_elem = {val}[i];");
+                uwrite!(self.src, "{body}");
+                uwriteln!(self.src, "}}");
+                if bitwise {
+                    uwriteln!(self.src, "}}");
+                }
+                // the buffer of an empty list is null, which is freed safely
+                if self.gen.gen.opts.empty_ptr == EmptyPtr::Dangling {
+                    let align = self.gen.sizes.align(element);
+                    let empty = self.empty_ptr_value(align);
+                    results.push(format!("({len} > 0 ? {ptr} : {empty})"));
+                } else {
+                    results.push(ptr);
+                }
+                results.push(len);
+            }
             abi::Instruction::ListLower {
                 element: _,
                 realloc,
             } => {
+                let _ = self.blocks.pop().unwrap();
                 let tmp = self.tmp();
                 let val = format!("vec{}", tmp);
                 let ptr = format!("ptr{}", tmp);
@@ -2782,14 +5950,43 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                 };
                 results.push(result);
             }
+            abi::Instruction::ListLift { element, .. } if !self.gen.gen.opts.host_side() => {
+                let (body, body_results) = self.blocks.pop().unwrap();
+                let tmp = self.tmp();
+                let size = self.gen.sizes.size(element);
+                let vtype = self.list_element_type(element);
+                let len = format!("len{tmp}");
+                let base = format!("base{tmp}");
+                let result = format!("result{tmp}");
+                uwriteln!(self.src, "auto {base} = {};", operands[0]);
+                uwriteln!(self.src, "auto {len} = {};", operands[1]);
+                // returning a decode error destroys the partially filled vector
+                let (create, add) = if self.decode_error.is_some() {
+                    ("with_capacity", format!("{result}.push_back_unchecked("))
+                } else {
+                    ("allocate", format!("{result}.initialize(i, "))
+                };
+                uwriteln!(
+                    self.src,
+                    "auto {result} = wit::vector<{vtype}>::{create}({len});"
+                );
+                uwriteln!(self.src, "for (size_t i = 0; i < {len}; ++i) {{");
+                uwriteln!(self.src, "auto base = {base} + i * {size};");
+                uwrite!(self.src, "{body}");
+                uwriteln!(self.src, "{add}std::move({}));", body_results[0]);
+                uwriteln!(self.src, "}}");
+                // the elements have been converted, release the lowered list
+                uwriteln!(self.src, "if ({len} > 0) {{");
+                uwriteln!(self.src, "free((void*) ({base}));");
+                uwriteln!(self.src, "}}");
+                results.push(format!("std::move({result})"));
+            }
             abi::Instruction::ListLift { element, .. } => {
                 // let body = self.blocks.pop().unwrap();
                 let tmp = self.tmp();
                 let size = self.gen.sizes.size(element);
                 let _align = self.gen.sizes.align(element);
-                let vtype = self
-                    .gen
-                    .type_name(element, &self.namespace, Flavor::InStruct);
+                let vtype = self.list_element_type(element);
                 let len = format!("len{tmp}");
                 let base = format!("base{tmp}");
                 let result = format!("result{tmp}");
@@ -2819,7 +6016,7 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                 //     rt = self.gen.gen.runtime_path(),
                 // ));
             }
-            abi::Instruction::IterElem { .. } => results.push("IterElem".to_string()),
+            abi::Instruction::IterElem { .. } => results.push("iter_elem".to_string()),
             abi::Instruction::IterBasePointer => results.push("base".to_string()),
             abi::Instruction::RecordLower { record, .. } => {
                 let op = &operands[0];
@@ -2827,17 +6024,20 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     results.push(format!("({}).{}", op, to_c_ident(&f.name)));
                 }
             }
-            abi::Instruction::RecordLift { record, ty, .. } => {
+            abi::Instruction::RecordLift { ty, .. } => {
                 //                let t = self.gen.resolve().types[*ty];
                 let mut result =
                     self.gen
                         .type_name(&Type::Id(*ty), &self.namespace, Flavor::InStruct);
                 // self.typename_lift(*ty);
                 result.push_str("{");
-                for (_field, val) in record.fields.iter().zip(operands) {
-                    result.push_str("std::move(");
-                    result.push_str(&val);
-                    result.push_str("), ");
+                let fields = operands
+                    .iter()
+                    .map(|val| format!("std::move({val})"))
+                    .collect::<Vec<_>>();
+                result.push_str(&fields.join(", "));
+                if !fields.is_empty() && self.gen.gen.opts.trailing_commas {
+                    result.push_str(", ");
                 }
                 result.push_str("}");
                 results.push(result);
@@ -2848,7 +6048,9 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
             } => {
                 let op = &operands[0];
                 if self.gen.gen.opts.host_side() {
-                    if matches!(self.variant, AbiVariant::GuestImport) {
+                    if self.emplaced.as_ref() == Some(op) {
+                        results.push(op.clone());
+                    } else if matches!(self.variant, AbiVariant::GuestImport) {
                         results.push(format!("{op}.release()->get_handle()"));
                     } else {
                         let tmp = self.tmp();
@@ -2869,12 +6071,17 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                 ..
             } => {
                 let op = &operands[0];
+                let explicit_this = op == "(*this)" && self.gen.gen.opts.explicit_this;
                 if self.gen.gen.opts.host_side() {
-                    if op == "(*this)" {
+                    if explicit_this {
+                        results.push("this->get_rep()".into());
+                    } else if op == "(*this)" {
                         results.push(format!("{op}.get_rep()"));
                     } else {
                         results.push(format!("{op}.get().get_rep()"));
                     }
+                } else if explicit_this {
+                    results.push("this->get_handle()".into());
                 } else if op == "(*this)" {
                     // TODO is there a better way to decide?
                     results.push(format!("{op}.get_handle()"));
@@ -2909,6 +6116,15 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                         AbiVariant::GuestImport => {
                             results.push(format!("wit::{RESOURCE_IMPORT_BASE_CLASS_NAME}{{{op}}}"))
                         }
+                        AbiVariant::GuestExport if self.gen.callback_method(*ty).is_some() => {
+                            // owned by the closure, shared as std::function copies it
+                            let (resource, method) = self.gen.callback_method(*ty).unwrap();
+                            let class = self.gen.scoped_type_name(resource, &Vec::new(), false);
+                            let (_, name) = self.gen.func_namespace_name(&method, false, false);
+                            results.push(format!(
+                                "[callback = std::make_shared<::{class}>(wit::{RESOURCE_IMPORT_BASE_CLASS_NAME}{{{op}}})](auto&&... args) {{ return callback->{name}(std::forward<decltype(args)>(args)...); }}"
+                            ));
+                        }
                         AbiVariant::GuestExport => {
                             let tmp = self.tmp();
                             let var = self.tempname("obj", tmp);
@@ -3027,6 +6243,12 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     .payloads
                     .drain(self.payloads.len() - variant.cases.len()..)
                     .collect::<Vec<_>>();
+                let payloadless = payloadless_enum(variant).is_some();
+                // the blocks only store the discriminant when writing to memory
+                if payloadless && !result_types.is_empty() {
+                    results.push(format!("int32_t({})", operands[0]));
+                    return;
+                }
 
                 let mut variant_results = Vec::with_capacity(result_types.len());
                 for ty in result_types.iter() {
@@ -3039,7 +6261,11 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     variant_results.push(name);
                 }
 
-                let expr_to_match = format!("({}).tag", operands[0]);
+                let expr_to_match = if payloadless {
+                    operands[0].clone()
+                } else {
+                    format!("({}).variants.index()", operands[0])
+                };
 
                 uwriteln!(self.src, "switch ((int32_t) {}) {{", expr_to_match);
                 for (i, ((case, (block, block_results)), payload)) in
@@ -3048,16 +6274,13 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     uwriteln!(self.src, "case {}: {{", i);
                     if let Some(ty) = case.ty.as_ref() {
                         let ty = self.gen.type_name(ty, &self.namespace, Flavor::InStruct);
-                        uwrite!(
+                        uwriteln!(
                             self.src,
-                            "const {} *{} = &({}).val",
+                            "{} const& {} = std::get<{i}>(({}).variants).value;",
                             ty,
                             payload,
                             operands[0],
                         );
-                        self.src.push_str(".");
-                        self.src.push_str(&to_c_ident(&case.name));
-                        self.src.push_str(";\n");
                     }
                     self.src.push_str(&block);
 
@@ -3066,65 +6289,56 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     }
                     self.src.push_str("break;\n}\n");
                 }
+                // the discriminant always names one of the cases
+                self.gen.gen.dependencies.needs_wit = true;
+                self.src.push_str("default:\nWIT_UNREACHABLE();\n");
                 self.src.push_str("}\n");
             }
             abi::Instruction::VariantLift { variant, ty, .. } => {
-                let mut result = String::new();
-                result.push_str("{");
-
-                let named_enum = variant.cases.iter().all(|c| c.ty.is_none());
-                // let blocks = self
-                //     .blocks
-                //     .drain(self.blocks.len() - variant.cases.len()..)
-                //     .collect::<Vec<_>>();
+                let blocks = self
+                    .blocks
+                    .drain(self.blocks.len() - variant.cases.len()..)
+                    .collect::<Vec<_>>();
+                let name = self
+                    .gen
+                    .type_name(&Type::Id(*ty), &self.namespace, Flavor::InStruct);
                 let op0 = &operands[0];
+                if payloadless_enum(variant).is_some() {
+                    // the discriminant comes from the other side of the boundary
+                    let trap = self.trap("discriminant");
+                    let cases = variant.cases.len();
+                    uwriteln!(self.src, "if (uint32_t({op0}) >= {cases}u) {{ {trap} }}");
+                    results.push(format!("({name}){op0}"));
+                    return;
+                }
+                let tmp = self.tmp();
+                let resultname = self.tempname("variant", tmp);
 
-                if named_enum {
-                    // In unchecked mode when this type is a named enum then we know we
-                    // defined the type so we can transmute directly into it.
-                    // result.push_str("#[cfg(not(debug_assertions))]");
-                    // result.push_str("{");
-                    // result.push_str("::core::mem::transmute::<_, ");
-                    // result.push_str(&name.to_upper_camel_case());
-                    // result.push_str(">(");
-                    // result.push_str(op0);
-                    // result.push_str(" as ");
-                    // result.push_str(int_repr(variant.tag()));
-                    // result.push_str(")");
-                    // result.push_str("}");
-                }
-
-                // if named_enum {
-                //     result.push_str("#[cfg(debug_assertions)]");
-                // }
-                let blocks: Vec<String> = Vec::new();
-                result.push_str("{");
-                result.push_str(&format!("match {op0} {{\n"));
-                let name = self.typename_lift(*ty);
-                for (i, (case, block)) in variant.cases.iter().zip(blocks).enumerate() {
-                    let pat = i.to_string();
-                    let block = if case.ty.is_some() {
-                        format!("({block})")
+                uwriteln!(self.src, "{name} {resultname};");
+                uwriteln!(self.src, "switch ((int32_t) {op0}) {{");
+                for (i, (case, (block, block_results))) in
+                    variant.cases.iter().zip(blocks).enumerate()
+                {
+                    let case_name = case.name.to_pascal_case();
+                    let payload = if case.ty.is_some() {
+                        format!("std::move({})", block_results[0])
                     } else {
                         String::new()
                     };
-                    let case = case.name.to_upper_camel_case();
-                    // if i == variant.cases.len() - 1 {
-                    //     result.push_str("#[cfg(debug_assertions)]");
-                    //     result.push_str(&format!("{pat} => {name}::{case}{block},\n"));
-                    //     result.push_str("#[cfg(not(debug_assertions))]");
-                    //     result.push_str(&format!("_ => {name}::{case}{block},\n"));
-                    // } else {
-                    result.push_str(&format!("{pat} => {name}::{case}{block},\n"));
-                    // }
-                }
-                // result.push_str("#[cfg(debug_assertions)]");
-                // result.push_str("_ => panic!(\"invalid enum discriminant\"),\n");
-                result.push_str("}");
-                result.push_str("}");
-
-                result.push_str("}");
-                results.push(result);
+                    uwriteln!(
+                        self.src,
+                        "case {i}: {{
+                            {block}
+                            {resultname}.variants.emplace<{i}>({name}::{case_name}{{{payload}}});
+                            break;
+                        }}"
+                    );
+                }
+                // the discriminant comes from the other side of the boundary
+                let trap = self.trap("discriminant");
+                uwriteln!(self.src, "default:\n{trap}");
+                self.src.push_str("}\n");
+                results.push(resultname);
             }
             abi::Instruction::EnumLower { .. } => results.push(format!("int32_t({})", operands[0])),
             abi::Instruction::EnumLift { ty, .. } => {
@@ -3134,7 +6348,6 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                 results.push(format!("({typename}){}", &operands[0]));
             }
             abi::Instruction::OptionLower {
-                payload,
                 results: result_types,
                 ..
             } => {
@@ -3158,10 +6371,9 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                 }
 
                 let op0 = &operands[0];
-                let ty = self
-                    .gen
-                    .type_name(payload, &self.namespace, Flavor::InStruct);
-                let bind_some = format!("{ty} {some_payload} = (std::move({op0})).value();");
+                // the payload type depends on where the option came from (e.g. a
+                // string_view for imported arguments), dereferencing never throws
+                let bind_some = format!("auto&& {some_payload} = *std::move({op0});");
 
                 uwrite!(
                     self.src,
@@ -3187,6 +6399,7 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
 
                 let tmp = self.tmp();
                 let resultname = self.tempname("option", tmp);
+                self.trap_on_invalid_discriminant(op0);
                 uwriteln!(
                     self.src,
                     "{full_type} {resultname};
@@ -3223,23 +6436,13 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                 }
 
                 let op0 = &operands[0];
-                let ok_ty = self.gen.optional_type_name(
-                    result.ok.as_ref(),
-                    &self.namespace,
-                    Flavor::InStruct,
-                );
-                let err_ty = self.gen.optional_type_name(
-                    result.err.as_ref(),
-                    &self.namespace,
-                    Flavor::InStruct,
-                );
                 let bind_ok = if let Some(_ok) = result.ok.as_ref() {
-                    format!("{ok_ty} {ok_payload} = std::move({op0}).value();")
+                    format!("auto&& {ok_payload} = *std::move({op0});")
                 } else {
                     String::new()
                 };
                 let bind_err = if let Some(_err) = result.err.as_ref() {
-                    format!("{err_ty} {err_payload} = std::move({op0}).error();")
+                    format!("auto&& {err_payload} = std::move({op0}).error();")
                 } else {
                     String::new()
                 };
@@ -3275,23 +6478,48 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     &self.namespace,
                     Flavor::InStruct,
                 );
-                let err_type = self.gen.optional_type_name(
+                let operand = &operands[0];
+                let tmp = self.tmp();
+                let resultname = self.tempname("result", tmp);
+                self.trap_on_invalid_discriminant(operand);
+                if self.gen.is_optional_result(result) {
+                    // an empty optional represents the error
+                    uwriteln!(
+                        self.src,
+                        "std::optional<{ok_type}> {resultname};
+                        if ({operand}==0) {{
+                            {ok}
+                            {resultname}.emplace({ok_result});
+                        }}"
+                    );
+                    results.push(resultname);
+                    return;
+                }
+                let err_type = self.gen.error_type_name(
                     result.err.as_ref(),
                     &self.namespace,
                     Flavor::InStruct,
                 );
-                let full_type = format!("std::expected<{ok_type}, {err_type}>",);
-                let err_type = "std::unexpected";
-                let operand = &operands[0];
-
-                let tmp = self.tmp();
-                let resultname = self.tempname("result", tmp);
+                let full_type = format!(
+                    "{}<{ok_type}, {err_type}>",
+                    self.gen.gen.opts.result_template()
+                );
+                if result.err.is_none() {
+                    err_result = "wit::Void{}".into();
+                }
+                let err_type = self.gen.gen.opts.unexpected_template();
+                // a default constructed expected<void, E> already holds a value
+                let emplace_ok = if result.ok.is_some() {
+                    format!("{resultname}.emplace({ok_result});")
+                } else {
+                    String::new()
+                };
                 uwriteln!(
                     self.src,
                     "{full_type} {resultname};
                     if ({operand}==0) {{
                         {ok}
-                        {resultname}.emplace({ok_result});
+                        {emplace_ok}
                     }} else {{
                         {err}
                         {resultname}={err_type}{{{err_result}}};
@@ -3308,11 +6536,15 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     .gen
                     .wasm_import_module
                     .as_ref()
-                    .map(|e| String::from(*module_prefix) + e)
-                    .unwrap();
+                    .map(|e| String::from(*module_prefix) + e);
                 if self.gen.gen.opts.host {
+                    // world level exports are found by their plain name
+                    let export_name = match &module_name {
+                        Some(module_name) => format!("{module_name}#{name}"),
+                        None => name.to_string(),
+                    };
                     uwriteln!(self.src, "wasm_function_inst_t wasm_func = wasm_runtime_lookup_function(wasm_runtime_get_module_inst(exec_env), \n\
-                            \"{}#{}\", \"{}\");", module_name, name, self.wamr_signature.as_ref().unwrap().to_string());
+                            \"{}\", \"{}\");", export_name, self.wamr_signature.as_ref().unwrap().to_string());
                     if !sig.results.is_empty() {
                         uwriteln!(
                             self.src,
@@ -3385,9 +6617,12 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                         results.push("ret".to_string());
                     }
                 } else {
-                    let func =
-                        self.gen
-                            .declare_import(&module_name, name, &sig.params, &sig.results);
+                    let func = self.gen.declare_import(
+                        &module_name.unwrap(),
+                        name,
+                        &sig.params,
+                        &sig.results,
+                    );
 
                     // ... then call the function with all our operands
                     if sig.results.len() > 0 {
@@ -3403,6 +6638,13 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
             abi::Instruction::CallInterface { func } => {
                 // dbg!(func);
                 self.let_results(func.results.len(), results);
+                let wide = self.gen.wide_host_import(self.variant);
+                let wide_result = wide
+                    && matches!(&func.results, Results::Anon(ty) if dealias(self.gen.resolve, ty) == Type::String);
+                if wide_result {
+                    // copied into guest memory like a `wit::string` result
+                    self.push_str("wit::string::from_view(exec_env, wit::to_utf8(");
+                }
                 let (mut namespace, func_name_h) =
                     self.gen
                         .func_namespace_name(func, !self.gen.gen.opts.host_side(), true);
@@ -3415,6 +6657,15 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                         uwrite!(self.src, "({this}).get().");
                         // uwrite!(self.src, "(({objtype}*){this})->",);
                     }
+                } else if self.gen.uses_import_vtable(func, self.variant) {
+                    let member = self.gen.import_vtable_member(func);
+                    uwrite!(self.src, "import_vtable->{member}");
+                } else if self.gen.uses_import_context(func, self.variant) {
+                    let member = self.gen.import_vtable_member(func);
+                    uwrite!(
+                        self.src,
+                        "static_cast<ImportContext*>(wasm_runtime_get_user_data(exec_env))->{member}"
+                    );
                 } else {
                     if matches!(func.kind, FunctionKind::Constructor(_))
                         && self.gen.gen.opts.host_side()
@@ -3427,11 +6678,21 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                     self.push_str(&relative.src);
                     // self.gen.gen.c_src.qualify(&namespace);
                 }
-                self.src.push_str(&func_name_h);
+                if !self.gen.uses_import_vtable(func, self.variant)
+                    && !self.gen.uses_import_context(func, self.variant)
+                {
+                    self.src.push_str(&func_name_h);
+                }
                 if matches!(func.kind, FunctionKind::Constructor(_))
                     && self.gen.gen.opts.host_side()
                 {
-                    self.push_str("::New");
+                    if self.gen.gen.opts.host {
+                        // construct directly into the resource table
+                        self.push_str("::emplace");
+                        self.emplaced = results.first().cloned();
+                    } else {
+                        self.push_str("::New");
+                    }
                 }
                 self.push_str("(");
                 if self.gen.gen.opts.host {
@@ -3442,7 +6703,30 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
                         }
                     }
                 }
-                self.push_str(&operands.join(", "));
+                // options, results and tuples are taken by value, move the lifted temporaries
+                let skip = func.params.len() - operands.len();
+                let arguments = func.params[skip..]
+                    .iter()
+                    .zip(operands.iter())
+                    .map(|((_, ty), op)| match dealias(self.gen.resolve, ty) {
+                        Type::Id(id)
+                            if matches!(
+                                self.gen.resolve.types[id].kind,
+                                TypeDefKind::Option(_)
+                                    | TypeDefKind::Result(_)
+                                    | TypeDefKind::Tuple(_)
+                            ) =>
+                        {
+                            format!("std::move({op})")
+                        }
+                        Type::String if wide => format!("wit::to_wstring({op})"),
+                        _ => op.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                self.push_str(&arguments.join(", "));
+                if wide_result {
+                    self.push_str("))");
+                }
                 if false
                     && matches!(func.kind, FunctionKind::Constructor(_))
                     && !self.gen.gen.opts.is_only_handle(self.variant)
@@ -3457,9 +6741,14 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
             abi::Instruction::Return { amt, func } => {
                 // let guest_import = matches!(self.variant, AbiVariant::GuestImport);
                 match amt {
+                    // the `Try` wrapper returns success
+                    0 if self.decode_error.is_some() => self.src.push_str("return {};\n"),
                     0 => {}
                     _ => {
                         assert!(*amt == operands.len());
+                        if let Some(guard) = self.ret_area_guard.take() {
+                            uwriteln!(self.src, "{guard}.dismiss();");
+                        }
                         match &func.kind {
                             FunctionKind::Constructor(_)
                                 if self.gen.gen.opts.is_only_handle(self.variant) =>
@@ -3613,6 +6902,51 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
             8 => "uint64_t",
             _ => todo!(),
         };
+        if let Some(ret_area) = &self.shared_ret_area {
+            uwriteln!(
+                self.gen.gen.extern_c_decls,
+                "static {tp} {ret_area}[{elems}];
+                static bool {ret_area}_busy = false;"
+            );
+            let ptr_type = self.gen.gen.opts.ptr_type();
+            uwriteln!(
+                self.src,
+                "{ptr_type} ptr{tmp} = {ret_area}_busy ? ({ptr_type})(malloc(sizeof({ret_area}))) : ({ptr_type})(&{ret_area});
+                if (!ptr{tmp}) WIT_TRAP();
+                {ret_area}_busy = true;"
+            );
+            // given back if lowering the results throws, cabi_post releases it
+            // after a successful return
+            self.gen.gen.dependencies.needs_wit = true;
+            uwriteln!(
+                self.src,
+                "wit::scope_guard guard{tmp}([ptr{tmp}] {{
+                    if (ptr{tmp} == ({ptr_type})(&{ret_area})) {{
+                        {ret_area}_busy = false;
+                    }} else {{
+                        free(ptr{tmp});
+                    }}
+                }});"
+            );
+            self.ret_area_guard = Some(format!("guard{tmp}"));
+            return format!("ptr{}", tmp);
+        }
+        if self.gen.in_guest_import && size > MAX_STACK_RET_AREA {
+            // freed after the results are lifted, which copy out everything
+            self.gen.gen.dependencies.needs_wit = true;
+            let ptr_type = self.gen.gen.opts.ptr_type();
+            uwriteln!(
+                self.src,
+                "{ptr_type} ptr{tmp} = ({ptr_type})(malloc({size}));
+                if (!ptr{tmp}) WIT_TRAP();
+                wit::scope_guard guard{tmp}([ptr{tmp}] {{ free((void*) (ptr{tmp})); }});"
+            );
+            return format!("ptr{}", tmp);
+        }
+        // the return area of an export is read by the caller after it
+        // returned, without a cabi_post nothing could free a heap area.
+        // Being static it is sized for this function and can't overflow,
+        // whatever the size, and is valid until the next call.
         let static_var = if self.gen.in_guest_import {
             ""
         } else {
@@ -3630,13 +6964,22 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
 
     fn push_block(&mut self) {
         let prev = core::mem::take(&mut self.src);
-        self.block_storage.push(prev);
+        let prev_cleanup = core::mem::take(&mut self.cleanup);
+        self.block_storage.push((prev, prev_cleanup));
         //        uwriteln!(self.src, "// push_block()");
     }
 
     fn finish_block(&mut self, operands: &mut Vec<Self::Operand>) {
-        let to_restore = self.block_storage.pop().unwrap();
+        if !self.cleanup.is_empty() {
+            // allocations inside a nested block outlive its scope
+            self.needs_cleanup_list = true;
+            for ptr in core::mem::take(&mut self.cleanup) {
+                uwriteln!(self.src, "cleanup_list.push_back((void*) ({ptr}));");
+            }
+        }
+        let (to_restore, prev_cleanup) = self.block_storage.pop().unwrap();
         let src = core::mem::replace(&mut self.src, to_restore);
+        self.cleanup = prev_cleanup;
         self.blocks.push((src.into(), core::mem::take(operands)));
         //       uwriteln!(self.src, "// finish_block()");
     }
@@ -3660,6 +7003,109 @@ impl<'a, 'b> Bindgen for FunctionBindgen<'a, 'b> {
     }
 }
 
+// matches the WASI clocks datetime record
+fn is_datetime_shaped(resolve: &Resolve, record: &wit_bindgen_core::wit_parser::Record) -> bool {
+    let field = |name: &str| {
+        record
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| dealias(resolve, &f.ty))
+    };
+    record.fields.len() == 2
+        && field("seconds") == Some(Type::U64)
+        && field("nanoseconds") == Some(Type::U32)
+}
+
+// the parameter name from WIT, unless it is a C++ keyword
+fn param_name(name: &str) -> String {
+    to_c_ident(&name.to_snake_case())
+}
+
+// a variant without any payload is only its discriminant and generated as
+// an `enum class`
+fn payloadless_enum(variant: &Variant) -> Option<Enum> {
+    if variant.cases.iter().any(|case| case.ty.is_some()) {
+        return None;
+    }
+    let cases = variant
+        .cases
+        .iter()
+        .map(|case| EnumCase {
+            name: case.name.clone(),
+            docs: case.docs.clone(),
+        })
+        .collect();
+    Some(Enum { cases })
+}
+
+// the generated C++ type is laid out like the canonical ABI, tuples and
+// variants with payloads aren't, neither are flags wider than an integer
+fn bitwise_lowerable(resolve: &Resolve, ty: &Type) -> bool {
+    match ty {
+        Type::String => false,
+        Type::Id(id) => match &resolve.types[*id].kind {
+            TypeDefKind::Type(ty) => bitwise_lowerable(resolve, ty),
+            TypeDefKind::Enum(_) => true,
+            TypeDefKind::Variant(variant) => payloadless_enum(variant).is_some(),
+            TypeDefKind::Flags(flags) => !matches!(flags.repr(), FlagsRepr::U32(n) if n > 1),
+            TypeDefKind::Record(record) => record
+                .fields
+                .iter()
+                .all(|field| bitwise_lowerable(resolve, &field.ty)),
+            _ => false,
+        },
+        _ => true,
+    }
+}
+
+// the guest allocates lists it can't pass as is, the host
+// allocates returned strings and lists through cabi_realloc
+fn lowering_allocates(resolve: &Resolve, func: &Function) -> bool {
+    fn copies_list(resolve: &Resolve, ty: &Type) -> bool {
+        match ty {
+            Type::Id(id) => match &resolve.types[*id].kind {
+                TypeDefKind::List(ty) => !resolve.all_bits_valid(ty) || copies_list(resolve, ty),
+                TypeDefKind::Type(ty) | TypeDefKind::Option(ty) => copies_list(resolve, ty),
+                TypeDefKind::Record(r) => r.fields.iter().any(|f| copies_list(resolve, &f.ty)),
+                TypeDefKind::Tuple(t) => t.types.iter().any(|ty| copies_list(resolve, ty)),
+                TypeDefKind::Variant(v) => v
+                    .cases
+                    .iter()
+                    .filter_map(|c| c.ty.as_ref())
+                    .any(|ty| copies_list(resolve, ty)),
+                TypeDefKind::Result(r) => [&r.ok, &r.err]
+                    .iter()
+                    .filter_map(|ty| ty.as_ref())
+                    .any(|ty| copies_list(resolve, ty)),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+    abi::guest_export_needs_post_return(resolve, func)
+        || func.params.iter().any(|(_, ty)| copies_list(resolve, ty))
+}
+
+fn noexcept_spec(noexcept: Option<bool>) -> &'static str {
+    match noexcept {
+        Some(true) => " noexcept",
+        Some(false) => " noexcept(false)",
+        None => "",
+    }
+}
+
+// follow type aliases to the underlying type
+fn dealias(resolve: &Resolve, ty: &Type) -> Type {
+    match ty {
+        Type::Id(id) => match &resolve.types[*id].kind {
+            TypeDefKind::Type(t) => dealias(resolve, t),
+            _ => *ty,
+        },
+        _ => *ty,
+    }
+}
+
 /// This describes the common ABI function referenced or implemented, the C++ side might correspond to a different type
 enum SpecialMethod {
     None,
@@ -3,6 +3,8 @@
 #include <stdint.h>
 struct WASMExecEnv;
 typedef WASMExecEnv* wasm_exec_env_t;
+struct WASMModuleCommon;
+typedef WASMModuleCommon* wasm_module_t;
 struct WASMModuleInstanceCommon;
 typedef WASMModuleInstanceCommon* wasm_module_inst_t;
 typedef void* wasm_function_inst_t;
@@ -17,3 +19,9 @@ struct NativeSymbol {
 void wasm_runtime_register_natives(char const* module, NativeSymbol const*, unsigned);
 bool wasm_runtime_call_wasm_a(wasm_exec_env_t, wasm_function_inst_t, uint32_t, struct wasm_val_t*, uint32_t, struct wasm_val_t*);
 wasm_function_inst_t wasm_runtime_lookup_function(wasm_module_inst_t, const char*, const char*);
+wasm_module_inst_t wasm_runtime_instantiate(wasm_module_t, uint32_t, uint32_t, char*, uint32_t);
+void wasm_runtime_deinstantiate(wasm_module_inst_t);
+wasm_exec_env_t wasm_runtime_create_exec_env(wasm_module_inst_t, uint32_t);
+void wasm_runtime_destroy_exec_env(wasm_exec_env_t);
+void wasm_runtime_set_user_data(wasm_exec_env_t, void*);
+void* wasm_runtime_get_user_data(wasm_exec_env_t);
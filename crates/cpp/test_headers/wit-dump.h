@@ -0,0 +1 @@
+../helper-types/wit-dump.h
\ No newline at end of file
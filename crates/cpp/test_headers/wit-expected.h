@@ -0,0 +1 @@
+../helper-types/wit-expected.h
\ No newline at end of file
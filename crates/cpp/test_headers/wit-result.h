@@ -0,0 +1 @@
+../helper-types/wit-result.h
\ No newline at end of file
@@ -0,0 +1,597 @@
+//! Lowering and lifting of values through the canonical ABI
+
+use super::*;
+
+#[test]
+fn list_of_records_allocates_once() {
+    let wit = r#"
+        package test:lists;
+
+        interface i {
+            record named { name: string, x: u32 }
+            send: func(p: list<named>);
+        }
+
+        world records-in-lists {
+            import i;
+        }
+    "#;
+    let source = generate_file(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        "records_in_lists.cpp",
+    );
+    assert_eq!(count_code(&source, "malloc("), 1);
+    assert!(has_code(&source, "* 12)"));
+    assert_eq!(count_code(&source, "free("), 1);
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+}
+
+#[test]
+fn bool_lists_store_single_bytes() {
+    let wit = r#"
+        package test:bools;
+
+        interface i {
+            roundtrip: func(a: list<bool>) -> list<bool>;
+        }
+
+        world bools {
+            import i;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "bools.cpp");
+    assert!(has_code(&source, "*((uint8_t*)(base + 0))"));
+    assert!(!has_code(&source, "*((int32_t*)(base + 0))"));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using namespace test::bools::i;
+        static_assert(std::is_same_v<decltype(&Roundtrip),
+                                     wit::vector<bool> (*)(wit::span<bool const>)>);
+        "#,
+    );
+}
+
+#[test]
+fn nested_list_lowering() {
+    let wit = r#"
+        package test:lists;
+
+        interface i {
+            jagged: func(a: list<list<u32>>) -> list<list<u32>>;
+        }
+
+        world nested {
+            import i;
+            export i;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "nested.cpp");
+    // the import writes a (ptr, len) pair per inner list into one outer buffer
+    assert!(has_code(&source, "malloc(len1 * 8)"));
+    assert!(has_code(&source, "*((size_t*)(base + 4)) = len0;"));
+    assert!(has_code(&source, "*((uint8_t**)(base + 0)) = ptr0;"));
+    // the exported result keeps the inner lists alive until post-return frees them
+    assert!(has_code(&source, "iter_elem.leak();"));
+    assert!(has_code(&source, "free((void*) (ptr0));"));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&test::lists::i::Jagged),
+                                     wit::vector<wit::vector<uint32_t>> (*)(
+                                         wit::span<wit::span<uint32_t const> const>)>);
+        "#,
+    );
+}
+
+#[test]
+fn bulk_list_lowering() {
+    let wit = r#"
+        package test:bulk;
+
+        interface i {
+            record sample { value: f64, valid: bool }
+            floats: func(a: list<f64>);
+            samples: func(a: list<sample>);
+            pairs: func(a: list<tuple<f64, bool>>);
+        }
+
+        world bulk {
+            import i;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "bulk.cpp");
+    // canonical lists are passed without any copy
+    let floats = &source[source.find("::Floats(").unwrap()..source.find("::Samples(").unwrap()];
+    assert!(has_code(floats, "auto ptr0 = (uint8_t*)(vec0.data());"));
+    assert!(!has_code(floats, "memcpy") && !has_code(floats, "for ("));
+    // bool isn't canonical, but stored the same way
+    let samples = &source[source.find("::Samples(").unwrap()..source.find("::Pairs(").unwrap()];
+    assert!(has_code(
+        samples,
+        "if constexpr (wit::bitwise_lowerable<std::decay_t<decltype(vec0[0])>, 16>) {"
+    ));
+    assert!(has_code(
+        samples,
+        "memcpy((void*)ptr0, vec0.data(), len0 * 16);"
+    ));
+    assert!(has_code(samples, "} else {"));
+    // std::tuple has no fixed layout
+    let pairs = &source[source.find("::Pairs(").unwrap()..];
+    assert!(!has_code(pairs, "memcpy"));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using test::bulk::i::Sample;
+        static_assert(wit::bitwise_lowerable<Sample, 16>);
+        static_assert(!wit::bitwise_lowerable<std::tuple<double, bool>, 16>);
+        "#,
+    );
+}
+
+#[test]
+fn variant_lowering_marks_default_unreachable() {
+    let wit = r#"
+        package test:variants;
+
+        interface i {
+            variant v { a(u32), b }
+            f: func(x: v);
+        }
+
+        world unreachable-default {
+            import i;
+        }
+    "#;
+    let source = generate_file(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        "unreachable_default.cpp",
+    );
+    assert!(has_code(&source, "default: WIT_UNREACHABLE();"));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+}
+
+#[test]
+fn lifted_discriminants_are_checked() {
+    let wit = r#"
+        package test:discriminants;
+
+        world discriminants {
+            export take-option: func(a: option<u32>) -> u32;
+            export take-result: func(a: result<u32, u8>) -> u32;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "discriminants.cpp");
+    let option = source.find("take_option(").unwrap();
+    let result = source.find("take_result(").unwrap();
+    let check = "if (uint32_t(arg0) > 1u) { WIT_TRAP(); }";
+    assert!(has_code(&source[option..result], check));
+    assert!(has_code(&source[result..], check));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+}
+
+#[test]
+fn payloadless_variant_is_enum_class() {
+    let wit = r#"
+        package test:plain;
+
+        interface colors {
+            variant color { blue, red }
+            variant maybe { none, some(u32) }
+            paint: func(c: color) -> color;
+        }
+
+        world plain {
+            import colors;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "plain.cpp");
+    assert!(has_code(&source, "(int32_t(c));"));
+    // an out of range discriminant still traps
+    assert!(has_code(
+        &source,
+        "if (uint32_t(ret) >= 2u) { WIT_TRAP(); } return (Color)ret;"
+    ));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using namespace test::plain::colors;
+        static_assert(std::is_enum_v<Color>);
+        static_assert(std::is_same_v<std::underlying_type_t<Color>, uint8_t>);
+        static_assert(int(Color::kBlue) == 0 && int(Color::kRed) == 1);
+        // a single payload keeps the tagged union
+        static_assert(std::is_same_v<decltype(Maybe::variants),
+                                     std::variant<Maybe::None, Maybe::Some>>);
+        "#,
+    );
+}
+
+#[test]
+fn reentrant_export_return_area() {
+    let wit = include_str!("../native_reentrant/wit/reentrant.wit");
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "reentrant.cpp");
+    let ret_area = "ret_area_testX3AreentrantX2FapiX23greet";
+    assert!(has_code(
+        &source,
+        &format!("static bool {ret_area}_busy = false;")
+    ));
+    assert!(has_code(
+        &source,
+        &format!("{ret_area}_busy ? (uint8_t*)(malloc(sizeof({ret_area})))")
+    ));
+    // released by cabi_post, or while unwinding from lowering the results
+    assert!(has_code(&source, "wit::scope_guard release([arg0] {"));
+    assert!(has_code(
+        &source,
+        &format!("{ret_area}_busy = false; }} else {{ free(arg0);")
+    ));
+    assert!(has_code(&source, "guard1.dismiss(); return ptr1;"));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+}
+
+#[test]
+fn large_import_return_area_on_heap() {
+    let wit = r#"
+        package test:big;
+
+        interface test {
+            record small { a: u64, b: u64 }
+            record big {
+                a: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                b: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                c: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                d: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                e: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                f: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                g: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                h: tuple<u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64>,
+                i: u8,
+            }
+            get-small: func() -> small;
+            get-big: func() -> big;
+        }
+
+        world big {
+            import test;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "big.cpp");
+    let small = &source[source.find("::GetSmall()").unwrap()..];
+    assert!(has_code(small, "uint64_t ret_area[2];"));
+    let big = &source[source.find("::GetBig()").unwrap()..];
+    assert!(has_code(
+        big,
+        "uint8_t* ptr0 = (uint8_t*)(malloc(1032)); if (!ptr0) WIT_TRAP();"
+    ));
+    assert!(has_code(
+        big,
+        "wit::scope_guard guard0([ptr0] { free((void*) (ptr0)); });"
+    ));
+    assert!(!has_code(big, "ret_area["));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+
+    // exports keep a static area of the exact size
+    let wit = wit.replace("import test;", "export test;");
+    let exported = generate_file(wit_bindgen_cpp::Opts::default(), &wit, "big.cpp");
+    let big = &exported[exported.find("X23get_big()").unwrap()..];
+    assert!(has_code(big, "static uint64_t ret_area[129];"));
+    assert!(!has_code(big, "malloc"));
+    compile_generated(wit_bindgen_cpp::Opts::default(), &wit, verify);
+}
+
+#[test]
+fn wide_tuple_offsets() {
+    let wit = include_str!("../../../../tests/runtime/tuples_wide/world.wit");
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "tuples_wide.cpp");
+    // u8 at 0, u64 aligned to 8, f32 at 16 and the string (pointer, length) at 20
+    let import = &source[source.find("test::tuples_wide::test::Roundtrip(").unwrap()..];
+    assert!(has_code(import, "uint64_t ret_area[4];"));
+    assert!(has_code(import, "(*((uint8_t*) (ptr1 + 0)))"));
+    assert!(has_code(import, "*((int64_t*) (ptr1 + 8))"));
+    assert!(has_code(import, "*((float*) (ptr1 + 16))"));
+    assert!(has_code(import, "*((uint8_t**) (ptr1 + 20))"));
+    assert!(has_code(import, "auto len2 = *((size_t*) (ptr1 + 24));"));
+    let export = &source[source.find("X23roundtrip(").unwrap()..];
+    assert!(has_code(export, "*((uint8_t*)(ptr3 + 0)) = "));
+    assert!(has_code(export, "*((int64_t*)(ptr3 + 8)) = "));
+    assert!(has_code(export, "*((float*)(ptr3 + 16)) = "));
+    assert!(has_code(export, "*((size_t*)(ptr3 + 24)) = len4;"));
+    assert!(has_code(export, "*((uint8_t**)(ptr3 + 20)) = ptr4;"));
+    // the lifted tuple owns a string, the call moves it
+    assert!(has_code(export, "Roundtrip(std::move(tuple1));"));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+}
+
+#[test]
+fn small_integers_keep_their_width() {
+    let wit = include_str!("../../../../tests/runtime/small_ints/world.wit");
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "small_ints.cpp");
+    // only widened to the i32 of the core signature
+    assert!(has_code(&source, "(int32_t(a)), (int32_t(b))"));
+    assert!(has_code(&source, "(uint8_t(arg0)), (int8_t(arg1))"));
+    // stored through unsigned bytes, the conversion from i32 wraps
+    let export = &source[source.find("X23pair(").unwrap()..];
+    assert!(has_code(
+        export,
+        "*((uint8_t*)(ptr1 + 0)) = (int32_t(std::get<0>(result0)));"
+    ));
+    assert!(has_code(
+        export,
+        "*((uint8_t*)(ptr1 + 1)) = (int32_t(std::get<1>(result0)));"
+    ));
+    let record = &source[source.find("X23roundtrip(").unwrap()..];
+    assert!(has_code(
+        record,
+        "*((uint16_t*)(ptr1 + 4)) = (int32_t((result0).d));"
+    ));
+    let stores = source
+        .lines()
+        .filter(|line| line.trim_start().starts_with("*(("))
+        .collect::<Vec<_>>();
+    assert!(!stores.is_empty());
+    assert!(stores
+        .iter()
+        .all(|store| !has_code(store, "(int8_t*)") && !has_code(store, "(int16_t*)")));
+    // loads keep the signedness
+    assert!(has_code(
+        &source,
+        "(int8_t((int32_t) (*((int8_t*) (ptr0 + 1)))))"
+    ));
+    assert!(has_code(
+        &source,
+        "(uint16_t((int32_t) (*((uint16_t*) (ptr0 + 2)))))"
+    ));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using namespace test::small_ints::test;
+        static_assert(std::is_same_v<decltype(&Pair),
+                                     std::tuple<uint8_t, int8_t> (*)(uint8_t, int8_t)>);
+        static_assert(std::is_same_v<decltype(&Signed),
+                                     wit::vector<int8_t> (*)(wit::span<int8_t const>)>);
+        static_assert(std::is_same_v<decltype(Widths::a), uint8_t>);
+        static_assert(std::is_same_v<decltype(Widths::b), int8_t>);
+        static_assert(std::is_same_v<decltype(Widths::c), uint16_t>);
+        static_assert(std::is_same_v<decltype(Widths::d), int16_t>);
+        "#,
+    );
+}
+
+#[test]
+fn nan_canon_wraps_float_conversions() {
+    let wit = r#"
+        package test:nan-canon;
+
+        world nan-canon {
+            import bits-f32: func(a: f32) -> u32;
+            export half: func(a: f64) -> f64;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "nan_canon.cpp");
+    assert!(has_code(
+        &source,
+        "X24rootX00bits_f32((wit::canonicalize_nan(float(a))));"
+    ));
+    assert!(has_code(
+        &source,
+        "exports::nan_canon::Half((wit::canonicalize_nan(double(arg0))));"
+    ));
+    assert!(has_code(
+        &source,
+        "return (wit::canonicalize_nan(double(result0)));"
+    ));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.nan_canon = wit_bindgen_cpp::NanCanon::Off;
+    let source = generate_file(opts.clone(), wit, "nan_canon.cpp");
+    assert!(!has_code(&source, "canonicalize_nan"));
+    compile_generated(opts, wit, verify);
+}
+
+#[test]
+fn empty_ptr_for_empty_values() {
+    let wit = r#"
+        package test:empty-lists;
+
+        world empty-lists {
+            import host-sizes: func(a: list<u32>, b: list<string>, s: string) -> u32;
+            export empty-list: func() -> list<u32>;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "empty_lists.cpp");
+    assert!(has_code(&source, "if (len0 == 0) ptr0 = nullptr;"));
+    assert!(has_code(&source, "if (len3 == 0) ptr3 = nullptr;"));
+    // the buffer of an empty list of strings is null already
+    assert!(has_code(
+        &source,
+        "X24rootX00host_sizes(ptr0, len0, ptr2, len2, ptr3, len3);"
+    ));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.empty_ptr = wit_bindgen_cpp::EmptyPtr::Dangling;
+    let source = generate_file(opts.clone(), wit, "empty_lists.cpp");
+    // aligned for the elements
+    assert!(has_code(&source, "if (len0 == 0) ptr0 = (uint8_t*)4;"));
+    assert!(has_code(&source, "if (len1 == 0) ptr1 = (uint8_t*)1;"));
+    assert!(has_code(&source, "(len2 > 0 ? ptr2 : (uint8_t*)4), len2"));
+    // the guard only sees the real allocation
+    assert!(has_code(
+        &source,
+        "wit::scope_guard guard2([ptr2] { free((void*) (ptr2)); });"
+    ));
+    let result = &source[source.find("empty_list()").unwrap()..];
+    assert!(has_code(result, "if (len2 == 0) ptr2 = (uint8_t*)4;"));
+    compile_generated(opts, wit, verify);
+}
+
+#[test]
+fn reuse_lowering_buffer_for_import_lists() {
+    let wit = r#"
+        package test:lowering-buffer;
+
+        interface test {
+            record entry { name: string, value: u32 }
+            total: func(entries: list<entry>) -> u32;
+            nested: func(a: list<list<string>>);
+        }
+
+        world lowering-buffer {
+            import test;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.reuse_lowering_buffer = true;
+    let source = generate_file(opts.clone(), wit, "lowering_buffer.cpp");
+    let total = &source[source.find("::Total(").unwrap()..source.find("::Nested(").unwrap()];
+    assert!(has_code(
+        total,
+        "static thread_local wit::scratch_buffer scratch1;"
+    ));
+    assert!(has_code(
+        total,
+        "(len1 > 0 ? scratch1.reserve(len1 * 12) : nullptr);"
+    ));
+    assert!(!has_code(total, "malloc(") && !has_code(total, "free("));
+    // the inner lists are lowered per element and keep their allocation
+    let nested = &source[source.find("::Nested(").unwrap()..];
+    assert_eq!(count_code(nested, "wit::scratch_buffer"), 1);
+    assert_eq!(count_code(nested, "malloc("), 1);
+    compile_generated(opts, wit, verify);
+}
+
+#[test]
+fn span_byte_results_skip_copy_and_free() {
+    let wit = r#"
+        package test:span-results;
+
+        world span-results {
+            export bytes: func(n: u32) -> list<u8>;
+            export words: func() -> list<u32>;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.span_byte_results = true;
+    let source = generate_file(opts.clone(), wit, "span_results.cpp");
+    let bytes = &source[source.find("bytes(").unwrap()..source.find("words(").unwrap()];
+    assert!(!has_code(bytes, ".leak()"));
+    let post = &source[source.find("cabi_post_bytes(").unwrap()..];
+    let post = &post[..post.find("\n}").unwrap()];
+    assert!(has_code(post, "ret_area_bytes_busy = false;"));
+    assert!(!has_code(post, "free((void*) (ptr0))"));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace exports::span_results;
+        static_assert(std::is_same_v<decltype(&Bytes), wit::span<uint8_t const> (*)(uint32_t)>);
+        static_assert(std::is_same_v<decltype(&Words), wit::vector<uint32_t> (*)()>);
+        "#,
+    );
+}
+
+#[test]
+fn realloc_symbol_renames_cabi_realloc() {
+    let world = |name: &str| {
+        format!(
+            "package test:{name};
+
+            world {name} {{
+                export greet-{name}: func(name: string) -> string;
+            }}"
+        )
+    };
+    let (a, b) = (world("first"), world("second"));
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.realloc_symbol = Some("realloc_first".into());
+    let source = generate_file(opts.clone(), &a, "first.cpp");
+    // only the forwarder is exported under the canonical name
+    assert!(has_code(
+        &source,
+        "__attribute__((__weak__)) void *realloc_first(void *ptr, size_t old_size, size_t align, size_t new_size) {"
+    ));
+    assert_eq!(count_code(&source, "__export_name__(\"cabi_realloc\")"), 1);
+    assert!(has_code(
+        &source,
+        "void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) { \
+         return realloc_first(ptr, old_size, align, new_size); }"
+    ));
+    let (first, _) = write_generated(opts.clone(), &a, "");
+    opts.realloc_symbol = Some("realloc_second".into());
+    let (second, _) = write_generated(opts, &b, "");
+    // both sets of bindings in one translation unit keep a single forwarder
+    let src = first.join("both.cpp");
+    std::fs::write(&src, "#include \"first.cpp\"\n#include \"second.cpp\"\n").unwrap();
+    compile_guest(&first, &src, &["-I", second.to_str().unwrap()]);
+}
+
+#[test]
+fn length_type_sizes_vector_length() {
+    let wit = r#"
+        package test:lengths;
+
+        world lengths {
+            import sum: func(a: list<u32>) -> u32;
+            import reverse: func(a: list<u8>) -> list<u8>;
+        }
+    "#;
+    let check = |length_type: &str| {
+        format!(
+            "static_assert(std::is_same_v<wit::length_type, {length_type}>);
+            static_assert(std::is_same_v<decltype(std::declval<wit::vector<uint8_t>&>().size()), {length_type}>);
+            static_assert(std::is_same_v<decltype(std::declval<wit::span<uint8_t const>&>().size()), {length_type}>);"
+        )
+    };
+    check_generated(wit_bindgen_cpp::Opts::default(), wit, &check("size_t"));
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.length_type = wit_bindgen_cpp::LengthType::U64;
+    check_generated(opts, wit, &check("uint64_t"));
+}
+
+#[test]
+fn length_type_mismatch_fails_to_compile() {
+    let dir = test_helpers::test_directory("codegen", "cpp-inline", "length-mismatch");
+    let mut narrow = wit_bindgen_cpp::Opts::default();
+    narrow.length_type = wit_bindgen_cpp::LengthType::U32;
+    for (opts, world) in [
+        (wit_bindgen_cpp::Opts::default(), "native"),
+        (narrow, "narrow"),
+    ] {
+        let wit = format!(
+            "package test:{world};\n\nworld {world} {{\n  import echo: func(s: string) -> string;\n}}\n"
+        );
+        let header = generate_file(opts, &wit, &format!("{world}_cpp.h"));
+        std::fs::write(dir.join(format!("{world}_cpp.h")), header).unwrap();
+    }
+
+    // either order is rejected, the helper types follow the first header
+    let sdk_path = PathBuf::from(
+        env::var_os("WASI_SDK_PATH").expect("environment variable WASI_SDK_PATH should be set"),
+    );
+    for (first, second) in [("native", "narrow"), ("narrow", "native")] {
+        let src = dir.join(format!("{first}_{second}.cpp"));
+        std::fs::write(
+            &src,
+            format!("#include \"{first}_cpp.h\"\n#include \"{second}_cpp.h\"\n"),
+        )
+        .unwrap();
+        let output = Command::new(sdk_path.join("bin/clang++"))
+            .arg("--sysroot")
+            .arg(sdk_path.join("share/wasi-sysroot"))
+            .args(["-std=c++2b", "-fsyntax-only"])
+            .arg(&src)
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr)
+            .contains("bindings generated with a different --length-type"));
+    }
+}
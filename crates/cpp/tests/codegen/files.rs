@@ -0,0 +1,316 @@
+//! The generated files and what they contain besides the bindings
+
+use super::*;
+
+#[test]
+fn package_path_dir_layout() {
+    let wit = r#"
+        package test:nested;
+
+        interface i {
+            f: func(x: u32) -> u32;
+        }
+
+        world layout {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.split_interfaces = true;
+    opts.dir_layout = wit_bindgen_cpp::DirLayout::PackagePath;
+    let world = generate_file(opts.clone(), wit, "layout_cpp.h");
+    assert!(has_code(&world, "#include \"test/nested/i.h\""));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        #include "test/nested/i.h"
+        static_assert(std::is_same_v<decltype(&test::nested::i::F), uint32_t (*)(uint32_t)>);
+        "#,
+    );
+}
+
+#[test]
+fn split_interfaces_compile_imported_resources() {
+    let wit = r#"
+        package test:split;
+
+        interface things {
+            resource thing {
+                constructor(a: u32);
+                get: func() -> u32;
+            }
+        }
+
+        world split {
+            import things;
+            export run: func();
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.split_interfaces = true;
+    let world = generate_file(opts.clone(), wit, "split_cpp.h");
+    assert!(has_code(&world, "#include \"test-split-things-Thing.h\""));
+    compile_generated(opts.clone(), wit, verify);
+    opts.dir_layout = wit_bindgen_cpp::DirLayout::PackagePath;
+    compile_generated(opts, wit, verify);
+}
+
+#[test]
+fn fwd_header_declares_types() {
+    let wit = r#"
+        package test:fwd;
+
+        interface types {
+            record point { x: u32, y: u32 }
+            variant shape { circle(u32), square(point) }
+            enum color { red, green }
+            flags access { read, write }
+            resource file {
+                constructor();
+            }
+        }
+
+        interface handles {
+            resource handle;
+        }
+
+        world fwd {
+            import types;
+            export handles;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.emit_fwd_header = true;
+    let (dir, _) = write_generated(opts, wit, "");
+    // the declarations name the types, which are still incomplete
+    let src = write_check(
+        &dir,
+        "fwd_fwd.h",
+        r#"
+        namespace types = test::fwd::types;
+        static_assert(std::is_same_v<std::underlying_type_t<types::Color>, uint8_t>);
+        static_assert(std::is_same_v<std::underlying_type_t<types::Access>, uint8_t>);
+        void use(types::Point*, types::Shape*, types::File*, exports::test::fwd::handles::Handle*);
+        struct test::fwd::types::Point {};
+        struct test::fwd::types::Shape {};
+        class test::fwd::types::File {};
+        "#,
+    );
+    compile_guest(&dir, &src, &["-std=c++17"]);
+}
+
+#[test]
+fn fwd_header_compiles_standalone() {
+    let wit = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/codegen/resources-in-aggregates.wit");
+    test_helpers::run_world_codegen_test(
+        "cpp-fwd",
+        &wit,
+        |resolve, world, files| {
+            let mut opts = wit_bindgen_cpp::Opts::default();
+            opts.emit_fwd_header = true;
+            opts.build().generate(resolve, world, files).unwrap()
+        },
+        verify_fwd_header,
+    );
+}
+
+#[test]
+fn json_description_of_smoke() {
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.json = true;
+    let json = generate_file(
+        opts,
+        include_str!("../../../../tests/runtime/smoke/world.wit"),
+        "smoke.json",
+    );
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["world"], "smoke");
+    assert_eq!(
+        json["functions"],
+        serde_json::json!([
+            {
+                "name": "thunk",
+                "interface": "test:smoke/imports",
+                "direction": "import",
+                "params": [],
+                "results": [],
+            },
+            {
+                "name": "thunk",
+                "interface": null,
+                "direction": "export",
+                "params": [],
+                "results": [],
+            },
+        ])
+    );
+}
+
+#[test]
+fn nolint_markers_bracket_generated_files() {
+    let wit = r#"
+        package test:nolint;
+
+        world nolint {
+            import log: func(msg: string);
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.emit_nolint = true;
+    for file in ["nolint.cpp", "nolint_cpp.h"] {
+        let source = generate_file(opts.clone(), wit, file);
+        let lines = source.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("// Generated by"), "{file}");
+        assert_eq!(lines[1], "// NOLINTBEGIN", "{file}");
+        assert_eq!(lines.last(), Some(&"// NOLINTEND"), "{file}");
+        assert_eq!(source.matches("NOLINTBEGIN").count(), 1, "{file}");
+    }
+    let plain = generate_file(wit_bindgen_cpp::Opts::default(), wit, "nolint.cpp");
+    assert!(!plain.contains("NOLINT"));
+    compile_generated(opts, wit, verify);
+}
+
+#[test]
+fn wit_hash_comment_follows_wit() {
+    let hash = |wit: &str| {
+        let mut opts = wit_bindgen_cpp::Opts::default();
+        opts.emit_wit_hash = true;
+        let header = generate_file(opts.clone(), wit, "hashed_cpp.h");
+        let first_line = header.lines().next().unwrap();
+        let hash = first_line.strip_prefix("// wit-hash: ").unwrap();
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        let source = generate_file(opts, wit, "hashed.cpp");
+        assert_eq!(source.lines().next(), Some(first_line));
+        hash.to_string()
+    };
+    let wit = r#"
+        package test:hashed;
+
+        world hashed {
+            import add: func(a: u32, b: u32) -> u32;
+        }
+    "#;
+    let original = hash(wit);
+    assert_eq!(hash(wit), original);
+    let changed = hash(
+        r#"
+        package test:hashed;
+
+        world hashed {
+            import add: func(a: u32, b: u64) -> u32;
+        }
+    "#,
+    );
+    assert_ne!(changed, original);
+
+    let header = generate_file(wit_bindgen_cpp::Opts::default(), wit, "hashed_cpp.h");
+    assert!(!header.contains("wit-hash"));
+}
+
+#[test]
+fn abi_link_check_symbol_follows_wit() {
+    let symbol = |wit: &str| {
+        let mut opts = wit_bindgen_cpp::Opts::default();
+        opts.abi_link_check = true;
+        let header = generate_file(opts.clone(), wit, "linked_cpp.h");
+        let symbol = tokens(&header)
+            .into_iter()
+            .find(|token| token.starts_with("wit_abi_linked_"))
+            .unwrap()
+            .to_string();
+        // each file including the header references the symbol the bindings define
+        check_generated(
+            opts,
+            wit,
+            &format!(
+                "static_assert(std::is_same_v<decltype({symbol}), char const>);
+                char const* referenced() {{ return wit_abi_check_linked; }}"
+            ),
+        );
+        symbol
+    };
+    let original = symbol(
+        r#"
+        package test:linked;
+
+        world linked {
+            import add: func(a: u32, b: u32) -> u32;
+        }
+    "#,
+    );
+    let same = symbol(
+        r#"
+        package test:linked;
+
+        world linked {
+            import add: func(a: u32, b: u32) -> u32;
+        }
+    "#,
+    );
+    let changed = symbol(
+        r#"
+        package test:linked;
+
+        world linked {
+            import add: func(a: u64, b: u32) -> u32;
+        }
+    "#,
+    );
+    assert_eq!(original, same);
+    assert_ne!(original, changed);
+}
+
+#[test]
+fn export_name_table_by_index() {
+    let wit = r#"
+        package test:names;
+
+        interface pair {
+            first: func() -> u32;
+            second: func(a: u32);
+        }
+
+        world names {
+            import pair;
+            export pair;
+            export run: func();
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.export_name_table = true;
+    // the same names the exports are defined with
+    let source = generate_file(opts.clone(), wit, "names.cpp");
+    assert!(has_code(
+        &source,
+        "__export_name__(\"test:names/pair#first\")"
+    ));
+    assert!(has_code(
+        &source,
+        "__export_name__(\"test:names/pair#second\")"
+    ));
+    check_generated(
+        opts.clone(),
+        wit,
+        r#"
+        #include <iterator>
+        #include <string_view>
+        static_assert(std::size(names_export_names) == 3);
+        static_assert(std::string_view(names_export_names[0]) == "run");
+        static_assert(std::string_view(names_export_names[1]) == "test:names/pair#first");
+        static_assert(std::string_view(names_export_names[2]) == "test:names/pair#second");
+        "#,
+    );
+
+    let imports_only = r#"
+        package test:names;
+
+        world imports-only {
+            import f: func();
+        }
+    "#;
+    let header = generate_file(opts, imports_only, "imports_only_cpp.h");
+    assert!(!header.contains("export_names"));
+}
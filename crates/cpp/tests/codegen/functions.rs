@@ -0,0 +1,596 @@
+//! Signatures of the generated functions and the extra entry points
+//! generated for them
+
+use super::*;
+
+#[test]
+fn rename_single_function() {
+    let wit = r#"
+        package test:renames;
+
+        interface i {
+            errno-result: func() -> u32;
+            other-result: func() -> u32;
+        }
+
+        world rename-func {
+            import i;
+            export i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.rename_func.push((
+        "test.renames.i/errno-result".to_string(),
+        "check_errno".to_string(),
+    ));
+    let header = generate_file(opts.clone(), wit, "rename_func_cpp.h");
+    assert!(!header.contains("ErrnoResult"));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&test::renames::i::check_errno), uint32_t (*)()>);
+        static_assert(std::is_same_v<decltype(&test::renames::i::OtherResult), uint32_t (*)()>);
+        static_assert(std::is_same_v<decltype(&exports::test::renames::i::check_errno),
+                                     uint32_t (*)()>);
+        static_assert(std::is_same_v<decltype(&exports::test::renames::i::OtherResult),
+                                     uint32_t (*)()>);
+        "#,
+    );
+}
+
+#[test]
+fn parameter_names_from_wit() {
+    let wit = r#"
+        package test:params;
+
+        interface api {
+            resource thing {
+                constructor(new: u32);
+                set: func(default: u32, this: string);
+            }
+            move-to: func(target-point: u32, class: u32, delete: list<u8>) -> u32;
+        }
+
+        world params {
+            import api;
+        }
+    "#;
+    let header = generate_file(wit_bindgen_cpp::Opts::default(), wit, "params_cpp.h");
+    assert!(has_code(
+        &header,
+        "uint32_t MoveTo(uint32_t target_point, uint32_t class_, wit::span<uint8_t const> delete_);"
+    ));
+    // C++ keywords get a trailing underscore
+    assert!(has_code(&header, "Thing(uint32_t new_);"));
+    assert!(has_code(
+        &header,
+        "void Set(uint32_t default_, std::string_view this_) const;"
+    ));
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.host = true;
+    let header = generate_file(opts.clone(), wit, "params_cpp_host.h");
+    assert!(has_code(
+        &header,
+        "uint32_t target_point, uint32_t class_, wit::span<uint8_t const> delete_);"
+    ));
+    if env::var_os("CPP_HOST_TESTS").is_some() {
+        compile_generated(opts, wit, verify_host);
+    }
+}
+
+#[test]
+fn noexcept_only_without_allocation() {
+    let wit = r#"
+        package test:nothrow;
+
+        interface i {
+            is-ready: func() -> bool;
+            name: func() -> string;
+        }
+
+        world nothrow {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.noexcept = true;
+    let header = generate_file(opts.clone(), wit, "nothrow_cpp.h");
+    assert!(has_code(
+        &header,
+        "// allocates during lowering, which aborts when out of memory"
+    ));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::nothrow::i;
+        static_assert(noexcept(IsReady()));
+        static_assert(!noexcept(Name()));
+        "#,
+    );
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        static_assert(!noexcept(test::nothrow::i::IsReady()));
+        "#,
+    );
+}
+
+#[test]
+fn flavorful_compiles_without_exceptions() {
+    let wit = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/runtime/flavorful");
+    test_helpers::run_world_codegen_test(
+        "cpp-no-exceptions",
+        &wit,
+        |resolve, world, files| {
+            wit_bindgen_cpp::Opts::default()
+                .build()
+                .generate(resolve, world, files)
+                .unwrap()
+        },
+        verify_no_exceptions,
+    );
+}
+
+#[test]
+fn vector_overloads_for_list_params() {
+    let wit = r#"
+        package test:vectors;
+
+        world vectors {
+            import count-true: func(a: list<bool>) -> u32;
+            import total-length: func(a: list<string>, b: list<u32>) -> u32;
+            import sum: func(a: list<u32>) -> u32;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.vector_overloads = true;
+    let header = generate_file(opts.clone(), wit, "vectors_cpp.h");
+    // a std::vector<uint32_t> already converts to the span
+    assert!(!has_code(&header, "inline uint32_t Sum("));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        #include <string>
+        #include <vector>
+        using namespace vectors;
+        uint32_t call(std::vector<bool> const& flags, std::vector<std::string> const& names,
+                      std::vector<uint32_t> const& numbers) {
+            return CountTrue(flags) + TotalLength(names, numbers) + Sum(numbers);
+        }
+        "#,
+    );
+}
+
+#[test]
+fn map_heuristic_find_helpers() {
+    let wit = r#"
+        package test:maps;
+
+        interface scores {
+            record entry { key: u32, value: string }
+            best: func(a: list<tuple<string, u32>>) -> list<entry>;
+            plain: func(a: list<tuple<f32, u32>>);
+        }
+
+        world maps {
+            import scores;
+        }
+    "#;
+    let lookups = r#"
+        using namespace test::maps::scores;
+        static_assert(requires(wit::span<std::tuple<std::string_view, uint32_t> const> map) {
+            { find(map, "key") } -> std::same_as<uint32_t const*>;
+        });
+        static_assert(requires(wit::vector<std::tuple<wit::string, uint32_t>> const& map) {
+            { find(map, "key") } -> std::same_as<uint32_t const*>;
+        });
+        static_assert(requires(wit::vector<Entry> const& map) {
+            { find(map, 1u) } -> std::same_as<wit::string const*>;
+        });
+        // floating point keys are not compared for equality
+        template <class M, class K> concept findable = requires(M map, K key) { find(map, key); };
+        static_assert(findable<wit::vector<Entry> const&, uint32_t>);
+        static_assert(!findable<wit::span<std::tuple<float, uint32_t> const>, float>);
+    "#;
+    let header = generate_file(wit_bindgen_cpp::Opts::default(), wit, "maps_cpp.h");
+    assert!(!has_code(&header, "find("));
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.map_heuristic = true;
+    check_generated(opts, wit, lookups);
+}
+
+#[test]
+fn lifetimebound_on_view_accessors() {
+    let wit = r#"
+        package test:bound;
+
+        interface scores {
+            best: func(s: list<tuple<string, u32>>) -> string;
+        }
+
+        world bound {
+            import scores;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.map_heuristic = true;
+    let header = generate_file(opts.clone(), wit, "bound_cpp.h");
+    assert!(!header.contains("WIT_LIFETIMEBOUND"));
+    opts.lifetimebound = true;
+    let header = generate_file(opts.clone(), wit, "bound_cpp.h");
+    assert!(has_code(
+        &header,
+        "find(wit::vector<std::tuple<wit::string, uint32_t>> const& map WIT_LIFETIMEBOUND, std::string_view key)"
+    ));
+    // defined before the helper types see it
+    assert!(header.find("WIT_LIFETIMEBOUND").unwrap() < header.find("#include").unwrap());
+    // and the view accessors of the helper types
+    let guest = include_str!("../../helper-types/wit-guest.h");
+    assert!(has_code(
+        guest,
+        "char const *data() const WIT_LIFETIMEBOUND {"
+    ));
+    assert!(has_code(
+        guest,
+        "std::string_view get_view() const WIT_LIFETIMEBOUND {"
+    ));
+    assert!(has_code(
+        guest,
+        "size_t len = std::string_view::npos) const WIT_LIFETIMEBOUND {"
+    ));
+    assert!(has_code(
+        guest,
+        "wit::span<T> get_view() const WIT_LIFETIMEBOUND {"
+    ));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        uint32_t const* lookup(wit::vector<std::tuple<wit::string, uint32_t>> const& scores) {
+            return test::bound::scores::find(scores, "a");
+        }
+        "#,
+    );
+}
+
+#[test]
+fn absl_containers_in_import_signatures() {
+    let wit = r#"
+        package test:containers;
+
+        interface test {
+            send: func(bytes: list<u8>, names: list<string>, name: string) -> list<string>;
+        }
+
+        world containers {
+            import test;
+            export run: func(names: list<string>) -> string;
+        }
+    "#;
+    let header = generate_file(wit_bindgen_cpp::Opts::default(), wit, "containers_cpp.h");
+    assert!(!header.contains("absl"));
+
+    // the test headers have no abseil
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.containers = wit_bindgen_cpp::Containers::Absl;
+    let header = generate_file(opts, wit, "containers_cpp.h");
+    assert!(has_code(&header, "#include <absl/strings/string_view.h>"));
+    assert!(has_code(&header, "#include <absl/types/span.h>"));
+    assert!(has_code(
+        &header,
+        "wit::vector<wit::string> Send(::absl::Span<uint8_t const> bytes, \
+         ::absl::Span<::absl::string_view const> names, ::absl::string_view name);"
+    ));
+    // exports own their arguments
+    assert!(has_code(
+        &header,
+        "wit::string Run(wit::vector<wit::string>&& names);"
+    ));
+}
+
+#[test]
+fn try_call_returns_decode_errors() {
+    let wit = r#"
+        package test:try-call;
+
+        interface test {
+            variant shape { circle(u32), square(u32) }
+            maybe: func() -> option<u32>;
+            shapes: func() -> list<shape>;
+            count: func() -> u32;
+        }
+
+        world try-call {
+            import test;
+        }
+    "#;
+    let header = generate_file(wit_bindgen_cpp::Opts::default(), wit, "try_call_cpp.h");
+    assert!(!header.contains("Try"));
+
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.try_call = true;
+    let header = generate_file(opts.clone(), wit, "try_call_cpp.h");
+    // lifting a u32 can't fail
+    assert!(!header.contains("TryCount"));
+    let source = generate_file(opts.clone(), wit, "try_call.cpp");
+    let (trapping, trying) = source.split_at(source.find("::TryMaybe()").unwrap());
+    assert!(has_code(trapping, "WIT_TRAP();"));
+    assert!(has_code(
+        trying,
+        "> 1u) { return std::unexpected(wit::decode_error{\"discriminant\"}); }"
+    ));
+    // partially lifted lists are destroyed on an error
+    let shapes = &trying[trying.find("::TryShapes()").unwrap()..];
+    assert!(has_code(shapes, "wit::vector<Shape>::with_capacity("));
+    assert!(has_code(shapes, ".push_back_unchecked(std::move("));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::try_call::test;
+        static_assert(std::is_same_v<decltype(&TryMaybe),
+                                     std::expected<std::optional<uint32_t>, wit::decode_error> (*)()>);
+        static_assert(std::is_same_v<decltype(&TryShapes),
+                                     std::expected<wit::vector<Shape>, wit::decode_error> (*)()>);
+        "#,
+    );
+}
+
+#[test]
+fn flat_facade_forwards_interface_functions() {
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.flat_facade = true;
+    let smoke = include_str!("../../../../tests/runtime/smoke/world.wit");
+    let header = generate_file(opts.clone(), smoke, "smoke_cpp.h");
+    // the exported thunk of the world isn't part of any interface
+    let facade = &header[header.find("struct World {").unwrap()..];
+    assert_eq!(
+        count_code(&facade[..facade.find("};").unwrap()], "thunk"),
+        1
+    );
+    assert!(
+        !generate_file(wit_bindgen_cpp::Opts::default(), smoke, "smoke_cpp.h")
+            .contains("struct World")
+    );
+    check_generated(
+        opts.clone(),
+        smoke,
+        r#"
+        static_assert(std::is_same_v<decltype(World::thunk()), void>);
+        "#,
+    );
+
+    let wit = r#"
+        package test:facade;
+
+        interface left {
+            record point { x: u32, y: u32 }
+            area: func(p: point, scale: u32) -> u32;
+            explicit: func();
+        }
+
+        interface right {
+            area: func(name: string) -> u32;
+        }
+
+        interface shapes {
+            describe: func(p: string) -> string;
+        }
+
+        world facade {
+            import left;
+            import right;
+            export shapes;
+        }
+    "#;
+    // colliding names get the interface as prefix, whichever comes first
+    check_generated(
+        opts.clone(),
+        wit,
+        r#"
+        using test::facade::left::Point;
+        static_assert(std::is_same_v<decltype(World::left_area(Point{1, 2}, 3)), uint32_t>);
+        static_assert(std::is_same_v<decltype(World::right_area("name")), uint32_t>);
+        static_assert(std::is_same_v<decltype(World::explicit_()), void>);
+        static_assert(std::is_same_v<decltype(World::describe(std::declval<wit::string>())),
+                                     wit::string>);
+        "#,
+    );
+
+    // functions named like keywords
+    for wit in [
+        include_str!("../../../../tests/codegen/conventions.wit"),
+        include_str!("../../../../tests/codegen/keywords-in-interfaces-and-worlds.wit"),
+        include_str!("../../../../tests/runtime/small_ints/world.wit"),
+    ] {
+        compile_generated(opts.clone(), wit, verify);
+    }
+}
+
+#[test]
+fn single_func_functor_for_smoke() {
+    let wit = r#"
+        package test:smoke;
+
+        interface imports {
+            thunk: func();
+        }
+
+        interface logger {
+            log: func(message: string, level: u8) -> bool;
+        }
+
+        interface pair {
+            first: func();
+            second: func();
+        }
+
+        world smoke {
+            import imports;
+            import logger;
+            import pair;
+
+            export thunk: func();
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.single_func_functor = true;
+    let header = generate_file(opts.clone(), wit, "smoke_cpp.h");
+    // only interfaces with a single function
+    assert!(!header.contains("PairFunctor"));
+    assert!(
+        !generate_file(wit_bindgen_cpp::Opts::default(), wit, "smoke_cpp.h").contains("Functor")
+    );
+    check_generated(
+        opts,
+        wit,
+        r#"
+        #include <functional>
+        using namespace test::smoke;
+        std::function<void()> thunk = imports::ImportsFunctor{};
+        std::function<bool(std::string_view, uint8_t)> log = logger::LoggerFunctor{};
+        "#,
+    );
+
+    // inline interfaces share a namespace and pass move-only records
+    let wit = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/runtime/ownership");
+    test_helpers::run_world_codegen_test(
+        "cpp-functor",
+        &wit,
+        |resolve, world, files| {
+            let mut opts = wit_bindgen_cpp::Opts::default();
+            opts.single_func_functor = true;
+            opts.build().generate(resolve, world, files).unwrap()
+        },
+        verify,
+    );
+}
+
+#[test]
+fn preview1_compat_wraps_scalar_imports() {
+    let wit = r#"
+        package test:p1;
+
+        interface fs {
+            enum errno { badf, inval }
+            enum whence { set, cur }
+            read: func(fd: u32, whence: whence) -> result<u64, errno>;
+            close: func(fd: u32) -> result<_, errno>;
+            seed: func() -> u64;
+            name: func() -> string;
+            fail: func() -> result<u32, string>;
+        }
+
+        world p1 {
+            import fs;
+            import sync: func(fd: u32);
+            export run: func();
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.abi = wit_bindgen_cpp::Abi::Preview1Compat;
+    let header = generate_file(opts.clone(), wit, "p1_cpp.h");
+    // strings and errors other than enums keep only the C++ function
+    assert!(!header.contains("test_p1_fs_name"));
+    assert!(!header.contains("test_p1_fs_fail"));
+    let source = generate_file(opts.clone(), wit, "p1.cpp");
+    assert!(has_code(&source, "return int32_t(result.error()) + 1;"));
+    assert!(!generate_file(wit_bindgen_cpp::Opts::default(), wit, "p1.cpp").contains("retptr"));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&test_p1_fs_read),
+                                     int32_t (*)(uint32_t, int32_t, uint64_t*)>);
+        static_assert(std::is_same_v<decltype(&test_p1_fs_close), int32_t (*)(uint32_t)>);
+        static_assert(std::is_same_v<decltype(&test_p1_fs_seed), uint64_t (*)()>);
+        static_assert(std::is_same_v<decltype(&p1_sync), void (*)(uint32_t)>);
+        "#,
+    );
+}
+
+#[test]
+fn init_hooks_run_on_load() {
+    let wit = include_str!("../../../../tests/runtime/smoke/world.wit");
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.emit_init_hooks = true;
+    let source = generate_file(opts.clone(), wit, "smoke.cpp");
+    assert!(has_code(
+        &source,
+        "extern \"C\" __attribute__((__weak__)) void smoke_init() {}"
+    ));
+    assert!(has_code(
+        &source,
+        "extern \"C\" __attribute__((__weak__)) void smoke_fini() {}"
+    ));
+    assert!(has_code(
+        &source,
+        "__attribute__((constructor)) static void smoke_entry() { smoke_init(); }"
+    ));
+    assert!(has_code(
+        &source,
+        "__attribute__((destructor)) static void smoke_exit() { smoke_fini(); }"
+    ));
+    assert!(
+        !generate_file(wit_bindgen_cpp::Opts::default(), wit, "smoke.cpp").contains("smoke_init")
+    );
+    // the defaults are replaced by definitions of the user
+    check_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&smoke_init), void (*)()>);
+        static int loads = 0;
+        extern "C" void smoke_init() { ++loads; }
+        "#,
+    );
+}
+
+#[test]
+fn bench_harness_calls_every_export() {
+    let wit = include_str!("../../../../tests/codegen/strings.wit");
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    let mut resolve = Resolve::default();
+    let pkgs = resolve
+        .push_group(UnresolvedPackageGroup::parse("input.wit", wit).unwrap())
+        .unwrap();
+    let world = resolve.select_world(&pkgs, None).unwrap();
+    let mut files = Default::default();
+    opts.clone()
+        .build()
+        .generate(&resolve, world, &mut files)
+        .unwrap();
+    assert!(!files.iter().any(|(name, _)| name == "the_world_bench.cpp"));
+
+    opts.emit_bench = true;
+    let bench = generate_file(opts.clone(), wit, "the_world_bench.cpp");
+    assert!(has_code(
+        &bench,
+        "bench(\"foo:foo/strings#a\", [] { fooX3AfooX2FstringsX23a(0, 0); });"
+    ));
+    // returned strings are released by the post-return function
+    assert!(has_code(
+        &bench,
+        "bench(\"foo:foo/strings#b\", [] { cabi_post_fooX3AfooX2FstringsX23b(fooX3AfooX2FstringsX23b()); });"
+    ));
+    assert!(has_code(
+        &bench,
+        "bench(\"foo:foo/strings#c\", [] { cabi_post_fooX3AfooX2FstringsX23c(fooX3AfooX2FstringsX23c(0, 0, 0, 0)); });"
+    ));
+    assert_eq!(bench.matches("bench(\"").count(), 3);
+    // the declarations match the generated exports
+    let (dir, _) = write_generated(opts, wit, "");
+    let src = dir.join("bench_check.cpp");
+    std::fs::write(
+        &src,
+        "#include \"the_world.cpp\"\n#include \"the_world_bench.cpp\"\n",
+    )
+    .unwrap();
+    compile_guest(&dir, &src, &[]);
+}
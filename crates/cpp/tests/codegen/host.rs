@@ -0,0 +1,402 @@
+//! The host bindings for WAMR, only compiled with `CPP_HOST_TESTS` set
+
+use super::*;
+
+fn host_opts() -> wit_bindgen_cpp::Opts {
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.host = true;
+    opts
+}
+
+#[test]
+fn host_instance_for_smoke() {
+    let wit = include_str!("../../../../tests/runtime/smoke/world.wit");
+    let source = generate_file(host_opts(), wit, "smoke_host.cpp");
+    // failed instantiations are reported, not asserted
+    assert!(!has_code(&source, "assert(module_inst);"));
+    assert!(has_code(
+        &source,
+        "if (!exec_env) { throw std::runtime_error(error_buf); }"
+    ));
+    assert!(has_code(
+        &source,
+        "return ::exports::smoke::Thunk(exec_env);"
+    ));
+    check_host_generated(
+        host_opts(),
+        wit,
+        r#"
+        static_assert(!std::is_copy_constructible_v<Instance>);
+        static_assert(std::is_nothrow_destructible_v<Instance>);
+        static_assert(std::is_same_v<decltype(&Instance::thunk), void (Instance::*)()>);
+        static_assert(std::is_same_v<decltype(&Instance::has_value), bool (Instance::*)() const>);
+        static_assert(std::is_same_v<decltype(&Instance::error), char const* (Instance::*)() const>);
+        "#,
+    );
+}
+
+#[test]
+fn maybe_unused_exec_env() {
+    let wit = r#"
+        package test:unused;
+
+        interface i {
+            thunk: func();
+        }
+
+        world unused {
+            import i;
+        }
+    "#;
+    let source = generate_file(host_opts(), wit, "unused_host.cpp");
+    assert!(has_code(
+        &source,
+        "testX3AunusedX2FiX00thunk([[maybe_unused]] wasm_exec_env_t exec_env)"
+    ));
+    if env::var_os("CPP_HOST_TESTS").is_some() {
+        compile_generated(host_opts(), wit, verify_host);
+    }
+}
+
+#[test]
+fn host_string_type_for_instance_methods() {
+    let wit = r#"
+        package test:host-strings;
+
+        world host-strings {
+            export measure: func(s: string) -> u32;
+        }
+    "#;
+    // the ABI level export is the same under both settings
+    let export = r#"
+        static_assert(std::is_same_v<decltype(&exports::host_strings::Measure),
+                                     uint32_t (*)(WASMExecEnv*, wit::string)>);
+    "#;
+    check_host_generated(
+        host_opts(),
+        wit,
+        &format!(
+            "{export}
+            static_assert(std::is_same_v<decltype(&Instance::measure),
+                                         uint32_t (Instance::*)(wit::string)>);"
+        ),
+    );
+    let mut opts = host_opts();
+    opts.host_string_type = wit_bindgen_cpp::HostStringType::Std;
+    let source = generate_file(opts.clone(), wit, "host_strings_host.cpp");
+    assert!(has_code(&source, "wit::string::from_view(exec_env, s)"));
+    check_host_generated(
+        opts,
+        wit,
+        &format!(
+            "{export}
+            static_assert(std::is_same_v<decltype(&Instance::measure),
+                                         uint32_t (Instance::*)(std::string)>);"
+        ),
+    );
+}
+
+#[test]
+fn host_wstring_converts_at_the_boundary() {
+    let wit = r#"
+        package test:host-strings;
+
+        interface text {
+            measure: func(s: string) -> u32;
+            echo: func(s: string) -> string;
+        }
+
+        world host-strings {
+            export text;
+        }
+    "#;
+    let mut opts = host_opts();
+    opts.host_string_type = wit_bindgen_cpp::HostStringType::Wstring;
+    let source = generate_file(opts.clone(), wit, "host_strings_host.cpp");
+    assert!(has_code(
+        &source,
+        "return ::exports::test::host_strings::text::Measure(exec_env, wit::string::from_view(exec_env, wit::to_utf8(s)));"
+    ));
+    // converted while the guest still owns the result
+    assert!(has_code(
+        &source,
+        "return wit::to_wstring(::exports::test::host_strings::text::Echo(exec_env, wit::string::from_view(exec_env, wit::to_utf8(s))));"
+    ));
+    check_host_generated(
+        opts.clone(),
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&Instance::text_measure),
+                                     uint32_t (Instance::*)(std::wstring)>);
+        static_assert(std::is_same_v<decltype(&Instance::text_echo),
+                                     std::wstring (Instance::*)(std::wstring)>);
+        "#,
+    );
+
+    // the host implements imports with wide strings as well
+    let wit = include_str!("../wamr_wide_strings/wit/wide.wit");
+    let source = generate_file(opts.clone(), wit, "wide_host.cpp");
+    assert!(has_code(
+        &source,
+        "wit::string::from_view(exec_env, wit::to_utf8(test::wide::host_text::Echo(exec_env, wit::to_wstring(std::string_view(ptr0, len0)))))"
+    ));
+    check_host_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&test::wide::host_text::Echo),
+                                     std::wstring (*)(WASMExecEnv*, std::wstring)>);
+        "#,
+    );
+}
+
+#[test]
+fn host_constructor_emplaces_resource() {
+    let wit = r#"
+        package test:emplace;
+
+        interface counters {
+            resource counter {
+                constructor(start: u32, step: u32);
+                next: func() -> u32;
+            }
+        }
+
+        world emplace {
+            import counters;
+        }
+    "#;
+    let source = generate_file(host_opts(), wit, "emplace_host.cpp");
+    assert!(has_code(
+        &source,
+        "test::emplace::counters::Counter::emplace(exec_env, (uint32_t(arg0)), (uint32_t(arg1)));"
+    ));
+    assert!(!has_code(&source, "release()->get_handle()"));
+    // callable from the Owned deleter
+    check_host_generated(
+        host_opts(),
+        wit,
+        r#"
+        #include "test-emplace-counters-Counter.h"
+        using test::emplace::counters::Counter;
+        static_assert(std::is_same_v<decltype(&Counter::Dtor), void (*)(Counter*)>);
+        "#,
+    );
+}
+
+#[test]
+fn host_resource_drop_calls_on_drop() {
+    let wit = r#"
+        package test:drop-hooks;
+
+        interface hooks {
+            resource handle {
+                constructor(id: u32);
+            }
+        }
+
+        world drop-hooks {
+            import hooks;
+        }
+    "#;
+    let source = generate_file(host_opts(), wit, "drop_hooks_host.cpp");
+    let hook = source.find("on_drop()").unwrap();
+    assert!(hook > source.find("has_value()").unwrap());
+    assert!(hook < source.find("Handle::Dtor(").unwrap());
+    assert_eq!(count_code(&source, "on_drop"), 1);
+    if env::var_os("CPP_HOST_TESTS").is_some() {
+        compile_generated(host_opts(), wit, verify_host);
+    }
+}
+
+#[test]
+fn export_style_static_methods() {
+    let wit = r#"
+        package test:smoke;
+
+        interface imports {
+            thunk: func();
+        }
+
+        world smoke {
+            import imports;
+
+            export thunk: func();
+        }
+    "#;
+    let mut opts = host_opts();
+    opts.export_style = wit_bindgen_cpp::ExportStyle::StaticMethods;
+    let header = generate_file(opts.clone(), wit, "smoke_cpp_host.h");
+    assert!(!has_code(&header, "class Instance"));
+    let source = generate_file(opts.clone(), wit, "smoke_host.cpp");
+    assert!(!has_code(&source, "Instance::"));
+    check_host_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&Smoke::thunk), void (*)(WASMExecEnv*)>);
+        "#,
+    );
+}
+
+#[test]
+fn host_registers_each_interface() {
+    let wit = r#"
+        package test:wiring;
+
+        interface logging {
+            log: func(msg: string);
+        }
+
+        interface clock {
+            now: func() -> u64;
+        }
+
+        world wiring {
+            import logging;
+            import clock;
+            import hello: func();
+            export run: func();
+        }
+    "#;
+    let source = generate_file(host_opts(), wit, "wiring_host.cpp");
+    let clock = &source[source.find("void register_test_wiring_clock()").unwrap()..];
+    assert!(has_code(
+        &clock[..clock.find("\n}").unwrap()],
+        "wasm_runtime_register_natives(\"test:wiring/clock\", test_wiring_clock_funs,"
+    ));
+    // the world registers its own functions and calls the others
+    let world = &source[source.find("void register_wiring()").unwrap()..];
+    let world = &world[..world.find("\n}").unwrap()];
+    assert!(has_code(
+        world,
+        "wasm_runtime_register_natives(\"$root\", root_funs,"
+    ));
+    assert!(has_code(world, "register_test_wiring_clock();"));
+    assert!(has_code(world, "register_test_wiring_logging();"));
+    assert!(!has_code(world, "test_wiring_clock_funs"));
+    check_host_generated(
+        host_opts(),
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&register_test_wiring_clock), void (*)()>);
+        static_assert(std::is_same_v<decltype(&register_test_wiring_logging), void (*)()>);
+        static_assert(std::is_same_v<decltype(&register_wiring), void (*)()>);
+        "#,
+    );
+}
+
+#[test]
+fn import_vtable_for_flavorful_imports() {
+    let mut opts = host_opts();
+    opts.import_vtable = true;
+    let flavorful = include_str!("../../../../tests/runtime/flavorful/world.wit");
+    let interface = &flavorful[..flavorful.find("world flavorful").unwrap()];
+    let wit = format!("{interface}world flavorful-imports {{ import test; }}");
+    let source = generate_file(opts.clone(), &wit, "flavorful_imports_host.cpp");
+    assert!(has_code(
+        &source,
+        "import_vtable->test_flavorful_test_f_list_in_record1(exec_env, "
+    ));
+    check_host_generated(
+        opts,
+        &wit,
+        r#"
+        static_assert(std::is_same_v<decltype(ImportVTable::test_flavorful_test_f_list_in_record1),
+                                     decltype(&::test::flavorful::test::FListInRecord1)>);
+        static_assert(std::is_same_v<decltype(import_vtable), ImportVTable const*>);
+        "#,
+    );
+}
+
+#[test]
+fn import_vtable_checks_completeness() {
+    let mut opts = host_opts();
+    opts.import_vtable = true;
+    let wit = include_str!("../native_import_check/wit/check.wit");
+    let header = generate_file(opts.clone(), wit, "check_cpp_host.h");
+    assert!(has_code(
+        &header,
+        "constexpr bool is_complete() const { return test_check_host_log_log != nullptr && test_check_host_log_next_id != nullptr; }"
+    ));
+    check_host_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(!ImportVTable{}.is_complete());
+        "#,
+    );
+}
+
+#[test]
+fn import_context_holds_function_slots() {
+    let mut opts = host_opts();
+    opts.import_context = true;
+    let wit = include_str!("../wamr_context/wit/context.wit");
+    let source = generate_file(opts.clone(), wit, "context_host.cpp");
+    assert!(has_code(
+        &source,
+        "static_cast<ImportContext*>(wasm_runtime_get_user_data(exec_env))->test_context_host_log_next_id(exec_env);"
+    ));
+    assert!(has_code(
+        &source,
+        "wasm_runtime_set_user_data(exec_env, context);"
+    ));
+    assert!(!has_code(&source, "host_log::NextId("));
+    check_host_generated(
+        opts.clone(),
+        wit,
+        r#"
+        static_assert(std::is_same_v<
+                      decltype(ImportContext::test_context_host_log_log),
+                      std::function<std::remove_pointer_t<decltype(&::test::context::host_log::Log)>>>);
+        static_assert(std::is_same_v<decltype(&Instance::set_import_context),
+                                     void (Instance::*)(ImportContext*)>);
+        bool complete(ImportContext const& context) { return context.is_complete(); }
+        "#,
+    );
+
+    // the slots name types of the interface namespace from the global one
+    if env::var_os("CPP_HOST_TESTS").is_some() {
+        compile_generated(
+            opts,
+            include_str!("../../../../tests/codegen/conventions.wit"),
+            verify_host,
+        );
+    }
+}
+
+#[test]
+fn host_interface_export_accessors() {
+    let flavorful = include_str!("../../../../tests/runtime/flavorful/world.wit");
+    let interface = &flavorful[..flavorful.find("world flavorful").unwrap()];
+    let wit = format!("{interface}world flavorful-exports {{ export test; }}");
+    let source = generate_file(host_opts(), &wit, "flavorful_exports_host.cpp");
+    assert!(has_code(
+        &source,
+        "void Instance::TestFlavorfulTest::f_list_in_record1(test::flavorful::test::ListInRecord1 a) { \
+         return ::exports::test::flavorful::test::FListInRecord1(exec_env, std::move(a)); }"
+    ));
+    check_host_generated(
+        host_opts(),
+        &wit,
+        r#"
+        using test::flavorful::test::ListInRecord1;
+        static_assert(std::is_same_v<decltype(&Instance::TestFlavorfulTest::f_list_in_record1),
+                                     void (Instance::TestFlavorfulTest::*)(ListInRecord1)>);
+        static_assert(std::is_same_v<decltype(&Instance::test_flavorful_test),
+                                     Instance::TestFlavorfulTest (Instance::*)() const>);
+        // the flat methods stay
+        static_assert(std::is_same_v<decltype(&Instance::test_f_list_in_record1),
+                                     void (Instance::*)(ListInRecord1)>);
+        "#,
+    );
+
+    // methods named like keywords, e.g. `explicit` and `bool`
+    let wit = include_str!("../../../../tests/codegen/conventions.wit");
+    let header = generate_file(host_opts(), wit, "the_world_cpp_host.h");
+    assert!(has_code(&header, "void explicit_();"));
+    if env::var_os("CPP_HOST_TESTS").is_some() {
+        compile_generated(host_opts(), wit, verify_host);
+    }
+}
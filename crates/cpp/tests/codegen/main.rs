@@ -0,0 +1,334 @@
+use heck::*;
+use std::env;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use wit_bindgen_core::wit_parser::{Resolve, UnresolvedPackageGroup};
+
+mod abi;
+mod files;
+mod functions;
+mod host;
+mod resources;
+mod types;
+
+macro_rules! codegen_test {
+    ($id:ident $name:tt $test:tt) => {
+        #[test]
+        fn $id() {
+            if [
+                "go_params",
+                "guest-name",
+                "import-and-export-resource",
+                "import-and-export-resource-alias",
+                "interface-has-go-keyword",
+                "issue551",
+                "issue573",
+                "issue607",
+                "issue668",
+                "keywords",
+                "lift-lower-foreign",
+                "lists",
+                "multi-return",
+                "multiversion",
+                "option-result",
+                "record-has-go-keyword-and-used-in-fn",
+                "resource-alias",
+                "resource-borrow-in-record",
+                "resource-borrow-in-record-export",
+                "resource-local-alias",
+                "resource-local-alias-borrow",
+                "resource-local-alias-borrow-import",
+                "resource-own-in-other-interface",
+                "resources",
+                "resources-in-aggregates",
+                "resources-with-lists",
+                "result-empty",
+                "ret-areas",
+                "return-resource-from-export",
+                "same-names5",
+                "simple-http",
+                "simple-lists",
+                "use-across-interfaces",
+                "variants",
+                "variants-unioning-types",
+                "worlds-with-types",
+                "zero-size-tuple",
+            ]
+            .contains(&$name)
+            {
+                let test_all_code = env::var_os("CPP_ALL_TESTS").is_some();
+                if !test_all_code {
+                    return;
+                }
+            }
+            test_helpers::run_world_codegen_test(
+                "cpp",
+                $test.as_ref(),
+                |resolve, world, files| {
+                    wit_bindgen_cpp::Opts::default()
+                        .build()
+                        .generate(resolve, world, files)
+                        .unwrap()
+                },
+                verify,
+            );
+            let test_host_code = env::var_os("CPP_HOST_TESTS").is_some();
+            if test_host_code {
+                test_helpers::run_world_codegen_test(
+                    "cpp-host",
+                    $test.as_ref(),
+                    |resolve, world, files| {
+                        let mut opts = wit_bindgen_cpp::Opts::default();
+                        opts.host = true;
+                        opts.build().generate(resolve, world, files).unwrap()
+                    },
+                    verify_host,
+                );
+            }
+        }
+    };
+}
+
+test_helpers::codegen_tests!();
+
+fn verify(dir: &Path, name: &str) {
+    verify_with_args(dir, name, &[]);
+}
+
+fn verify_no_exceptions(dir: &Path, name: &str) {
+    verify_with_args(dir, name, &["-fno-exceptions"]);
+}
+
+fn verify_with_args(dir: &Path, name: &str, extra_args: &[&str]) {
+    let name = name.to_snake_case();
+    compile_guest(dir, &dir.join(format!("{name}.cpp")), extra_args);
+}
+
+fn compile_guest(dir: &Path, c_src: &Path, extra_args: &[&str]) {
+    let sdk_path = PathBuf::from(
+        env::var_os("WASI_SDK_PATH").expect("environment variable WASI_SDK_PATH should be set"),
+    );
+    let sysroot = sdk_path.join("share/wasi-sysroot");
+    let additional_includes = PathBuf::from(
+        env::var_os("CARGO_MANIFEST_DIR")
+            .expect("environment variable CARGO_MANIFEST_DIR should get set by cargo"),
+    )
+    .join("test_headers");
+
+    let mut shared_args = vec![
+        "--sysroot",
+        sysroot.to_str().unwrap(),
+        "-I",
+        dir.to_str().unwrap(),
+        "-I",
+        additional_includes.to_str().unwrap(),
+        // "-Wall",
+        // "-Wextra",
+        // "-Werror",
+        // "-Wno-unused-parameter",
+        "-std=c++2b",
+        "-c",
+        "-o",
+    ];
+    // an explicit standard replaces the default
+    if extra_args.iter().any(|arg| arg.starts_with("-std=")) {
+        shared_args.retain(|arg| !arg.starts_with("-std="));
+    }
+
+    let mut cmd = Command::new(sdk_path.join("bin/clang++"));
+    cmd.args(extra_args);
+    cmd.args(&shared_args);
+    cmd.arg(dir.join("obj.o"));
+    cmd.arg(c_src);
+    test_helpers::run_command(&mut cmd);
+}
+
+fn verify_cpp17(dir: &Path, name: &str) {
+    verify_with_args(dir, name, &["-std=c++17"]);
+}
+
+// compile `<world>_fwd.h` on its own
+fn verify_fwd_header(dir: &Path, name: &str) {
+    let name = name.to_snake_case();
+    let sdk_path = PathBuf::from(
+        env::var_os("WASI_SDK_PATH").expect("environment variable WASI_SDK_PATH should be set"),
+    );
+    let sysroot = sdk_path.join("share/wasi-sysroot");
+    let mut cmd = Command::new(sdk_path.join("bin/clang++"));
+    cmd.arg("--sysroot")
+        .arg(&sysroot)
+        .args(["-x", "c++", "-std=c++17", "-fsyntax-only"])
+        .arg(dir.join(format!("{name}_fwd.h")));
+    test_helpers::run_command(&mut cmd);
+}
+
+fn verify_host(dir: &Path, name: &str) {
+    let name = name.to_snake_case();
+    compile_host(dir, &dir.join(format!("{name}_host.cpp")));
+}
+
+fn compile_host(dir: &Path, c_src: &Path) {
+    let additional_includes = PathBuf::from(
+        env::var_os("CARGO_MANIFEST_DIR")
+            .expect("environment variable CARGO_MANIFEST_DIR should get set by cargo"),
+    )
+    .join("test_headers");
+
+    let shared_args = vec![
+        "-I",
+        dir.to_str().unwrap(),
+        "-I",
+        additional_includes.to_str().unwrap(),
+        // "-Wall",
+        // "-Wextra",
+        // "-Werror",
+        // "-Wno-unused-parameter",
+        "-std=c++2b",
+        "-c",
+        "-o",
+    ];
+
+    let mut cmd = Command::new("clang++");
+    cmd.args(&shared_args);
+    cmd.arg(dir.join("obj.o"));
+    cmd.arg(c_src);
+    test_helpers::run_command(&mut cmd);
+}
+
+/// Generate bindings for an inline WIT document and return the contents
+/// of the generated file called `file`
+fn generate_file(opts: wit_bindgen_cpp::Opts, wit: &str, file: &str) -> String {
+    let mut resolve = Resolve::default();
+    let pkgs = resolve
+        .push_group(UnresolvedPackageGroup::parse("input.wit", wit).unwrap())
+        .unwrap();
+    let world = resolve.select_world(&pkgs, None).unwrap();
+    let mut files = Default::default();
+    opts.build().generate(&resolve, world, &mut files).unwrap();
+    let (_, contents) = files.iter().find(|(name, _)| *name == file).unwrap();
+    String::from_utf8(contents.to_vec()).unwrap()
+}
+
+/// Write the bindings of an inline WIT document to a directory of their
+/// own, tests generating the same world can run in parallel
+fn write_generated(opts: wit_bindgen_cpp::Opts, wit: &str, extra: &str) -> (PathBuf, String) {
+    let mut resolve = Resolve::default();
+    let pkgs = resolve
+        .push_group(UnresolvedPackageGroup::parse("input.wit", wit).unwrap())
+        .unwrap();
+    let world = resolve.select_world(&pkgs, None).unwrap();
+    let world_name = resolve.worlds[world].name.clone();
+    let mut hasher = DefaultHasher::new();
+    (format!("{opts:?}"), wit, extra).hash(&mut hasher);
+    let dir = test_helpers::test_directory(
+        "codegen",
+        "cpp-inline",
+        &format!("{world_name}-{:016x}", hasher.finish()),
+    );
+    let mut files = Default::default();
+    opts.build().generate(&resolve, world, &mut files).unwrap();
+    for (file, contents) in files.iter() {
+        let dst = dir.join(file);
+        std::fs::create_dir_all(dst.parent().unwrap()).unwrap();
+        std::fs::write(&dst, contents).unwrap();
+    }
+    (dir, world_name)
+}
+
+/// Generate bindings for an inline WIT document and compile them with one
+/// of the `verify` functions above
+fn compile_generated(opts: wit_bindgen_cpp::Opts, wit: &str, verify: fn(&Path, &str)) {
+    let (dir, world_name) = write_generated(opts, wit, "");
+    verify(&dir, &world_name);
+}
+
+/// Compile the generated guest bindings, and `check` after including the
+/// world header, which tests the generated API with `static_assert`s and
+/// functions using it
+fn check_generated(opts: wit_bindgen_cpp::Opts, wit: &str, check: &str) {
+    check_generated_with_args(opts, wit, &[], check);
+}
+
+fn check_generated_with_args(
+    opts: wit_bindgen_cpp::Opts,
+    wit: &str,
+    extra_args: &[&str],
+    check: &str,
+) {
+    let (dir, world_name) = write_generated(opts, wit, check);
+    verify_with_args(&dir, &world_name, extra_args);
+    let src = write_check(
+        &dir,
+        &format!("{}_cpp.h", world_name.to_snake_case()),
+        check,
+    );
+    compile_guest(&dir, &src, extra_args);
+}
+
+/// Like `check_generated` for the host header, which is only compiled with
+/// `CPP_HOST_TESTS` set. The host side lifting of strings doesn't compile
+/// yet, so the source is left out.
+fn check_host_generated(opts: wit_bindgen_cpp::Opts, wit: &str, check: &str) {
+    if env::var_os("CPP_HOST_TESTS").is_none() {
+        return;
+    }
+    let (dir, world_name) = write_generated(opts, wit, check);
+    let header = format!("{}_cpp_host.h", world_name.to_snake_case());
+    compile_host(&dir, &write_check(&dir, &header, check));
+}
+
+fn write_check(dir: &Path, header: &str, check: &str) -> PathBuf {
+    let src = dir.join("check.cpp");
+    let contents = format!("#include \"{header}\"\n#include <type_traits>\n{check}\n");
+    std::fs::write(&src, contents).unwrap();
+    src
+}
+
+/// Split C++ code into identifiers, numbers, string literals and single
+/// punctuation characters, for checks independent of the formatting
+fn tokens(code: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut chars = code.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        if c.is_alphanumeric() || c == '_' {
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+        } else if c == '"' {
+            let mut escaped = false;
+            for (i, c) in chars.by_ref() {
+                end = i + c.len_utf8();
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => break,
+                    _ => {}
+                }
+            }
+        }
+        tokens.push(&code[start..end]);
+    }
+    tokens
+}
+
+/// How often the tokens of `snippet` follow each other in `code`
+fn count_code(code: &str, snippet: &str) -> usize {
+    let code = tokens(code);
+    let snippet = tokens(snippet);
+    code.windows(snippet.len())
+        .filter(|window| *window == snippet.as_slice())
+        .count()
+}
+
+fn has_code(code: &str, snippet: &str) -> bool {
+    count_code(code, snippet) > 0
+}
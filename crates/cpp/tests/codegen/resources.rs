@@ -0,0 +1,304 @@
+//! Imported and exported resources on the guest side
+
+use super::*;
+
+#[test]
+fn resource_table_is_function_local_static() {
+    let wit = r#"
+        package test:tables;
+
+        interface i {
+            resource r {
+                constructor();
+            }
+        }
+
+        world tables {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.short_cut = true;
+    let source = generate_file(opts, wit, "tables_native.cpp");
+    assert!(!has_code(&source, "::resources;"));
+    // the table of each resource type is created on first use
+    let common = include_str!("../../helper-types/wit-common.h");
+    assert!(has_code(
+        common,
+        "static std::map<int32_t, R> &resources() {"
+    ));
+}
+
+#[test]
+fn owned_resources_convert_to_borrows() {
+    let wit = r#"
+        package test:owned-borrows;
+
+        interface test {
+            resource thing {
+                constructor(v: u32);
+            }
+
+            get-value: func(v: borrow<thing>) -> u32;
+        }
+
+        world owned-borrows {
+            export test;
+        }
+    "#;
+    let class = generate_file(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        "exports-test-owned_borrows-test-Thing.h",
+    );
+    assert!(has_code(
+        &class,
+        "class Thing : public wit::ResourceExportBase<Thing>"
+    ));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        #include "exports-test-owned_borrows-test-Thing.h"
+        using namespace exports::test::owned_borrows::test;
+        static_assert(std::is_same_v<decltype(&GetValue),
+                                     uint32_t (*)(std::reference_wrapper<const Thing>)>);
+        // Thing::Owned converts to the const reference the borrow wraps
+        static_assert(std::is_convertible_v<Thing::Owned const&, Thing const&>);
+        uint32_t pass(Thing::Owned const& thing) { return GetValue(thing); }
+        "#,
+    );
+}
+
+#[test]
+fn imported_resources_reset() {
+    let wit = include_str!("../../../../tests/runtime/resource_reset/world.wit");
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using test::resource_reset::test::Thing;
+        static_assert(requires(Thing thing) { thing.reset(); });
+        static_assert(std::is_same_v<decltype(std::declval<Thing const&>().has_value()), bool>);
+        // only explicitly convertible to bool
+        static_assert(std::is_constructible_v<bool, Thing const&>);
+        static_assert(!std::is_convertible_v<Thing const&, bool>);
+        "#,
+    );
+}
+
+#[test]
+fn explicit_this_in_member_bodies() {
+    let wit = r#"
+        package test:members;
+
+        interface res {
+            resource counter {
+                add: func(n: u32) -> u32;
+            }
+        }
+
+        world members {
+            import res;
+        }
+    "#;
+    let body = |explicit_this| {
+        let mut opts = wit_bindgen_cpp::Opts::default();
+        opts.explicit_this = explicit_this;
+        let source = generate_file(opts, wit, "members.cpp");
+        let start = source.find("::Counter::~Counter()").unwrap();
+        let end = start + source[start..].find("::Counter::Counter(").unwrap();
+        source[start..end].to_string()
+    };
+    let implicit = body(false);
+    assert!(has_code(&implicit, "if (handle>=0) {"));
+    assert!(has_code(&implicit, "(*this).get_handle(), (int32_t(n))"));
+    let explicit = body(true);
+    assert!(has_code(&explicit, "if (this->handle>=0) {"));
+    assert!(has_code(&explicit, "(this->handle);"));
+    assert!(has_code(&explicit, "(this->get_handle(), (int32_t(n))"));
+    // nothing else changes
+    assert_eq!(
+        tokens(
+            &explicit
+                .replace("this->handle", "handle")
+                .replace("this->get_handle()", "(*this).get_handle()")
+        ),
+        tokens(&implicit)
+    );
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.explicit_this = true;
+    compile_generated(opts, wit, verify);
+}
+
+#[test]
+fn borrow_lists_name_the_resource() {
+    let wit = r#"
+        package test:borrow-lists;
+
+        interface test {
+            resource thing {
+                value: func() -> u32;
+            }
+            sum: func(things: list<borrow<thing>>) -> u32;
+        }
+
+        world borrow-lists {
+            import test;
+            export test;
+        }
+    "#;
+    let source = generate_file(wit_bindgen_cpp::Opts::default(), wit, "borrow_lists.cpp");
+    // the borrows are references, neither side drops a handle
+    let lift = &source[source.find("X23sum(").unwrap()..];
+    assert!(!has_code(lift, "resource_drop"));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        namespace imported = test::borrow_lists::test;
+        namespace exported = exports::test::borrow_lists::test;
+        static_assert(std::is_same_v<
+                      decltype(&imported::Sum),
+                      uint32_t (*)(wit::span<std::reference_wrapper<const imported::Thing> const>)>);
+        static_assert(std::is_same_v<
+                      decltype(&exported::Sum),
+                      uint32_t (*)(wit::vector<std::reference_wrapper<const exported::Thing>>&&)>);
+        "#,
+    );
+}
+
+#[test]
+fn resources_spawning_themselves() {
+    let wit = r#"
+        package test:spawn;
+
+        interface nodes {
+            resource node {
+                constructor(depth: u32);
+                spawn: func() -> node;
+                siblings: func(count: u32) -> list<node>;
+                kin: func() -> family;
+            }
+            record family {
+                parent: option<node>,
+                children: list<node>,
+            }
+        }
+
+        world spawn {
+            import nodes;
+        }
+    "#;
+    // the record holding nodes is defined after the resource
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using namespace test::spawn::nodes;
+        static_assert(std::is_same_v<decltype(&Node::Spawn), Node (Node::*)() const>);
+        static_assert(std::is_same_v<decltype(&Node::Siblings),
+                                     wit::vector<Node> (Node::*)(uint32_t) const>);
+        static_assert(std::is_same_v<decltype(&Node::Kin), Family (Node::*)() const>);
+        static_assert(std::is_same_v<decltype(Family::parent), std::optional<Node>>);
+        static_assert(std::is_same_v<decltype(Family::children), wit::vector<Node>>);
+        "#,
+    );
+}
+
+#[test]
+fn resource_static_functions_are_static_members() {
+    let wit = include_str!("../../../../tests/runtime/resource_statics/world.wit");
+    let class = generate_file(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        "exports-test-resource_statics-counters-Counter.h",
+    );
+    // each on its own line after the inline `New`
+    assert!(class.contains("{return Owned(new Counter(start));}\n"));
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        #include "exports-test-resource_statics-counters-Counter.h"
+        namespace imported = test::resource_statics::counters;
+        namespace exported = exports::test::resource_statics::counters;
+        static_assert(std::is_same_v<decltype(&imported::Counter::FromString),
+                                     imported::Counter (*)(std::string_view)>);
+        static_assert(std::is_same_v<decltype(&imported::Counter::Zero), imported::Counter (*)()>);
+        static_assert(std::is_same_v<decltype(&imported::Counter::Value),
+                                     uint32_t (imported::Counter::*)() const>);
+        static_assert(std::is_same_v<decltype(&exported::Counter::FromString),
+                                     exported::Counter::Owned (*)(wit::string&&)>);
+        static_assert(std::is_same_v<decltype(&exported::Counter::Zero),
+                                     exported::Counter::Owned (*)()>);
+        static_assert(std::is_same_v<decltype(&exported::Counter::Value),
+                                     uint32_t (exported::Counter::*)()>);
+        "#,
+    );
+}
+
+#[test]
+fn callback_resources_become_std_function() {
+    let wit = r#"
+        package test:callbacks;
+
+        interface events {
+            resource listener {
+                notify: func(name: string, count: u32) -> bool;
+            }
+            // more than a single method
+            resource source {
+                constructor();
+                name: func() -> string;
+            }
+        }
+
+        interface emitters {
+            use events.{listener, source};
+
+            resource emitter {
+                constructor();
+                subscribe: func(l: listener);
+                attach: func(s: source);
+            }
+        }
+
+        world callbacks {
+            import events;
+            export emitters;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.callback_resources = true;
+    let class = generate_file(
+        opts.clone(),
+        wit,
+        "exports-test-callbacks-emitters-Emitter.h",
+    );
+    // the source keeps its class
+    assert_eq!(count_code(&class, "std::function"), 1);
+    let source = generate_file(opts.clone(), wit, "callbacks.cpp");
+    assert!(has_code(
+        &source,
+        "Subscribe([callback = std::make_shared<::test::callbacks::events::Listener>(wit::ResourceImportBase{arg1})](auto&&... args) { return callback->Notify(std::forward<decltype(args)>(args)...); });"
+    ));
+    // exports taking an imported resource by value don't compile yet
+    let wit = wit.replace("attach: func(s: source);", "");
+    check_generated(
+        opts,
+        &wit,
+        r#"
+        #include "exports-test-callbacks-emitters-Emitter.h"
+        using exports::test::callbacks::emitters::Emitter;
+        static_assert(std::is_same_v<decltype(&Emitter::Subscribe),
+                                     void (Emitter::*)(std::function<bool(std::string_view, uint32_t)>)>);
+        "#,
+    );
+    let class = generate_file(
+        wit_bindgen_cpp::Opts::default(),
+        &wit,
+        "exports-test-callbacks-emitters-Emitter.h",
+    );
+    assert!(!has_code(&class, "std::function"));
+}
@@ -0,0 +1,589 @@
+//! The C++ types generated for WIT types
+
+use super::*;
+
+#[test]
+fn derive_ord_for_comparable_records() {
+    let wit = r#"
+        package test:ord;
+
+        interface i {
+            record named { name: string, x: u32 }
+            record listed { items: list<u32> }
+            f: func(a: named, b: listed);
+        }
+
+        world derive-ord {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.derive_ord = true;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::ord::i;
+        static_assert(std::three_way_comparable<Named>);
+        static_assert(!std::three_way_comparable<Listed>);
+        "#,
+    );
+}
+
+#[test]
+fn fast_scalar_types_only_in_signatures() {
+    let wit = r#"
+        package test:scalars;
+
+        interface i {
+            record point { x: u32, y: u32 }
+            f: func(a: u32, p: point) -> u32;
+        }
+
+        world fast-scalars {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.scalar_types = wit_bindgen_cpp::ScalarTypes::Fast;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::scalars::i;
+        static_assert(std::is_same_v<decltype(&F), uint_fast32_t (*)(uint_fast32_t, Point)>);
+        // the record fields match the ABI
+        static_assert(std::is_same_v<decltype(Point::x), uint32_t>);
+        "#,
+    );
+}
+
+#[test]
+fn chrono_conversions_for_datetime() {
+    let wit = r#"
+        package test:clocks;
+
+        interface i {
+            record datetime { seconds: u64, nanoseconds: u32 }
+            record other { seconds: u32, nanoseconds: u32 }
+            now: func() -> datetime;
+            f: func(o: other);
+        }
+
+        world chrono {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.chrono_conversions = true;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::clocks::i;
+        template <class T> concept chrono_convertible = requires(T const& t) { t.to_chrono(); };
+        static_assert(chrono_convertible<Datetime>);
+        static_assert(std::is_same_v<decltype(Datetime::from_chrono(std::chrono::nanoseconds())),
+                                     Datetime>);
+        // only the fields of wasi:clocks/wall-clock.datetime
+        static_assert(!chrono_convertible<Other>);
+        "#,
+    );
+}
+
+#[test]
+fn enum_discriminant_constants() {
+    let wit = r#"
+        package test:enums;
+
+        interface i {
+            enum my-errno { success, a, b }
+            f: func() -> my-errno;
+        }
+
+        world enums {
+            import i;
+        }
+    "#;
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using namespace test::enums::i;
+        static_assert(std::is_same_v<decltype(MyErrno_Success_value), uint8_t const>);
+        static_assert(MyErrno_Success_value == 0);
+        static_assert(MyErrno_A_value == 1);
+        static_assert(MyErrno_B_value == uint8_t(MyErrno::kB));
+        "#,
+    );
+}
+
+#[test]
+fn enum_reflection_entries() {
+    let wit = r#"
+        package test:reflection;
+
+        interface i {
+            enum color { red, dark-green, blue }
+            get: func() -> color;
+        }
+
+        world reflection {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.enum_reflection = true;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::reflection::i;
+        static_assert(Color_entries.size() == 3);
+        static_assert(Color_entries[0].first == Color::kRed && Color_entries[0].second == "red");
+        static_assert(Color_entries[1].first == Color::kDarkGreen &&
+                      Color_entries[1].second == "dark-green");
+        static_assert(Color_entries[2].first == Color::kBlue && Color_entries[2].second == "blue");
+        "#,
+    );
+}
+
+#[test]
+fn consteval_enum_factories() {
+    let wit = r#"
+        package test:factories;
+
+        interface test {
+            enum color { red, light-green }
+            paint: func(c: color);
+        }
+
+        world factories {
+            import test;
+        }
+    "#;
+    let header = generate_file(wit_bindgen_cpp::Opts::default(), wit, "factories_cpp.h");
+    assert!(!header.contains("consteval"));
+
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.consteval_factories = true;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::factories::test;
+        #if __cpp_consteval >= 201811L
+        static_assert(make_Color_red() == Color::kRed);
+        static_assert(make_Color_light_green() == Color::kLightGreen);
+        // immediate functions can use the discriminants
+        consteval uint32_t light_green() { return to_discriminant(make_Color_light_green()); }
+        static_assert(light_green() == 1);
+        #endif
+        "#,
+    );
+}
+
+#[test]
+fn layout_constants_for_record() {
+    let wit = r#"
+        package test:layout;
+
+        interface i {
+            record pair { a: u8, b: u32 }
+            get: func() -> pair;
+        }
+
+        world layouts {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.layouts = true;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using Layout = wit::layout<test::layout::i::Pair>;
+        static_assert(Layout::size == 8);
+        static_assert(Layout::align == 4);
+        static_assert(Layout::flat.size() == 2);
+        static_assert(Layout::flat[0] == wit::flat_type::i32 &&
+                      Layout::flat[1] == wit::flat_type::i32);
+        "#,
+    );
+}
+
+#[test]
+fn compat_typedef_for_renamed_type() {
+    let wit = r#"
+        package test:compat;
+
+        interface i {
+            enum my-errno { success, failure }
+            check: func() -> my-errno;
+        }
+
+        world compat {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.rename_type = vec![("test.compat.i/my-errno".into(), "Errno".into())];
+    opts.emit_compat_typedefs = true;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::compat::i;
+        static_assert(std::is_enum_v<Errno>);
+        static_assert(std::is_same_v<MyErrno, Errno>);
+        static_assert(std::is_same_v<decltype(&Check), Errno (*)()>);
+        "#,
+    );
+}
+
+#[test]
+fn bare_error_results() {
+    let wit = r#"
+        package test:bare;
+
+        interface i {
+            f: func() -> result<u32>;
+        }
+
+        world bare {
+            import i;
+        }
+    "#;
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&test::bare::i::F),
+                                     std::expected<uint32_t, wit::Void> (*)()>);
+        "#,
+    );
+
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.bare_error = wit_bindgen_cpp::BareError::Optional;
+    let source = generate_file(opts.clone(), wit, "bare.cpp");
+    assert!(!source.contains("std::unexpected"));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&test::bare::i::F), std::optional<uint32_t> (*)()>);
+        "#,
+    );
+}
+
+#[test]
+fn wit_result_replaces_expected() {
+    let wit = r#"
+        package test:results;
+
+        world results {
+            import checked-div: func(a: u32, b: u32) -> result<u32, u8>;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.wit_result = true;
+    let header = generate_file(opts.clone(), wit, "results_cpp.h");
+    assert!(!header.contains("std::expected"));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        static_assert(std::is_same_v<decltype(&results::CheckedDiv),
+                                     wit::result<uint32_t, uint8_t> (*)(uint32_t, uint32_t)>);
+        static_assert(requires(wit::result<uint32_t, uint8_t> r) {
+            r.error_or(0);
+            r.map_err([](uint8_t e) { return int(e); });
+        });
+        "#,
+    );
+}
+
+#[test]
+fn error_enums_map_to_error_code() {
+    let wit = r#"
+        package test:errors;
+
+        interface fs {
+            enum my-errno { success, a, b }
+            enum mode { read, write }
+            open: func(m: mode) -> result<u32, my-errno>;
+        }
+
+        world errors {
+            import fs;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.error_code = true;
+    check_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::errors::fs;
+        static_assert(std::is_error_code_enum<MyErrno>::value);
+        // only enums used as an error
+        static_assert(!std::is_error_code_enum<Mode>::value);
+        std::string message() {
+            std::error_code error = MyErrno::kB;
+            return error.message();
+        }
+        "#,
+    );
+}
+
+#[test]
+fn records_assert_nothrow_move() {
+    let wit = r#"
+        package test:moves;
+
+        interface people {
+            record person { name: string, tags: list<string> }
+            variant contact { mail(string), phone(u64) }
+            // the std::expected implementation is up to the user
+            record checked { person: person, valid: result<_, string> }
+            add: func(p: person, c: contact, k: checked);
+        }
+
+        world moves {
+            import people;
+        }
+    "#;
+    let header = generate_file(wit_bindgen_cpp::Opts::default(), wit, "moves_cpp.h");
+    assert_eq!(
+        count_code(&header, "is_nothrow_move_constructible<Person>"),
+        1
+    );
+    assert_eq!(
+        count_code(&header, "is_nothrow_move_constructible<Contact>"),
+        1
+    );
+    assert!(!has_code(&header, "is_nothrow_move_constructible<Checked>"));
+    // the asserts hold for the helper types and tl::expected alike
+    compile_generated(wit_bindgen_cpp::Opts::default(), wit, verify);
+}
+
+#[test]
+fn variant_record_payload_fields() {
+    let wit = r#"
+        package test:payload;
+
+        interface shapes {
+            record point { x: s32, y: s32 }
+            record label { text: string }
+            variant shape { none, at(point), named(label) }
+            draw: func(s: shape);
+        }
+
+        world payload {
+            import shapes;
+        }
+    "#;
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        using namespace test::payload::shapes;
+        static_assert(std::is_same_v<decltype(std::declval<Shape::At&>().fields()),
+                                     std::tuple<int32_t&, int32_t&>>);
+        static_assert(std::is_same_v<decltype(std::declval<Shape::At const&>().fields()),
+                                     std::tuple<int32_t const&, int32_t const&>>);
+        // a single field needs no unpacking
+        template <class T> concept unpackable = requires(T t) { t.fields(); };
+        static_assert(unpackable<Shape::At>);
+        static_assert(!unpackable<Shape::Named>);
+        "#,
+    );
+}
+
+#[test]
+fn trailing_commas_in_cases_and_initializers() {
+    let wit = r#"
+        package test:commas;
+
+        interface i {
+            enum color { red, green }
+            flags perms { read, write }
+            record point { x: u32, y: u32 }
+            origin: func() -> point;
+        }
+
+        world commas {
+            import i;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    let header = generate_file(opts.clone(), wit, "commas_cpp.h");
+    assert!(has_code(&header, "kGreen = 1 };"));
+    assert!(has_code(&header, "kWrite = (1ULL<<1) };"));
+    let source = generate_file(opts.clone(), wit, "commas.cpp");
+    assert!(has_code(
+        &source,
+        "return Point{std::move((uint32_t(l1))), std::move((uint32_t(l2)))};"
+    ));
+
+    opts.trailing_commas = true;
+    let header = generate_file(opts.clone(), wit, "commas_cpp.h");
+    assert!(has_code(&header, "kRed = 0, kGreen = 1, };"));
+    assert!(has_code(&header, "kWrite = (1ULL<<1), };"));
+    let source = generate_file(opts.clone(), wit, "commas.cpp");
+    assert!(has_code(
+        &source,
+        "return Point{std::move((uint32_t(l1))), std::move((uint32_t(l2))), };"
+    ));
+    compile_generated(opts, wit, verify);
+}
+
+#[test]
+fn debug_dump_records_and_variants() {
+    let wit = r#"
+        package test:dump;
+
+        interface shapes {
+            record point { x: s32, y: s32 }
+            variant shape { circle(u32), polygon(list<point>), empty }
+            get: func() -> shape;
+        }
+
+        world dump {
+            import shapes;
+        }
+    "#;
+    check_generated(
+        wit_bindgen_cpp::Opts::default(),
+        wit,
+        r#"
+        #include <ostream>
+        using namespace test::dump::shapes;
+        template <class T> concept dumpable = requires(T const& t, std::ostream& os) { t.dump(os); };
+        static_assert(!dumpable<Point>);
+        "#,
+    );
+
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.debug_dump = true;
+    let header = generate_file(opts.clone(), wit, "dump_cpp.h");
+    assert!(has_code(&header, "os << \"Point {\\n\";"));
+    assert!(has_code(
+        &header,
+        "wit::dump_field(os, indent + 1, \"x\", x);"
+    ));
+    assert!(has_code(&header, "os << \"Shape::Polygon(\";"));
+    assert!(has_code(&header, "case 2: os << \"Shape::Empty\"; break;"));
+    check_generated(
+        opts,
+        wit,
+        r#"
+        #include <sstream>
+        using namespace test::dump::shapes;
+        std::string dump(Shape const& shape, Point const& point) {
+            std::ostringstream os;
+            shape.dump(os);
+            point.dump(os, 1);
+            return os.str();
+        }
+        "#,
+    );
+}
+
+#[test]
+fn alignas_where_canonical_alignment_is_larger() {
+    let wit = r#"
+        package test:align;
+
+        interface test {
+            record named { name: string, id: u32 }
+            record wide { id: u64, small: u8 }
+            f: func(a: named, b: wide);
+        }
+
+        world align {
+            import test;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.wasm64 = true;
+    let header = generate_file(opts.clone(), wit, "align_cpp.h");
+    assert!(!header.contains("alignas"));
+
+    // host strings keep 32 bit guest addresses, wasm64 aligns them to 8
+    opts.host = true;
+    let header = generate_file(opts.clone(), wit, "align_cpp_host.h");
+    assert!(has_code(&header, "struct alignas(8) Named {"));
+    check_host_generated(
+        opts,
+        wit,
+        r#"
+        using namespace test::align::test;
+        static_assert(alignof(Named) == 8);
+        static_assert(alignof(Wide) == alignof(uint64_t));
+        "#,
+    );
+}
+
+#[test]
+fn cpp17_polyfills_compile_variants() {
+    // lists are lowered through the span polyfill
+    for world in ["variants", "lists", "nested_lists", "ownership"] {
+        let wit = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/runtime")
+            .join(world)
+            .join("world.wit");
+        test_helpers::run_world_codegen_test(
+            "cpp17",
+            &wit,
+            |resolve, world, files| {
+                let mut opts = wit_bindgen_cpp::Opts::default();
+                opts.cpp_std = wit_bindgen_cpp::CppStd::Cpp17;
+                opts.build().generate(resolve, world, files).unwrap()
+            },
+            verify_cpp17,
+        );
+    }
+
+    let wit = r#"
+        package test:polyfill;
+
+        world polyfill {
+            import check: func(a: u32) -> result<u32, string>;
+        }
+    "#;
+    let mut opts = wit_bindgen_cpp::Opts::default();
+    opts.cpp_std = wit_bindgen_cpp::CppStd::Cpp17;
+    let header = generate_file(opts.clone(), wit, "polyfill_cpp.h");
+    assert!(!header.contains("std::expected"));
+    let source = generate_file(opts.clone(), wit, "polyfill.cpp");
+    assert!(!source.contains("std::unexpected"));
+    check_generated_with_args(
+        opts,
+        wit,
+        &["-std=c++17"],
+        r#"
+        static_assert(std::is_same_v<decltype(&polyfill::Check),
+                                     wit::expected<uint32_t, wit::string> (*)(uint32_t)>);
+        "#,
+    );
+}
+
+#[test]
+fn string_helpers_assert_utf8_chars() {
+    let common = include_str!("../../helper-types/wit-common.h");
+    assert!(has_code(common, "static_assert(CHAR_BIT == 8,"));
+    assert!(has_code(
+        common,
+        "static_assert(std::is_same<uint8_t, unsigned char>::value,"
+    ));
+    // both string implementations see the asserts
+    for helper in [
+        include_str!("../../helper-types/wit-guest.h"),
+        include_str!("../../helper-types/wit-host.h"),
+    ] {
+        let include = helper.find("#include \"wit-common.h\"").unwrap();
+        assert!(include < helper.find("class string {").unwrap());
+    }
+}
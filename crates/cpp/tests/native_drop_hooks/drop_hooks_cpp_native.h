@@ -0,0 +1,12 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_NATIVE_BINDINGS_DROP_HOOKS_H
+#define __CPP_NATIVE_BINDINGS_DROP_HOOKS_H
+#define WIT_HOST_DIRECT
+#include "test-drop_hooks-hooks-Handle.h"
+#include <cstdint>
+#include <utility>
+// export_interface Interface(Id { idx: 1 })
+namespace exports {namespace test {namespace drop_hooks {namespace runner {void Run();
+}}}}
+
+#endif
@@ -0,0 +1,23 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#include "drop_hooks_cpp_native.h"
+extern "C" __attribute__((import_module("test:drop-hooks/runner")))
+__attribute__((import_name("run")))
+void testX3Adrop_hooksX2FrunnerX23run();
+extern "C" void testX3Adrop_hooksX2FhooksX00X5Bresource_dropX5Dhandle(int32_t arg0)
+{
+  auto ptr = test::drop_hooks::hooks::Handle::remove_resource(arg0);
+  assert(ptr.has_value());
+  (*ptr)->on_drop();
+  test::drop_hooks::hooks::Handle::Dtor(*ptr);
+}
+extern "C" int32_t testX3Adrop_hooksX2FhooksX00X5BconstructorX5Dhandle(int32_t arg0)
+{
+  auto result0 = test::drop_hooks::hooks::Handle::New((uint32_t(arg0)));
+  return result0.release()->get_handle();
+}
+void exports::test::drop_hooks::runner::Run()
+{
+  testX3Adrop_hooksX2FrunnerX23run();
+}
+
+// Component Adapters
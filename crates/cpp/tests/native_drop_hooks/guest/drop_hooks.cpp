@@ -0,0 +1,49 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+
+// Ensure that the *_component_type.o object is linked in
+#ifdef __wasm32__
+extern void __component_type_object_force_link_drop_hooks(void);
+void __component_type_object_force_link_drop_hooks_public_use_in_this_compilation_unit(void) {
+  __component_type_object_force_link_drop_hooks();
+}
+#endif
+#include "drop_hooks_cpp.h"
+#include <cstdlib> // realloc
+
+extern "C" void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size);
+
+__attribute__((__weak__, __export_name__("cabi_realloc")))
+void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) {
+  (void) old_size;
+  if (new_size == 0) return (void*) align;
+  void *ret = realloc(ptr, new_size);
+  if (!ret) abort();
+  return ret;
+}
+
+
+extern "C" __attribute__((import_module("test:drop-hooks/hooks")))
+__attribute__((import_name("[resource-drop]handle")))
+void testX3Adrop_hooksX2FhooksX00X5Bresource_dropX5Dhandle(int32_t);
+extern "C" __attribute__((import_module("test:drop-hooks/hooks")))
+__attribute__((import_name("[constructor]handle")))
+int32_t testX3Adrop_hooksX2FhooksX00X5BconstructorX5Dhandle(int32_t);
+test::drop_hooks::hooks::Handle::~Handle()
+{
+  if (handle>=0) {
+    testX3Adrop_hooksX2FhooksX00X5Bresource_dropX5Dhandle(handle);
+  }
+}
+test::drop_hooks::hooks::Handle::Handle(uint32_t id)
+{
+  auto ret = testX3Adrop_hooksX2FhooksX00X5BconstructorX5Dhandle((int32_t(id)));
+  this->handle = wit::ResourceImportBase{ret}.into_handle();
+}
+test::drop_hooks::hooks::Handle::Handle(wit::ResourceImportBase&&b) : wit::ResourceImportBase(std::move(b)) {}
+extern "C" __attribute__((__export_name__("test:drop-hooks/runner#run")))
+void testX3Adrop_hooksX2FrunnerX23run()
+{
+  exports::test::drop_hooks::runner::Run();
+}
+
+// Component Adapters
@@ -0,0 +1,24 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_GUEST_BINDINGS_DROP_HOOKS_H
+#define __CPP_GUEST_BINDINGS_DROP_HOOKS_H
+#include <cstdint>
+#include <utility>
+#include <cassert>
+#include <wit-guest.h>
+namespace test {namespace drop_hooks {namespace hooks {class Handle : public wit::ResourceImportBase{
+
+  public:
+
+  ~Handle();
+  Handle(uint32_t id);
+  Handle(wit::ResourceImportBase &&);
+  Handle(Handle&&) = default;
+  Handle& operator=(Handle&&) = default;
+};
+
+// export_interface Interface(Id { idx: 1 })
+}}}
+namespace exports {namespace test {namespace drop_hooks {namespace runner {void Run();
+}}}}
+
+#endif
@@ -0,0 +1,9 @@
+#include "drop_hooks_cpp.h"
+
+void exports::test::drop_hooks::runner::Run() {
+  ::test::drop_hooks::hooks::Handle first(1);
+  {
+    ::test::drop_hooks::hooks::Handle second(2);
+  }
+  auto moved = std::move(first);
+}
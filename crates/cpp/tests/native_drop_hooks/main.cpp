@@ -0,0 +1,16 @@
+#include "drop_hooks_cpp_native.h"
+#include <assert.h>
+#include <iostream>
+
+std::map<uint32_t, int> test::drop_hooks::hooks::Handle::drops;
+
+int main() {
+  using test::drop_hooks::hooks::Handle;
+  exports::test::drop_hooks::runner::Run();
+  // the moved from handle isn't dropped
+  assert(Handle::drops.size() == 2);
+  assert(Handle::drops[1] == 1);
+  assert(Handle::drops[2] == 1);
+  std::cout << "ok" << std::endl;
+  return 0;
+}
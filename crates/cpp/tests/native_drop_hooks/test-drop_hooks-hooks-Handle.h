@@ -0,0 +1,28 @@
+/* User class definition file, autogenerated once, then user modified
+ * Updated versions of this file are generated into Handle.template.
+ */
+#pragma once
+#include <cstdint>
+#include <map>
+#include <utility>
+#include <wit-host.h>
+
+namespace test {
+namespace drop_hooks {
+namespace hooks {
+class Handle : public wit::ResourceImportBase<Handle> {
+  uint32_t id;
+
+public:
+  // how often the guest dropped each id
+  static std::map<uint32_t, int> drops;
+
+  static void Dtor(Handle *self) { delete self; }
+  Handle(uint32_t id) : id(id) {}
+  static Owned New(uint32_t id) { return Owned(new Handle(id)); }
+  void on_drop() { ++drops[id]; }
+};
+
+} // namespace hooks
+} // namespace drop_hooks
+} // namespace test
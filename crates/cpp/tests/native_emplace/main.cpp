@@ -0,0 +1,38 @@
+#include <wit-host.h>
+#include <assert.h>
+#include <iostream>
+
+// a host defined resource with constructor arguments
+class Counter : public wit::ResourceImportBase<Counter> {
+  uint32_t value;
+  uint32_t step;
+
+public:
+  static void Dtor(Counter *self) { delete self; }
+  Counter(uint32_t start, uint32_t step) : value(start), step(step) {}
+  uint32_t Next() {
+    uint32_t result = value;
+    value += step;
+    return result;
+  }
+};
+
+int main() {
+  int32_t first = Counter::emplace(10, 5);
+  int32_t second = Counter::emplace(1, 1);
+  assert(first != second);
+  Counter *counter = *Counter::lookup_resource(first);
+  assert(counter->get_handle() == first);
+  assert(counter->Next() == 10);
+  assert(counter->Next() == 15);
+  assert((*Counter::lookup_resource(second))->Next() == 1);
+  // what the generated [resource-drop] does
+  for (int32_t handle : {first, second}) {
+    auto ptr = Counter::remove_resource(handle);
+    assert(ptr.has_value());
+    Counter::Dtor(*ptr);
+  }
+  assert(Counter::lookup_resource(first) == nullptr);
+  std::cout << "ok" << std::endl;
+  return 0;
+}
@@ -0,0 +1,39 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_HOST_BINDINGS_CHECK_H
+#define __CPP_HOST_BINDINGS_CHECK_H
+struct WASMExecEnv; // WAMR execution environment
+#include <cstdint>
+#include <utility>
+#include <string_view>
+namespace test {namespace check {namespace host_log {void Log(WASMExecEnv* exec_env, std::string_view msg);
+uint32_t NextId(WASMExecEnv* exec_env);
+}}}
+namespace exports {namespace check {uint32_t Run(WASMExecEnv* exec_env);
+}}
+struct ImportVTable {
+  decltype(&::test::check::host_log::Log) test_check_host_log_log;
+  decltype(&::test::check::host_log::NextId) test_check_host_log_next_id;
+  // whether every import has an implementation, `static_assert` this on constexpr tables
+  constexpr bool is_complete() const { return test_check_host_log_log != nullptr && test_check_host_log_next_id != nullptr; }
+};
+// has to be set before the guest calls any import
+extern ImportVTable const* import_vtable;
+struct WASMModuleCommon;
+struct WASMModuleInstanceCommon;
+/// An instance of the guest module, destroyed with this object,
+/// the methods call the exported functions
+class Instance {
+  WASMModuleInstanceCommon* module_inst;
+  WASMExecEnv* exec_env;
+  public:
+  Instance(WASMModuleCommon* module, uint32_t stack_size, uint32_t heap_size);
+  Instance(Instance const&) = delete;
+  Instance& operator=(Instance const&) = delete;
+  ~Instance();
+  WASMExecEnv* get_exec_env() const { return exec_env; }
+  uint32_t run();
+};
+extern "C" void register_test_check_host_log();
+extern "C" void register_check();
+
+#endif
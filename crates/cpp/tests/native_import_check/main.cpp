@@ -0,0 +1,35 @@
+#include "check_cpp_host.h"
+#include <iostream>
+
+// defined by the host bindings which need wasm-micro-runtime
+ImportVTable const *import_vtable = nullptr;
+
+static void log(WASMExecEnv *, std::string_view msg) { std::cout << msg << std::endl; }
+static uint32_t next_id(WASMExecEnv *) { return 1; }
+
+// a table missing an import fails to compile
+static constexpr ImportVTable complete = {log, next_id};
+static_assert(complete.is_complete(), "every import is implemented");
+
+// tables filled at runtime are checked before use
+static ImportVTable const *choose(bool with_next_id) {
+    static ImportVTable table;
+    table.test_check_host_log_log = log;
+    table.test_check_host_log_next_id = with_next_id ? next_id : nullptr;
+    return &table;
+}
+
+int main() {
+    ImportVTable const *partial = choose(false);
+    if (partial->is_complete()) {
+        std::cerr << "missing next-id not detected" << std::endl;
+        return 1;
+    }
+    import_vtable = choose(true);
+    if (!import_vtable->is_complete()) {
+        std::cerr << "complete table rejected" << std::endl;
+        return 1;
+    }
+    std::cout << "missing import detected" << std::endl;
+    return 0;
+}
@@ -1,6 +1,5 @@
 // Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
 #include "mesh_cpp_native.h"
-template <class R> std::map<int32_t, R> wit::ResourceTable<R>::resources;
 #include <assert.h>
 extern "C" void fooX3AfooX2FresourcesX23X5BdtorX5Dr(uint8_t *);
 extern "C" int32_t fooX3AfooX2FresourcesX23X5BconstructorX5Dr(int32_t);
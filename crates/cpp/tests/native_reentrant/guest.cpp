@@ -0,0 +1,12 @@
+#include "reentrant_cpp.h"
+#include <string>
+
+wit::string exports::test::reentrant::api::Greet(uint32_t depth) {
+    std::string result = "greet" + std::to_string(depth);
+    if (depth < 2) {
+        // the host calls back into Greet from within Notify
+        auto inner = ::test::reentrant::callback::Notify(depth);
+        result += "(" + std::string(inner.get_view()) + ")";
+    }
+    return wit::string::from_view(result);
+}
@@ -0,0 +1,44 @@
+// Emulates a host which re-enters the guest from an import and keeps
+// the inner result alive until the outer call returned.
+#include <assert.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+#include <iostream>
+#include <string>
+#include <vector>
+
+extern "C" uint8_t *testX3AreentrantX2FapiX23greet(int32_t);
+extern "C" void cabi_post_testX3AreentrantX2FapiX23greet(uint8_t *);
+
+static std::vector<uint8_t *> pending_post_return;
+
+static std::string lift_string(uint8_t *ret) {
+    return std::string(*(char const **)(ret + 0), *(size_t *)(ret + 8));
+}
+
+extern "C" void testX3AreentrantX2FcallbackX00notify(int32_t depth,
+                                                      uint8_t *ret) {
+    uint8_t *inner = testX3AreentrantX2FapiX23greet(depth + 1);
+    std::string text = lift_string(inner);
+    char *copy = (char *)malloc(text.size());
+    memcpy(copy, text.data(), text.size());
+    *(char **)(ret + 0) = copy;
+    *(size_t *)(ret + 8) = text.size();
+    // delay post-return like an owning host side handle
+    pending_post_return.push_back(inner);
+}
+
+int main() {
+    uint8_t *outer = testX3AreentrantX2FapiX23greet(0);
+    std::string result = lift_string(outer);
+    for (auto ret : pending_post_return) {
+        // the outer result must not have been written into this area
+        assert(ret != outer);
+        cabi_post_testX3AreentrantX2FapiX23greet(ret);
+    }
+    cabi_post_testX3AreentrantX2FapiX23greet(outer);
+    std::cout << result << std::endl;
+    assert(result == "greet0(greet1(greet2))");
+    return 0;
+}
@@ -0,0 +1,66 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+
+// Ensure that the *_component_type.o object is linked in
+#ifdef __wasm32__
+extern void __component_type_object_force_link_reentrant(void);
+void __component_type_object_force_link_reentrant_public_use_in_this_compilation_unit(void) {
+  __component_type_object_force_link_reentrant();
+}
+#endif
+#include "reentrant_cpp.h"
+#include <cstdlib> // realloc
+
+extern "C" void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size);
+
+__attribute__((__weak__, __export_name__("cabi_realloc")))
+void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) {
+  (void) old_size;
+  if (new_size == 0) return (void*) align;
+  void *ret = realloc(ptr, new_size);
+  if (!ret) abort();
+  return ret;
+}
+
+
+extern "C" __attribute__((import_module("test:reentrant/callback")))
+__attribute__((import_name("notify")))
+void testX3AreentrantX2FcallbackX00notify(int32_t, uint8_t*);
+static uint64_t ret_area_testX3AreentrantX2FapiX23greet[2];
+static bool ret_area_testX3AreentrantX2FapiX23greet_busy = false;
+wit::string test::reentrant::callback::Notify(uint32_t depth)
+{
+  uint64_t ret_area[2];
+  uint8_t* ptr0 = (uint8_t*)(&ret_area);
+  testX3AreentrantX2FcallbackX00notify((int32_t(depth)), ptr0);
+  auto len1 = *((size_t*) (ptr0 + 8));
+
+  return wit::string((char const*)(*((uint8_t**) (ptr0 + 0))), len1);
+}
+extern "C" __attribute__((__export_name__("test:reentrant/api#greet")))
+uint8_t* testX3AreentrantX2FapiX23greet(int32_t arg0)
+{
+  auto result0 = exports::test::reentrant::api::Greet((uint32_t(arg0)));
+  uint8_t* ptr1 = ret_area_testX3AreentrantX2FapiX23greet_busy ? (uint8_t*)(malloc(sizeof(ret_area_testX3AreentrantX2FapiX23greet))) : (uint8_t*)(&ret_area_testX3AreentrantX2FapiX23greet);
+  ret_area_testX3AreentrantX2FapiX23greet_busy = true;
+  auto const&vec2 = result0;
+  auto ptr2 = (uint8_t*)(vec2.data());
+  auto len2 = (size_t)(vec2.size());
+  result0.leak();
+
+  *((size_t*)(ptr1 + 8)) = len2;
+  *((uint8_t**)(ptr1 + 0)) = ptr2;
+  return ptr1;
+}
+extern "C" __attribute__((__weak__, __export_name__("cabi_post_testX3AreentrantX2FapiX23greet")))
+void cabi_post_testX3AreentrantX2FapiX23greet(uint8_t* arg0) {
+  if ((*((size_t*) (arg0 + 8))) > 0) {
+    wit::string::drop_raw((void*) (*((uint8_t**) (arg0 + 0))));
+  }
+  if (arg0 == (uint8_t*)(&ret_area_testX3AreentrantX2FapiX23greet)) {
+    ret_area_testX3AreentrantX2FapiX23greet_busy = false;
+  } else {
+    free(arg0);
+  }
+}
+
+// Component Adapters
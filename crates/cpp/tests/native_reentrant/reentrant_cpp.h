@@ -0,0 +1,13 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_GUEST_BINDINGS_REENTRANT_H
+#define __CPP_GUEST_BINDINGS_REENTRANT_H
+#include <cstdint>
+#include <utility>
+#include <wit-guest.h>
+namespace test {namespace reentrant {namespace callback {wit::string Notify(uint32_t depth);
+// export_interface Interface(Id { idx: 1 })
+}}}
+namespace exports {namespace test {namespace reentrant {namespace api {wit::string Greet(uint32_t depth);
+}}}}
+
+#endif
@@ -1,6 +1,5 @@
 // Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
 #include "the_world_cpp_native.h"
-template <class R> std::map<int32_t, R> wit::ResourceTable<R>::resources;
 #include <assert.h>
 extern "C" __attribute__((import_module("foo:foo/resources")))
 __attribute__((import_name("[dtor]r"))) void
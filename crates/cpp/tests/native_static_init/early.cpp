@@ -0,0 +1,4 @@
+#include <wit-common.h>
+
+// runs during static initialization, possibly before main.cpp's globals
+int32_t early_id = wit::ResourceTable<int>::store_resource(42);
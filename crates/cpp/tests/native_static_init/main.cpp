@@ -0,0 +1,17 @@
+#include <wit-common.h>
+#include <assert.h>
+#include <iostream>
+
+extern int32_t early_id;
+// another static constructor using the table
+static int32_t late_id = wit::ResourceTable<int>::store_resource(17);
+
+int main() {
+    assert(early_id != late_id);
+    assert(*wit::ResourceTable<int>::lookup_resource(early_id) == 42);
+    assert(*wit::ResourceTable<int>::lookup_resource(late_id) == 17);
+    assert(wit::ResourceTable<int>::remove_resource(early_id).value() == 42);
+    assert(wit::ResourceTable<int>::lookup_resource(early_id) == nullptr);
+    std::cout << "ok" << std::endl;
+    return 0;
+}
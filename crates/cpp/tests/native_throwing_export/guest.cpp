@@ -0,0 +1,19 @@
+#include "throwing_cpp.h"
+#include <stdexcept>
+#include <string>
+
+wit::vector<wit::string>
+exports::test::throwing::api::Names(wit::string &&text,
+                                    wit::vector<::test::throwing::api::Entry> &&entries) {
+    auto result = wit::vector<wit::string>::with_capacity(entries.size());
+    for (size_t i = 0; i < entries.size(); ++i) {
+        std::string name(entries[i].name.get_view());
+        if (name == text.get_view()) {
+            // the arguments are still owned by the export wrapper, the
+            // partial result is destroyed
+            throw std::runtime_error("unwanted " + name);
+        }
+        result.push_back_unchecked(wit::string::from_view(name));
+    }
+    return result;
+}
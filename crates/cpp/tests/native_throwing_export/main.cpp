@@ -0,0 +1,81 @@
+// Emulates a host calling an export which throws, the lifted arguments
+// and the return area have to be released while unwinding.
+#include <assert.h>
+#include <malloc.h>
+#include <stdint.h>
+#include <string.h>
+#include <iostream>
+#include <stdexcept>
+#include <string>
+
+extern "C" void *cabi_realloc(void *ptr, size_t old_size, size_t align,
+                              size_t new_size);
+extern "C" uint8_t *testX3AthrowingX2FapiX23names(uint8_t *, size_t, uint8_t *,
+                                                   size_t);
+extern "C" void cabi_post_testX3AthrowingX2FapiX23names(uint8_t *);
+
+// a string in guest memory, owned by the callee
+static uint8_t *guest_string(char const *text) {
+    size_t len = strlen(text);
+    uint8_t *ptr = (uint8_t *)cabi_realloc(nullptr, 0, 1, len);
+    memcpy(ptr, text, len);
+    return ptr;
+}
+
+static void store_string(uint8_t *base, char const *text) {
+    *(uint8_t **)(base + 0) = guest_string(text);
+    *(size_t *)(base + 8) = strlen(text);
+}
+
+// calls names(text, [{a, [x]}, {b, [y, z]}])
+static uint8_t *call_names(char const *text) {
+    uint8_t *entries = (uint8_t *)cabi_realloc(nullptr, 0, 8, 2 * 32);
+    char const *names[] = {"a", "b"};
+    size_t tag_count[] = {1, 2};
+    char const *tags[][2] = {{"x", nullptr}, {"y", "z"}};
+    for (size_t i = 0; i < 2; ++i) {
+        uint8_t *entry = entries + i * 32;
+        store_string(entry, names[i]);
+        uint8_t *list =
+            (uint8_t *)cabi_realloc(nullptr, 0, 8, tag_count[i] * 16);
+        for (size_t j = 0; j < tag_count[i]; ++j) {
+            store_string(list + j * 16, tags[i][j]);
+        }
+        *(uint8_t **)(entry + 16) = list;
+        *(size_t *)(entry + 24) = tag_count[i];
+    }
+    return testX3AthrowingX2FapiX23names(guest_string(text), strlen(text),
+                                         entries, 2);
+}
+
+static size_t in_use() { return mallinfo2().uordblks; }
+
+static bool throws(char const *text) {
+    try {
+        call_names(text);
+    } catch (std::runtime_error const &e) {
+        return std::string(e.what()) == std::string("unwanted ") + text;
+    }
+    return false;
+}
+
+int main() {
+    uint8_t *first = call_names("none");
+    assert(*(size_t *)(first + 8) == 2);
+    cabi_post_testX3AthrowingX2FapiX23names(first);
+
+    // the first throw allocates the exception handling state
+    assert(throws("a"));
+    size_t before = in_use();
+    assert(throws("b"));
+    // every argument was freed by the export wrapper
+    assert(in_use() == before);
+
+    // the return area isn't left busy
+    uint8_t *second = call_names("none");
+    assert(second == first);
+    cabi_post_testX3AthrowingX2FapiX23names(second);
+    assert(in_use() == before);
+    std::cout << "ok" << std::endl;
+    return 0;
+}
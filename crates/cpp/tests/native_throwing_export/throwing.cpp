@@ -0,0 +1,110 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+
+// Ensure that the *_component_type.o object is linked in
+#ifdef __wasm32__
+extern void __component_type_object_force_link_throwing(void);
+void __component_type_object_force_link_throwing_public_use_in_this_compilation_unit(void) {
+  __component_type_object_force_link_throwing();
+}
+#endif
+#include "throwing_cpp.h"
+#include <cstdlib> // realloc
+
+extern "C" void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size);
+
+__attribute__((__weak__, __export_name__("cabi_realloc")))
+void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) {
+  (void) old_size;
+  if (new_size == 0) return (void*) align;
+  void *ret = realloc(ptr, new_size);
+  if (!ret) abort();
+  return ret;
+}
+
+
+static uint64_t ret_area_testX3AthrowingX2FapiX23names[2];
+static bool ret_area_testX3AthrowingX2FapiX23names_busy = false;
+extern "C" __attribute__((__export_name__("test:throwing/api#names")))
+uint8_t* testX3AthrowingX2FapiX23names(uint8_t* arg0, size_t arg1, uint8_t* arg2, size_t arg3)
+{
+  auto len0 = arg1;
+
+  auto base4 = arg2;
+  auto len4 = arg3;
+  auto result4 = wit::vector<test::throwing::api::Entry>::allocate(len4);
+  for (size_t i = 0; i < len4; ++i) {
+    auto base = base4 + i * 32;
+    auto len1 = *((size_t*) (base + 8));
+
+    auto base3 = *((uint8_t**) (base + 16));
+    auto len3 = *((size_t*) (base + 24));
+    auto result3 = wit::vector<wit::string>::allocate(len3);
+    for (size_t i = 0; i < len3; ++i) {
+      auto base = base3 + i * 16;
+      auto len2 = *((size_t*) (base + 8));
+
+      result3.initialize(i, std::move(wit::string((char const*)(*((uint8_t**) (base + 0))), len2)));
+    }
+    if (len3 > 0) {
+      free((void*) (base3));
+    }
+    result4.initialize(i, std::move(test::throwing::api::Entry{std::move(wit::string((char const*)(*((uint8_t**) (base + 0))), len1)), std::move(std::move(result3))}));
+  }
+  if (len4 > 0) {
+    free((void*) (base4));
+  }
+  auto result5 = exports::test::throwing::api::Names(wit::string((char const*)(arg0), len0), std::move(result4));
+  uint8_t* ptr6 = ret_area_testX3AthrowingX2FapiX23names_busy ? (uint8_t*)(malloc(sizeof(ret_area_testX3AthrowingX2FapiX23names))) : (uint8_t*)(&ret_area_testX3AthrowingX2FapiX23names);
+  if (!ptr6) WIT_TRAP();
+  ret_area_testX3AthrowingX2FapiX23names_busy = true;
+  wit::scope_guard guard6([ptr6] {
+    if (ptr6 == (uint8_t*)(&ret_area_testX3AthrowingX2FapiX23names)) {
+      ret_area_testX3AthrowingX2FapiX23names_busy = false;
+    } else {
+      free(ptr6);
+    }
+  });
+  auto&& vec8 = result5;
+  auto len8 = (size_t)(vec8.size());
+  uint8_t* ptr8 = (uint8_t*)(len8 > 0 ? malloc(len8 * 16) : nullptr);
+  for (size_t i = 0; i < len8; ++i) {
+    uint8_t* base = (uint8_t*)ptr8 + i * 16;
+    auto&& iter_elem = vec8[i];
+    auto const&vec7 = iter_elem;
+    auto ptr7 = (uint8_t*)(vec7.data());
+    auto len7 = (size_t)(vec7.size());
+    if (len7 == 0) ptr7 = nullptr;
+    iter_elem.leak();
+
+    *((size_t*)(base + 8)) = len7;
+    *((uint8_t**)(base + 0)) = ptr7;
+  }
+  *((size_t*)(ptr6 + 8)) = len8;
+  *((uint8_t**)(ptr6 + 0)) = ptr8;
+  guard6.dismiss();
+  return ptr6;
+}
+extern "C" __attribute__((__weak__, __export_name__("cabi_post_testX3AthrowingX2FapiX23names")))
+void cabi_post_testX3AthrowingX2FapiX23names(uint8_t* arg0) {
+  wit::scope_guard release([arg0] {
+    if (arg0 == (uint8_t*)(&ret_area_testX3AthrowingX2FapiX23names)) {
+      ret_area_testX3AthrowingX2FapiX23names_busy = false;
+    } else {
+      free(arg0);
+    }
+  });
+  uint8_t* ptr0 = *((uint8_t**) (arg0 + 0));
+  size_t len0 = *((size_t*) (arg0 + 8));
+  for (size_t i0 = 0; i0 < len0; i0++) {
+    uint8_t* base = ptr0 + i0 * 16;
+    (void) base;
+    if ((*((size_t*) (base + 8))) > 0) {
+      wit::string::drop_raw((void*) (*((uint8_t**) (base + 0))));
+    }
+  }
+  if (len0 > 0) {
+    free((void*) (ptr0));
+  }
+}
+
+// Component Adapters
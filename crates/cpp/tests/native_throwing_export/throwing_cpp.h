@@ -0,0 +1,19 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_GUEST_BINDINGS_THROWING_H
+#define __CPP_GUEST_BINDINGS_THROWING_H
+#include <cstdint>
+#include <utility>
+#include <wit-guest.h>
+#include <type_traits>
+static_assert(std::is_same<wit::length_type, size_t>::value, "bindings generated with a different --length-type");
+// export_interface Interface(Id { idx: 0 })
+namespace test {namespace throwing {namespace api {struct Entry {
+  wit::string name;
+  wit::vector<wit::string> tags;
+};
+static_assert(std::is_nothrow_move_constructible<Entry>::value);
+}}}
+namespace exports {namespace test {namespace throwing {namespace api {wit::vector<wit::string> Names(wit::string && text, wit::vector<::test::throwing::api::Entry>&& entries);
+}}}}
+
+#endif
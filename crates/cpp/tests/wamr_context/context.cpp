@@ -0,0 +1,51 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+
+// Ensure that the *_component_type.o object is linked in
+#ifdef __wasm32__
+extern void __component_type_object_force_link_context(void);
+void __component_type_object_force_link_context_public_use_in_this_compilation_unit(void) {
+  __component_type_object_force_link_context();
+}
+#endif
+#include "context_cpp.h"
+#include <cstdlib> // realloc
+
+extern "C" void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size);
+
+__attribute__((__weak__, __export_name__("cabi_realloc")))
+void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) {
+  (void) old_size;
+  if (new_size == 0) return (void*) align;
+  void *ret = realloc(ptr, new_size);
+  if (!ret) abort();
+  return ret;
+}
+
+
+extern "C" __attribute__((import_module("test:context/host-log")))
+__attribute__((import_name("log")))
+void testX3AcontextX2Fhost_logX00log(uint8_t*, size_t);
+extern "C" __attribute__((import_module("test:context/host-log")))
+__attribute__((import_name("next-id")))
+int32_t testX3AcontextX2Fhost_logX00next_id();
+void test::context::host_log::Log(std::string_view msg)
+{
+  auto const&vec0 = msg;
+  auto ptr0 = (uint8_t*)(vec0.data());
+  auto len0 = (size_t)(vec0.size());
+  if (len0 == 0) ptr0 = nullptr;
+  testX3AcontextX2Fhost_logX00log(ptr0, len0);
+}
+uint32_t test::context::host_log::NextId()
+{
+  auto ret = testX3AcontextX2Fhost_logX00next_id();
+  return (uint32_t(ret));
+}
+extern "C" __attribute__((__export_name__("test:context/guest#run")))
+int32_t testX3AcontextX2FguestX23run()
+{
+  auto result0 = exports::test::context::guest::Run();
+  return (int32_t(result0));
+}
+
+// Component Adapters
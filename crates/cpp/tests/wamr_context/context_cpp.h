@@ -0,0 +1,14 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_GUEST_BINDINGS_CONTEXT_H
+#define __CPP_GUEST_BINDINGS_CONTEXT_H
+#include <cstdint>
+#include <utility>
+#include <string_view>
+namespace test {namespace context {namespace host_log {void Log(std::string_view msg);
+uint32_t NextId();
+// export_interface Interface(Id { idx: 1 })
+}}}
+namespace exports {namespace test {namespace context {namespace guest {uint32_t Run();
+}}}}
+
+#endif
@@ -0,0 +1,58 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_HOST_BINDINGS_CONTEXT_H
+#define __CPP_HOST_BINDINGS_CONTEXT_H
+struct WASMExecEnv; // WAMR execution environment
+#include <cstdint>
+#include <utility>
+#include <string_view>
+#include <functional>
+#include <type_traits>
+namespace test {namespace context {namespace host_log {void Log(WASMExecEnv* exec_env, std::string_view msg);
+uint32_t NextId(WASMExecEnv* exec_env);
+// export_interface Interface(Id { idx: 1 })
+}}}
+namespace exports {namespace test {namespace context {namespace guest {uint32_t Run(WASMExecEnv* exec_env);
+}}}}
+/// The implementations of the imports, used by the guest running
+/// on the execution environment this is attached to
+struct ImportContext {
+  std::function<std::remove_pointer_t<decltype(&::test::context::host_log::Log)>> test_context_host_log_log;
+  std::function<std::remove_pointer_t<decltype(&::test::context::host_log::NextId)>> test_context_host_log_next_id;
+  // whether every import has an implementation
+  bool is_complete() const { return test_context_host_log_log != nullptr && test_context_host_log_next_id != nullptr; }
+};
+struct WASMModuleCommon;
+struct WASMModuleInstanceCommon;
+/// An instance of the guest module, destroyed with this object,
+/// the methods call the exported functions
+class Instance {
+  WASMModuleInstanceCommon* module_inst;
+  WASMExecEnv* exec_env;
+  char error_buf[128];
+  public:
+  /// Throws `std::runtime_error` with the message of WAMR if the
+  /// module can't be instantiated, without exceptions check `has_value()`
+  Instance(WASMModuleCommon* module, uint32_t stack_size, uint32_t heap_size);
+  Instance(Instance const&) = delete;
+  Instance& operator=(Instance const&) = delete;
+  ~Instance();
+  bool has_value() const { return exec_env != nullptr; }
+  // why instantiating failed
+  char const* error() const { return error_buf; }
+  WASMExecEnv* get_exec_env() const { return exec_env; }
+  // the imports called by this instance, has to outlive it
+  void set_import_context(ImportContext* context);
+  uint32_t guest_run();
+  /// The exports of `test:context/guest`
+  class TestContextGuest {
+    WASMExecEnv* exec_env;
+    public:
+    explicit TestContextGuest(WASMExecEnv* exec_env) : exec_env(exec_env) {}
+    uint32_t run();
+  };
+  TestContextGuest test_context_guest() const { return TestContextGuest(exec_env); }
+};
+extern "C" void register_test_context_host_log();
+extern "C" void register_context();
+
+#endif
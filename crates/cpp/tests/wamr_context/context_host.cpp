@@ -0,0 +1,78 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#include "context_cpp_host.h"
+#include <wasm_export.h> // wasm-micro-runtime header
+#include <wasm_c_api.h>
+#include <assert.h>
+#include <stdexcept>
+#include <string.h>
+
+static void testX3AcontextX2Fhost_logX00log([[maybe_unused]] wasm_exec_env_t exec_env, int32_t arg0, size_t arg1)
+{
+  auto len0 = arg1;
+
+  char const* ptr0 = (char const*)wasm_runtime_addr_app_to_native(wasm_runtime_get_module_inst(exec_env), arg0);
+
+  static_cast<ImportContext*>(wasm_runtime_get_user_data(exec_env))->test_context_host_log_log(exec_env, std::string_view(ptr0, len0));
+}
+static int32_t testX3AcontextX2Fhost_logX00next_id([[maybe_unused]] wasm_exec_env_t exec_env)
+{
+  auto result0 = static_cast<ImportContext*>(wasm_runtime_get_user_data(exec_env))->test_context_host_log_next_id(exec_env);
+  return (int32_t(result0));
+}
+uint32_t exports::test::context::guest::Run(wasm_exec_env_t exec_env)
+{
+  wasm_function_inst_t wasm_func = wasm_runtime_lookup_function(wasm_runtime_get_module_inst(exec_env), 
+  "test:context/guest#run", "()i");
+  wasm_val_t wasm_results[1] = { WASM_INIT_VAL };
+  wasm_val_t *wasm_args = nullptr;
+  bool wasm_ok = wasm_runtime_call_wasm_a(exec_env, wasm_func, 1, wasm_results, 0, wasm_args);
+  assert(wasm_ok);
+  assert(wasm_results[0].kind==WASM_I32);
+  auto ret = wasm_results[0].of.i32;
+  return (uint32_t(ret));
+}
+
+// Component Adapters
+Instance::Instance(wasm_module_t module, uint32_t stack_size, uint32_t heap_size)
+: module_inst(nullptr), exec_env(nullptr) {
+  error_buf[0] = 0;
+  module_inst = wasm_runtime_instantiate(module, stack_size, heap_size, error_buf, sizeof(error_buf));
+  if (module_inst) {
+    exec_env = wasm_runtime_create_exec_env(module_inst, stack_size);
+    if (!exec_env) {
+      strncpy(error_buf, "failed to create the execution environment", sizeof(error_buf) - 1);
+      wasm_runtime_deinstantiate(module_inst);
+      module_inst = nullptr;
+    }
+  }
+  #if __cpp_exceptions
+  if (!exec_env) {
+    throw std::runtime_error(error_buf);
+  }
+  #endif
+}
+Instance::~Instance() {
+  if (exec_env) {
+    wasm_runtime_destroy_exec_env(exec_env);
+    wasm_runtime_deinstantiate(module_inst);
+  }
+}
+void Instance::set_import_context(ImportContext* context) {
+  wasm_runtime_set_user_data(exec_env, context);
+}
+uint32_t Instance::guest_run() {
+  return ::exports::test::context::guest::Run(exec_env);
+}
+uint32_t Instance::TestContextGuest::run() {
+  return ::exports::test::context::guest::Run(exec_env);
+}
+void register_test_context_host_log() {
+    static NativeSymbol test_context_host_log_funs[] = {
+        { "log", (void*)testX3AcontextX2Fhost_logX00log, "($~)", nullptr },
+        { "next-id", (void*)testX3AcontextX2Fhost_logX00next_id, "()i", nullptr },
+    };
+    wasm_runtime_register_natives("test:context/host-log", test_context_host_log_funs, sizeof(test_context_host_log_funs)/sizeof(NativeSymbol));
+}
+void register_context() {
+    register_test_context_host_log();
+}
@@ -0,0 +1,8 @@
+#include "context_cpp.h"
+
+uint32_t exports::test::context::guest::Run() {
+    ::test::context::host_log::Log("hello");
+    uint32_t a = ::test::context::host_log::NextId();
+    uint32_t b = ::test::context::host_log::NextId();
+    return a * 100 + b;
+}
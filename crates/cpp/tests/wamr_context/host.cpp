@@ -0,0 +1,69 @@
+#include "context_cpp_host.h"
+#include "bh_read_file.h"
+#include <wasm_export.h>
+#include <assert.h>
+#include <iostream>
+#include <vector>
+
+int main() {
+    static char global_heap_buf[512 * 1024];
+    char error_buf[128];
+    uint32_t const stack_size = 65536, heap_size = 2 * stack_size;
+    uint32_t buf_size;
+
+    RuntimeInitArgs init_args = {};
+    init_args.mem_alloc_type = Alloc_With_Pool;
+    init_args.mem_alloc_option.pool.heap_buf = global_heap_buf;
+    init_args.mem_alloc_option.pool.heap_size = sizeof(global_heap_buf);
+    init_args.running_mode = Mode_Interp;
+    if (!wasm_runtime_full_init(&init_args)) {
+        return 1;
+    }
+    register_context();
+
+    char *buffer = bh_read_file_to_buffer("guest.wasm", &buf_size);
+    wasm_module_t module = wasm_runtime_load((uint8_t *)buffer, buf_size,
+                                             error_buf, sizeof(error_buf));
+    assert(module);
+    {
+        // two instances with their own state, captured by the lambdas
+        std::vector<std::string> logged;
+        uint32_t first_id = 0, second_id = 10;
+        ImportContext first;
+        first.test_context_host_log_log = [&](WASMExecEnv *,
+                                              std::string_view msg) {
+            logged.push_back("first: " + std::string(msg));
+        };
+        first.test_context_host_log_next_id = [&](WASMExecEnv *) {
+            return ++first_id;
+        };
+        ImportContext second;
+        second.test_context_host_log_log = [&](WASMExecEnv *,
+                                               std::string_view msg) {
+            logged.push_back("second: " + std::string(msg));
+        };
+        second.test_context_host_log_next_id = [&](WASMExecEnv *) {
+            return ++second_id;
+        };
+        assert(first.is_complete() && second.is_complete());
+
+        Instance a(module, stack_size, heap_size);
+        Instance b(module, stack_size, heap_size);
+        a.set_import_context(&first);
+        b.set_import_context(&second);
+        assert(a.guest_run() == 102);
+        assert(b.guest_run() == 1112);
+        assert(a.guest_run() == 304);
+        assert(logged.size() == 3);
+        assert(logged[0] == "first: hello");
+        assert(logged[1] == "second: hello");
+        for (auto const &line : logged) {
+            std::cout << line << std::endl;
+        }
+    }
+
+    wasm_runtime_unload(module);
+    wasm_runtime_free(buffer);
+    wasm_runtime_destroy();
+    return 0;
+}
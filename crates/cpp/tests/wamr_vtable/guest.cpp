@@ -0,0 +1,10 @@
+#include "vtable_cpp.h"
+
+uint32_t exports::test::vtable::guest::Run() {
+    ::test::vtable::host_log::Log("first");
+    // the host swaps its import table while handling the first log
+    uint32_t a = ::test::vtable::host_log::NextId();
+    ::test::vtable::host_log::Log("second");
+    uint32_t b = ::test::vtable::host_log::NextId();
+    return a * 100 + b;
+}
@@ -0,0 +1,60 @@
+#include "vtable_cpp_host.h"
+#include "bh_read_file.h"
+#include <wasm_export.h>
+#include <assert.h>
+#include <iostream>
+
+static void first_log(WASMExecEnv *exec_env, std::string_view msg);
+static void second_log(WASMExecEnv *exec_env, std::string_view msg);
+static uint32_t first_next_id(WASMExecEnv *) { return 1; }
+static uint32_t second_next_id(WASMExecEnv *) { return 2; }
+
+static constexpr ImportVTable first = {first_log, first_next_id};
+static constexpr ImportVTable second = {second_log, second_next_id};
+static_assert(first.is_complete() && second.is_complete());
+
+static void first_log(WASMExecEnv *, std::string_view msg) {
+    std::cout << "first table: " << msg << std::endl;
+    // hot-swap the implementation while the guest is running
+    import_vtable = &second;
+}
+
+static void second_log(WASMExecEnv *, std::string_view msg) {
+    std::cout << "second table: " << msg << std::endl;
+}
+
+int main() {
+    static char global_heap_buf[512 * 1024];
+    char error_buf[128];
+    uint32_t const stack_size = 65536, heap_size = 2 * stack_size;
+    uint32_t buf_size;
+
+    RuntimeInitArgs init_args = {};
+    init_args.mem_alloc_type = Alloc_With_Pool;
+    init_args.mem_alloc_option.pool.heap_buf = global_heap_buf;
+    init_args.mem_alloc_option.pool.heap_size = sizeof(global_heap_buf);
+    init_args.running_mode = Mode_Interp;
+    if (!wasm_runtime_full_init(&init_args)) {
+        return 1;
+    }
+    register_vtable();
+
+    char *buffer = bh_read_file_to_buffer("guest.wasm", &buf_size);
+    wasm_module_t module = wasm_runtime_load((uint8_t *)buffer, buf_size,
+                                             error_buf, sizeof(error_buf));
+    assert(module);
+    {
+        Instance instance(module, stack_size, heap_size);
+
+        import_vtable = &first;
+        uint32_t result = instance.guest_run();
+        assert(result == 102);
+        assert(import_vtable == &second);
+        std::cout << "result " << result << std::endl;
+    }
+
+    wasm_runtime_unload(module);
+    wasm_runtime_free(buffer);
+    wasm_runtime_destroy();
+    return 0;
+}
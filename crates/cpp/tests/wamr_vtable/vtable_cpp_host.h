@@ -0,0 +1,54 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_HOST_BINDINGS_VTABLE_H
+#define __CPP_HOST_BINDINGS_VTABLE_H
+struct WASMExecEnv; // WAMR execution environment
+#include <cstdint>
+#include <utility>
+#include <string_view>
+namespace test {namespace vtable {namespace host_log {void Log(WASMExecEnv* exec_env, std::string_view msg);
+uint32_t NextId(WASMExecEnv* exec_env);
+// export_interface Interface(Id { idx: 1 })
+}}}
+namespace exports {namespace test {namespace vtable {namespace guest {uint32_t Run(WASMExecEnv* exec_env);
+}}}}
+struct ImportVTable {
+  decltype(&::test::vtable::host_log::Log) test_vtable_host_log_log;
+  decltype(&::test::vtable::host_log::NextId) test_vtable_host_log_next_id;
+  // whether every import has an implementation, `static_assert` this on constexpr tables
+  constexpr bool is_complete() const { return test_vtable_host_log_log != nullptr && test_vtable_host_log_next_id != nullptr; }
+};
+// has to be set before the guest calls any import
+extern ImportVTable const* import_vtable;
+struct WASMModuleCommon;
+struct WASMModuleInstanceCommon;
+/// An instance of the guest module, destroyed with this object,
+/// the methods call the exported functions
+class Instance {
+  WASMModuleInstanceCommon* module_inst;
+  WASMExecEnv* exec_env;
+  char error_buf[128];
+  public:
+  /// Throws `std::runtime_error` with the message of WAMR if the
+  /// module can't be instantiated, without exceptions check `has_value()`
+  Instance(WASMModuleCommon* module, uint32_t stack_size, uint32_t heap_size);
+  Instance(Instance const&) = delete;
+  Instance& operator=(Instance const&) = delete;
+  ~Instance();
+  bool has_value() const { return exec_env != nullptr; }
+  // why instantiating failed
+  char const* error() const { return error_buf; }
+  WASMExecEnv* get_exec_env() const { return exec_env; }
+  uint32_t guest_run();
+  /// The exports of `test:vtable/guest`
+  class TestVtableGuest {
+    WASMExecEnv* exec_env;
+    public:
+    explicit TestVtableGuest(WASMExecEnv* exec_env) : exec_env(exec_env) {}
+    uint32_t run();
+  };
+  TestVtableGuest test_vtable_guest() const { return TestVtableGuest(exec_env); }
+};
+extern "C" void register_test_vtable_host_log();
+extern "C" void register_vtable();
+
+#endif
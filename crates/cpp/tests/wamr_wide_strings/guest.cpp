@@ -0,0 +1,5 @@
+#include "wide_cpp.h"
+
+wit::string exports::test::wide::guest::Relay(wit::string &&s) {
+    return ::test::wide::host_text::Echo(s.get_view());
+}
@@ -0,0 +1,48 @@
+#include "wide_cpp_host.h"
+#include "bh_read_file.h"
+#include <wasm_export.h>
+#include <assert.h>
+#include <iostream>
+
+// emoji outside of the BMP, surrogate pairs where wchar_t has 16 bits
+static std::wstring const unicode = L"🚀🚀🚀 𠈄𓀀";
+
+std::wstring test::wide::host_text::Echo(WASMExecEnv *, std::wstring s) {
+    assert(s == unicode);
+    return s + L" ✓";
+}
+
+int main() {
+    static char global_heap_buf[512 * 1024];
+    char error_buf[128];
+    uint32_t const stack_size = 65536, heap_size = 2 * stack_size;
+    uint32_t buf_size;
+
+    RuntimeInitArgs init_args = {};
+    init_args.mem_alloc_type = Alloc_With_Pool;
+    init_args.mem_alloc_option.pool.heap_buf = global_heap_buf;
+    init_args.mem_alloc_option.pool.heap_size = sizeof(global_heap_buf);
+    init_args.running_mode = Mode_Interp;
+    if (!wasm_runtime_full_init(&init_args)) {
+        return 1;
+    }
+    register_wide();
+
+    char *buffer = bh_read_file_to_buffer("guest.wasm", &buf_size);
+    wasm_module_t module = wasm_runtime_load((uint8_t *)buffer, buf_size,
+                                             error_buf, sizeof(error_buf));
+    assert(module);
+    {
+        Instance instance(module, stack_size, heap_size);
+        // through the export into the guest and the import back to the host
+        std::wstring result = instance.guest_relay(unicode);
+        assert(result == unicode + L" ✓");
+        assert(instance.test_wide_guest().relay(L"") == L" ✓");
+        std::cout << wit::to_utf8(result) << std::endl;
+    }
+
+    wasm_runtime_unload(module);
+    wasm_runtime_free(buffer);
+    wasm_runtime_destroy();
+    return 0;
+}
@@ -0,0 +1,84 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+
+// Ensure that the *_component_type.o object is linked in
+#ifdef __wasm32__
+extern void __component_type_object_force_link_wide(void);
+void __component_type_object_force_link_wide_public_use_in_this_compilation_unit(void) {
+  __component_type_object_force_link_wide();
+}
+#endif
+#include "wide_cpp.h"
+#include <cstdlib> // realloc
+
+extern "C" void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size);
+
+__attribute__((__weak__, __export_name__("cabi_realloc")))
+void *cabi_realloc(void *ptr, size_t old_size, size_t align, size_t new_size) {
+  (void) old_size;
+  if (new_size == 0) return (void*) align;
+  void *ret = realloc(ptr, new_size);
+  if (!ret) abort();
+  return ret;
+}
+
+
+extern "C" __attribute__((import_module("test:wide/host-text")))
+__attribute__((import_name("echo")))
+void testX3AwideX2Fhost_textX00echo(uint8_t*, size_t, uint8_t*);
+static uint32_t ret_area_testX3AwideX2FguestX23relay[2];
+static bool ret_area_testX3AwideX2FguestX23relay_busy = false;
+wit::string test::wide::host_text::Echo(std::string_view s)
+{
+  auto const&vec0 = s;
+  auto ptr0 = (uint8_t*)(vec0.data());
+  auto len0 = (size_t)(vec0.size());
+  if (len0 == 0) ptr0 = nullptr;
+  uint32_t ret_area[2];
+  uint8_t* ptr1 = (uint8_t*)(&ret_area);
+  testX3AwideX2Fhost_textX00echo(ptr0, len0, ptr1);
+  auto len2 = *((size_t*) (ptr1 + 4));
+
+  return wit::string((char const*)(*((uint8_t**) (ptr1 + 0))), len2);
+}
+extern "C" __attribute__((__export_name__("test:wide/guest#relay")))
+uint8_t* testX3AwideX2FguestX23relay(uint8_t* arg0, size_t arg1)
+{
+  auto len0 = arg1;
+
+  auto result1 = exports::test::wide::guest::Relay(wit::string((char const*)(arg0), len0));
+  uint8_t* ptr2 = ret_area_testX3AwideX2FguestX23relay_busy ? (uint8_t*)(malloc(sizeof(ret_area_testX3AwideX2FguestX23relay))) : (uint8_t*)(&ret_area_testX3AwideX2FguestX23relay);
+  if (!ptr2) WIT_TRAP();
+  ret_area_testX3AwideX2FguestX23relay_busy = true;
+  wit::scope_guard guard2([ptr2] {
+    if (ptr2 == (uint8_t*)(&ret_area_testX3AwideX2FguestX23relay)) {
+      ret_area_testX3AwideX2FguestX23relay_busy = false;
+    } else {
+      free(ptr2);
+    }
+  });
+  auto const&vec3 = result1;
+  auto ptr3 = (uint8_t*)(vec3.data());
+  auto len3 = (size_t)(vec3.size());
+  if (len3 == 0) ptr3 = nullptr;
+  result1.leak();
+
+  *((size_t*)(ptr2 + 4)) = len3;
+  *((uint8_t**)(ptr2 + 0)) = ptr3;
+  guard2.dismiss();
+  return ptr2;
+}
+extern "C" __attribute__((__weak__, __export_name__("cabi_post_testX3AwideX2FguestX23relay")))
+void cabi_post_testX3AwideX2FguestX23relay(uint8_t* arg0) {
+  wit::scope_guard release([arg0] {
+    if (arg0 == (uint8_t*)(&ret_area_testX3AwideX2FguestX23relay)) {
+      ret_area_testX3AwideX2FguestX23relay_busy = false;
+    } else {
+      free(arg0);
+    }
+  });
+  if ((*((size_t*) (arg0 + 4))) > 0) {
+    wit::string::drop_raw((void*) (*((uint8_t**) (arg0 + 0))));
+  }
+}
+
+// Component Adapters
@@ -0,0 +1,15 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_GUEST_BINDINGS_WIDE_H
+#define __CPP_GUEST_BINDINGS_WIDE_H
+#include <cstdint>
+#include <utility>
+#include <string_view>
+#include <wit-guest.h>
+static_assert(std::is_same<wit::length_type, size_t>::value, "bindings generated with a different --length-type");
+namespace test {namespace wide {namespace host_text {wit::string Echo(std::string_view s);
+// export_interface Interface(Id { idx: 1 })
+}}}
+namespace exports {namespace test {namespace wide {namespace guest {wit::string Relay(wit::string && s);
+}}}}
+
+#endif
@@ -0,0 +1,48 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#ifndef __CPP_HOST_BINDINGS_WIDE_H
+#define __CPP_HOST_BINDINGS_WIDE_H
+struct WASMExecEnv; // WAMR execution environment
+#include <cstdint>
+#include <utility>
+#include <string>
+#include <string_view>
+#include <wit-host.h>
+static_assert(std::is_same<wit::length_type, size_t>::value, "bindings generated with a different --length-type");
+namespace test {namespace wide {namespace host_text {std::wstring Echo(WASMExecEnv* exec_env, std::wstring s);
+// export_interface Interface(Id { idx: 1 })
+}}}
+namespace exports {namespace test {namespace wide {namespace guest {wit::guest_owned<std::string_view> Relay(WASMExecEnv* exec_env, wit::string s);
+}}}}
+struct WASMModuleCommon;
+struct WASMModuleInstanceCommon;
+/// An instance of the guest module, destroyed with this object,
+/// the methods call the exported functions
+class Instance {
+  WASMModuleInstanceCommon* module_inst;
+  WASMExecEnv* exec_env;
+  char error_buf[128];
+  public:
+  /// Throws `std::runtime_error` with the message of WAMR if the
+  /// module can't be instantiated, without exceptions check `has_value()`
+  Instance(WASMModuleCommon* module, uint32_t stack_size, uint32_t heap_size);
+  Instance(Instance const&) = delete;
+  Instance& operator=(Instance const&) = delete;
+  ~Instance();
+  bool has_value() const { return exec_env != nullptr; }
+  // why instantiating failed
+  char const* error() const { return error_buf; }
+  WASMExecEnv* get_exec_env() const { return exec_env; }
+  std::wstring guest_relay(std::wstring s);
+  /// The exports of `test:wide/guest`
+  class TestWideGuest {
+    WASMExecEnv* exec_env;
+    public:
+    explicit TestWideGuest(WASMExecEnv* exec_env) : exec_env(exec_env) {}
+    std::wstring relay(std::wstring s);
+  };
+  TestWideGuest test_wide_guest() const { return TestWideGuest(exec_env); }
+};
+extern "C" void register_test_wide_host_text();
+extern "C" void register_wide();
+
+#endif
@@ -0,0 +1,81 @@
+// Generated by `wit-bindgen` 0.3.0. DO NOT EDIT!
+#include "wide_cpp_host.h"
+#include <wasm_export.h> // wasm-micro-runtime header
+#include <wasm_c_api.h>
+#include <assert.h>
+#include <stdexcept>
+#include <string.h>
+
+static void testX3AwideX2Fhost_textX00echo([[maybe_unused]] wasm_exec_env_t exec_env, int32_t arg0, size_t arg1, int32_t arg2, int32_t resultptr)
+{
+  auto len0 = arg1;
+
+  char const* ptr0 = (char const*)wasm_runtime_addr_app_to_native(wasm_runtime_get_module_inst(exec_env), arg0);
+
+  auto result1 = wit::string::from_view(exec_env, wit::to_utf8(test::wide::host_text::Echo(exec_env, wit::to_wstring(std::string_view(ptr0, len0)))));
+  auto const&vec2 = result1;
+  auto ptr2 = vec2.data();
+  auto len2 = vec2.size();
+  *((size_t*)wasm_runtime_addr_app_to_native(wasm_runtime_get_module_inst(exec_env), (arg2 + 4))) = len2;
+  *((int32_t*)wasm_runtime_addr_app_to_native(wasm_runtime_get_module_inst(exec_env), (arg2 + 0))) = ptr2;
+}
+wit::guest_owned<std::string_view> exports::test::wide::guest::Relay(wasm_exec_env_t exec_env, wit::string s)
+{
+  auto const&vec0 = s;
+  auto ptr0 = vec0.data();
+  auto len0 = vec0.size();
+  wasm_function_inst_t wasm_func = wasm_runtime_lookup_function(wasm_runtime_get_module_inst(exec_env), 
+  "test:wide/guest#relay", "($~*)");
+  wasm_val_t wasm_results[1] = { WASM_INIT_VAL };
+  wasm_val_t wasm_args[2] = {WASM_I32_VAL((int32_t)ptr0),WASM_I32_VAL((int32_t)len0),};
+  bool wasm_ok = wasm_runtime_call_wasm_a(exec_env, wasm_func, 1, wasm_results, 2, wasm_args);
+  assert(wasm_ok);
+  assert(wasm_results[0].kind==WASM_I32);
+  auto ret = wasm_results[0].of.i32;
+  auto len1 = *((size_t*) wasm_runtime_addr_app_to_native(wasm_runtime_get_module_inst(exec_env), (ret + 4)));
+
+  char const* ptr1 = (char const*)wasm_runtime_addr_app_to_native(wasm_runtime_get_module_inst(exec_env), *((int32_t*) wasm_runtime_addr_app_to_native(wasm_runtime_get_module_inst(exec_env), (ret + 0))));
+
+  return wit::guest_owned<std::string_view>(std::string_view(ptr1, len1), wasm_results[0].of.i32, wasm_runtime_lookup_function(wasm_runtime_get_module_inst(exec_env), "cabi_post_testX3AwideX2FguestX23relay", "(i)"), exec_env);
+}
+
+// Component Adapters
+Instance::Instance(wasm_module_t module, uint32_t stack_size, uint32_t heap_size)
+: module_inst(nullptr), exec_env(nullptr) {
+  error_buf[0] = 0;
+  module_inst = wasm_runtime_instantiate(module, stack_size, heap_size, error_buf, sizeof(error_buf));
+  if (module_inst) {
+    exec_env = wasm_runtime_create_exec_env(module_inst, stack_size);
+    if (!exec_env) {
+      strncpy(error_buf, "failed to create the execution environment", sizeof(error_buf) - 1);
+      wasm_runtime_deinstantiate(module_inst);
+      module_inst = nullptr;
+    }
+  }
+  #if __cpp_exceptions
+  if (!exec_env) {
+    throw std::runtime_error(error_buf);
+  }
+  #endif
+}
+Instance::~Instance() {
+  if (exec_env) {
+    wasm_runtime_destroy_exec_env(exec_env);
+    wasm_runtime_deinstantiate(module_inst);
+  }
+}
+std::wstring Instance::guest_relay(std::wstring s) {
+  return wit::to_wstring(::exports::test::wide::guest::Relay(exec_env, wit::string::from_view(exec_env, wit::to_utf8(s))));
+}
+std::wstring Instance::TestWideGuest::relay(std::wstring s) {
+  return wit::to_wstring(::exports::test::wide::guest::Relay(exec_env, wit::string::from_view(exec_env, wit::to_utf8(s))));
+}
+void register_test_wide_host_text() {
+    static NativeSymbol test_wide_host_text_funs[] = {
+        { "echo", (void*)testX3AwideX2Fhost_textX00echo, "($~*)", nullptr },
+    };
+    wasm_runtime_register_natives("test:wide/host-text", test_wide_host_text_funs, sizeof(test_wide_host_text_funs)/sizeof(NativeSymbol));
+}
+void register_wide() {
+    register_test_wide_host_text();
+}
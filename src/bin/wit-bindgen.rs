@@ -113,6 +113,11 @@ struct Common {
     #[clap(long)]
     check: bool,
 
+    /// Indicates that no files are written and instead the paths of the files
+    /// which would be generated are printed, one per line.
+    #[clap(long, conflicts_with = "check")]
+    list_outputs: bool,
+
     /// Comma-separated list of features that should be enabled when processing
     /// WIT files.
     ///
@@ -149,6 +154,10 @@ fn main() -> Result<()> {
             Some(path) => path.join(name),
             None => name.into(),
         };
+        if opt.list_outputs {
+            println!("{}", dst.display());
+            continue;
+        }
         println!("Generating {:?}", dst);
 
         if opt.check {
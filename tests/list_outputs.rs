@@ -0,0 +1,53 @@
+#![cfg(feature = "cpp")]
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn generated_files(dir: &Path, files: &mut BTreeSet<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            generated_files(&path, files);
+        } else {
+            files.insert(path);
+        }
+    }
+}
+
+#[test]
+fn listed_outputs_match_generated_files() {
+    let wit = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/codegen/variants.wit");
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("list-outputs-variants");
+    drop(std::fs::remove_dir_all(&out_dir));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_wit-bindgen"))
+        .arg("cpp")
+        .arg("--list-outputs")
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg(&wit)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let listed = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(PathBuf::from)
+        .collect::<BTreeSet<_>>();
+    assert!(!listed.is_empty());
+    // nothing is written
+    assert!(!out_dir.exists());
+
+    let status = Command::new(env!("CARGO_BIN_EXE_wit-bindgen"))
+        .arg("cpp")
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg(&wit)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut generated = BTreeSet::new();
+    generated_files(&out_dir, &mut generated);
+    assert_eq!(listed, generated);
+}
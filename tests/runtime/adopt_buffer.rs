@@ -0,0 +1,33 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/adopt_buffer");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl AdoptBufferImports for MyImports {
+    fn sum(&mut self, values: Vec<u32>) -> u32 {
+        values.iter().sum()
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "adopt_buffer",
+        |linker| AdoptBuffer::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| AdoptBuffer::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: AdoptBuffer, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    // the adopted buffer is the only allocation of the call
+    let before = exports.call_allocations(&mut *store)?;
+    assert_eq!(exports.call_adopted(&mut *store, 4)?, [0, 1, 4, 9]);
+    assert_eq!(exports.call_allocations(&mut *store)?, before + 1);
+    Ok(())
+}
@@ -0,0 +1,42 @@
+#include <assert.h>
+#include <stdlib.h>
+// counts the allocations of strings and vectors
+#define WIT_CUSTOM_ALLOCATOR
+#include <adopt_buffer_cpp.h>
+
+static uint32_t allocations = 0;
+extern "C" void *wit_malloc(size_t size) {
+  ++allocations;
+  return malloc(size);
+}
+extern "C" void wit_free(void *ptr) { free(ptr); }
+
+// room for one more than filled
+static wit::vector<uint32_t> squares(uint32_t count) {
+  uint32_t *buffer = (uint32_t *)wit_malloc(sizeof(uint32_t) * (count + 1));
+  for (uint32_t i = 0; i < count; ++i) {
+    buffer[i] = i * i;
+  }
+  return wit::vector<uint32_t>::from_parts(buffer, count, count + 1);
+}
+
+wit::vector<uint32_t> exports::adopt_buffer::Adopted(uint32_t count) {
+  // lowered without copying
+  return squares(count);
+}
+
+uint32_t exports::adopt_buffer::Allocations() { return allocations; }
+
+void exports::adopt_buffer::TestImports() {
+  uint32_t before = allocations;
+  wit::vector<uint32_t> values = squares(4);
+  assert(allocations == before + 1);
+  assert(values.size() == 4 && values[3] == 9);
+  uint32_t const *buffer = values.data();
+
+  // the adopted buffer moves and is viewed without another allocation
+  wit::vector<uint32_t> moved = std::move(values);
+  assert(moved.data() == buffer);
+  assert(::adopt_buffer::Sum(wit::span<uint32_t const>(moved.data(), moved.size())) == 14);
+  assert(allocations == before + 1);
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/bare_results");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::bare_results::test::Host for MyImports {
+    fn even(&mut self, x: u32) -> Result<u32, ()> {
+        if x % 2 == 0 {
+            Ok(x)
+        } else {
+            Err(())
+        }
+    }
+
+    fn is_ok(&mut self, x: Result<u32, ()>) -> bool {
+        x.is_ok()
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "bare_results",
+        |linker| BareResults::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| BareResults::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: BareResults, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_bare_results_test();
+    assert_eq!(test.call_even(&mut *store, 10)?, Ok(10));
+    assert_eq!(test.call_even(&mut *store, 11)?, Err(()));
+    assert!(test.call_is_ok(&mut *store, Ok(1))?);
+    assert!(!test.call_is_ok(&mut *store, Err(()))?);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+#include <assert.h>
+#include <bare_results_cpp.h>
+
+void exports::bare_results::TestImports() {
+    using namespace ::test::bare_results::test;
+    assert(Even(4) == std::optional<uint32_t>(4));
+    assert(!Even(3).has_value());
+    assert(IsOk(std::optional<uint32_t>(7)));
+    assert(!IsOk(std::nullopt));
+}
+
+std::optional<uint32_t> exports::test::bare_results::test::Even(uint32_t x) {
+    if (x % 2 == 0) {
+        return x;
+    }
+    return std::nullopt;
+}
+
+bool exports::test::bare_results::test::IsOk(std::optional<uint32_t> x) {
+    return x.has_value();
+}
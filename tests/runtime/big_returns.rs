@@ -0,0 +1,85 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/big_returns");
+
+use test::big_returns::test::{Block, Row};
+
+// the number in column `c` of row `r`, the id keeps the high bits busy
+fn row(id: u32, r: u64) -> Row {
+    let cell = |c: u64| (u64::from(id) << 32) | (r * 16 + c);
+    (
+        cell(0),
+        cell(1),
+        cell(2),
+        cell(3),
+        cell(4),
+        cell(5),
+        cell(6),
+        cell(7),
+        cell(8),
+        cell(9),
+        cell(10),
+        cell(11),
+        cell(12),
+        cell(13),
+        cell(14),
+        cell(15),
+    )
+}
+
+fn block(id: u32) -> Block {
+    Block {
+        id,
+        row0: row(id, 0),
+        row1: row(id, 1),
+        row2: row(id, 2),
+        row3: row(id, 3),
+        row4: row(id, 4),
+        row5: row(id, 5),
+        row6: row(id, 6),
+        row7: row(id, 7),
+        row8: row(id, 8),
+        row9: row(id, 9),
+    }
+}
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::big_returns::test::Host for MyImports {
+    fn make_block(&mut self, id: u32) -> Block {
+        block(id)
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "big_returns",
+        |linker| BigReturns::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| BigReturns::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: BigReturns, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_big_returns_test();
+    for id in [7, 0xfffffffe] {
+        let (actual, expected) = (test.call_make_block(&mut *store, id)?, block(id));
+        assert_eq!(actual.id, expected.id);
+        assert_eq!(actual.row0, expected.row0);
+        assert_eq!(actual.row1, expected.row1);
+        assert_eq!(actual.row2, expected.row2);
+        assert_eq!(actual.row3, expected.row3);
+        assert_eq!(actual.row4, expected.row4);
+        assert_eq!(actual.row5, expected.row5);
+        assert_eq!(actual.row6, expected.row6);
+        assert_eq!(actual.row7, expected.row7);
+        assert_eq!(actual.row8, expected.row8);
+        assert_eq!(actual.row9, expected.row9);
+    }
+    Ok(())
+}
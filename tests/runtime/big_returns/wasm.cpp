@@ -0,0 +1,67 @@
+#include <assert.h>
+#include <big_returns_cpp.h>
+#include <tuple>
+#include <utility>
+
+namespace {
+// named tuples have no name in C++
+using Row = std::tuple<uint64_t, uint64_t, uint64_t, uint64_t, uint64_t,
+                       uint64_t, uint64_t, uint64_t, uint64_t, uint64_t,
+                       uint64_t, uint64_t, uint64_t, uint64_t, uint64_t,
+                       uint64_t>;
+using Block = test::big_returns::test::Block;
+
+// the number in column `c` of row `r`, the id keeps the high bits busy
+uint64_t cell(uint32_t id, size_t r, size_t c) {
+  return (uint64_t(id) << 32) | (r * 16 + c);
+}
+
+template <size_t... C>
+Row make_row(uint32_t id, size_t r, std::index_sequence<C...>) {
+  return Row(cell(id, r, C)...);
+}
+
+Row make_row(uint32_t id, size_t r) {
+  return make_row(id, r, std::make_index_sequence<16>());
+}
+
+template <size_t... C>
+void check_row(Row const &row, uint32_t id, size_t r, std::index_sequence<C...>) {
+  ((assert(std::get<C>(row) == cell(id, r, C))), ...);
+}
+
+void check_row(Row const &row, uint32_t id, size_t r) {
+  check_row(row, id, r, std::make_index_sequence<16>());
+}
+} // namespace
+
+void exports::big_returns::TestImports() {
+  for (uint32_t id : {7u, 0xfffffffeu}) {
+    Block block = test::big_returns::test::MakeBlock(id);
+    assert(block.id == id);
+    check_row(block.row0, id, 0);
+    check_row(block.row1, id, 1);
+    check_row(block.row2, id, 2);
+    check_row(block.row3, id, 3);
+    check_row(block.row4, id, 4);
+    check_row(block.row5, id, 5);
+    check_row(block.row6, id, 6);
+    check_row(block.row7, id, 7);
+    check_row(block.row8, id, 8);
+    check_row(block.row9, id, 9);
+  }
+}
+
+Block exports::test::big_returns::test::MakeBlock(uint32_t id) {
+  return Block{id,
+               make_row(id, 0),
+               make_row(id, 1),
+               make_row(id, 2),
+               make_row(id, 3),
+               make_row(id, 4),
+               make_row(id, 5),
+               make_row(id, 6),
+               make_row(id, 7),
+               make_row(id, 8),
+               make_row(id, 9)};
+}
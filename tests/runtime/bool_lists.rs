@@ -0,0 +1,36 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/bool_lists");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::bool_lists::test::Host for MyImports {
+    fn list_bool_roundtrip(&mut self, a: Vec<bool>) -> Vec<bool> {
+        assert_eq!(a, [true, false, false, true, true, true, false, true, false]);
+        a
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "bool_lists",
+        |linker| BoolLists::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| BoolLists::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: BoolLists, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let values = [false, true, true, false, true, false, false, false, true];
+    let result = exports
+        .test_bool_lists_test()
+        .call_list_bool_roundtrip(&mut *store, &values)?;
+    assert_eq!(result, values);
+
+    Ok(())
+}
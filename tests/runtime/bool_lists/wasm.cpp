@@ -0,0 +1,16 @@
+#include <assert.h>
+#include <bool_lists_cpp.h>
+
+void exports::bool_lists::TestImports() {
+    bool values[] = {true, false, false, true, true, true, false, true, false};
+    auto result = ::test::bool_lists::test::ListBoolRoundtrip(
+        wit::span<bool const>(values, sizeof(values) / sizeof(values[0])));
+    assert(result.size() == sizeof(values) / sizeof(values[0]));
+    for (size_t i = 0; i < result.size(); ++i) {
+        assert(result[i] == values[i]);
+    }
+}
+
+wit::vector<bool> exports::test::bool_lists::test::ListBoolRoundtrip(wit::vector<bool> &&a) {
+    return std::move(a);
+}
@@ -0,0 +1,33 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/borrow_lists");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "borrow_lists",
+        |_| Ok(()),
+        |store, component, linker| BorrowLists::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(instance: BorrowLists, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    let test = instance.test_borrow_lists_test();
+    let things = [1, 2, 3]
+        .into_iter()
+        .map(|v| test.thing().call_constructor(&mut *store, v))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    assert_eq!(test.call_sum(&mut *store, &[])?, 0);
+    assert_eq!(test.call_sum(&mut *store, &things)?, 6);
+    assert_eq!(test.call_sum(&mut *store, &[things[2], things[2]])?, 6);
+    // the borrowed things are still alive and usable
+    assert_eq!(test.call_live(&mut *store)?, 3);
+    assert_eq!(test.thing().call_value(&mut *store, things[1])?, 2);
+
+    for thing in things {
+        thing.resource_drop(&mut *store)?;
+    }
+    assert_eq!(test.call_live(&mut *store)?, 0);
+    Ok(())
+}
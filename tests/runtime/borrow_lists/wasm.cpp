@@ -0,0 +1,27 @@
+#include <map>
+#include <borrow_lists_cpp.h>
+
+namespace test = exports::test::borrow_lists::test;
+
+// the generated class has no members of its own
+static std::map<test::Thing const *, uint32_t> values;
+
+test::Thing::Thing(uint32_t v) { values[this] = v; }
+
+void test::Thing::Dtor(test::Thing *self) {
+    values.erase(self);
+    delete self;
+}
+
+uint32_t test::Thing::Value() { return values[this]; }
+
+uint32_t test::Sum(wit::vector<std::reference_wrapper<const test::Thing>> &&things) {
+    uint32_t sum = 0;
+    for (auto thing : things.get_view()) {
+        sum += values[&thing.get()];
+    }
+    // destroying the borrows drops nothing
+    return sum;
+}
+
+uint32_t test::Live() { return uint32_t(values.size()); }
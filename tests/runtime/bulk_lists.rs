@@ -0,0 +1,69 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/bulk_lists");
+
+use test::bulk_lists::test::{Level, Sample};
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::bulk_lists::test::Host for MyImports {
+    fn float_roundtrip(&mut self, a: Vec<f64>) -> Vec<f64> {
+        assert_eq!(a.len(), 100_000);
+        assert!(a.iter().enumerate().all(|(i, v)| *v == i as f64 * 0.5));
+        a
+    }
+
+    fn sample_roundtrip(&mut self, a: Vec<Sample>) -> Vec<Sample> {
+        assert_eq!(a.len(), 1000);
+        for (i, sample) in a.iter().enumerate() {
+            assert_eq!(sample.value, i as f64 * 0.25);
+            assert_eq!(sample.valid, i % 3 == 0);
+            assert_eq!(
+                sample.level,
+                if i % 2 == 1 { Level::High } else { Level::Low }
+            );
+        }
+        a
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "bulk_lists",
+        |linker| BulkLists::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| BulkLists::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: BulkLists, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let floats = (0..250_000).map(|i| i as f64 / 3.0).collect::<Vec<_>>();
+    let result = exports
+        .test_bulk_lists_test()
+        .call_float_roundtrip(&mut *store, &floats)?;
+    assert_eq!(result, floats);
+
+    let samples = (0..500)
+        .map(|i| Sample {
+            value: -(i as f64),
+            valid: i % 2 == 0,
+            level: if i % 5 == 0 { Level::High } else { Level::Low },
+        })
+        .collect::<Vec<_>>();
+    let result = exports
+        .test_bulk_lists_test()
+        .call_sample_roundtrip(&mut *store, &samples)?;
+    assert_eq!(result.len(), samples.len());
+    for (a, b) in result.iter().zip(&samples) {
+        assert_eq!(a.value, b.value);
+        assert_eq!(a.valid, b.valid);
+        assert_eq!(a.level, b.level);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+#include <assert.h>
+#include <bulk_lists_cpp.h>
+#include <vector>
+
+using ::test::bulk_lists::test::Level;
+using ::test::bulk_lists::test::Sample;
+
+void exports::bulk_lists::TestImports() {
+    std::vector<double> floats;
+    for (size_t i = 0; i < 100000; ++i) {
+        floats.push_back(i * 0.5);
+    }
+    auto result = ::test::bulk_lists::test::FloatRoundtrip(
+        wit::span<double const>(floats.data(), floats.size()));
+    assert(result.size() == floats.size());
+    for (size_t i = 0; i < result.size(); ++i) {
+        assert(result[i] == floats[i]);
+    }
+
+    std::vector<Sample> samples;
+    for (size_t i = 0; i < 1000; ++i) {
+        samples.push_back(Sample{i * 0.25, i % 3 == 0, i % 2 ? Level::kHigh : Level::kLow});
+    }
+    auto returned = ::test::bulk_lists::test::SampleRoundtrip(
+        wit::span<Sample const>(samples.data(), samples.size()));
+    assert(returned.size() == samples.size());
+    for (size_t i = 0; i < returned.size(); ++i) {
+        assert(returned[i].value == samples[i].value);
+        assert(returned[i].valid == samples[i].valid);
+        assert(returned[i].level == samples[i].level);
+    }
+}
+
+wit::vector<double> exports::test::bulk_lists::test::FloatRoundtrip(wit::vector<double> &&a) {
+    return std::move(a);
+}
+
+wit::vector<Sample> exports::test::bulk_lists::test::SampleRoundtrip(wit::vector<Sample> &&a) {
+    return std::move(a);
+}
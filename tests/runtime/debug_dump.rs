@@ -0,0 +1,49 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/debug_dump");
+
+use exports::test::debug_dump::shapes::{Color, Drawing, Point, Shape};
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "debug_dump",
+        |_| Ok(()),
+        |store, component, linker| DebugDump::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: DebugDump, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    let drawing = Drawing {
+        name: "sketch".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+        origin: Point { x: 1, y: -2 },
+        shapes: vec![
+            Shape::Circle(5),
+            Shape::Polygon(vec![Point { x: 3, y: 4 }]),
+            Shape::Empty,
+        ],
+        note: None,
+        scale: 1.5,
+        fill: (Color::Blue, 200),
+        checked: Err("bad".to_string()),
+    };
+    let dump = exports
+        .test_debug_dump_shapes()
+        .call_dump_drawing(&mut *store, &drawing)?;
+
+    assert!(dump.starts_with("Drawing {\n"));
+    assert!(dump.contains("  name: \"sketch\",\n"));
+    assert!(dump.contains("  tags: [\n    \"a\",\n    \"b\",\n  ],\n"));
+    assert!(dump.contains("  origin: Point {\n    x: 1,\n    y: -2,\n  },\n"));
+    assert!(dump.contains("    Shape::Circle(5),\n"));
+    assert!(dump.contains("    Shape::Polygon([\n      Point {\n        x: 3,\n"));
+    assert!(dump.contains("    Shape::Empty,\n"));
+    assert!(dump.contains("  note: none,\n"));
+    assert!(dump.contains("  scale: 1.5,\n"));
+    assert!(dump.contains("  fill: (2, 200),\n"));
+    assert!(dump.contains("  checked: err(\"bad\"),\n"));
+    assert!(dump.ends_with("}"));
+    Ok(())
+}
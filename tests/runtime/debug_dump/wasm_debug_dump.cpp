@@ -0,0 +1,9 @@
+#include <debug_dump_cpp.h>
+#include <sstream>
+
+wit::string exports::test::debug_dump::shapes::DumpDrawing(
+    ::test::debug_dump::shapes::Drawing d) {
+  std::ostringstream os;
+  d.dump(os);
+  return wit::string::from_view(os.str());
+}
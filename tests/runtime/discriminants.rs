@@ -0,0 +1,26 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/discriminants");
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "discriminants",
+        |_| Ok(()),
+        |store, component, linker| Discriminants::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: Discriminants, store: &mut Store<crate::Wasi<()>>) -> Result<()> {
+    assert_eq!(exports.call_take_option(&mut *store, Some(7))?, 7);
+    assert_eq!(exports.call_take_option(&mut *store, None)?, 0);
+    assert_eq!(exports.call_take_result(&mut *store, Ok(3))?, 3);
+    assert_eq!(exports.call_take_result(&mut *store, Err(4))?, 1004);
+
+    // a discriminant of 2 traps instead of being read as `some`
+    assert!(exports.call_trap_on_option(&mut *store).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+#include <assert.h>
+#include <discriminants_cpp.h>
+
+// the lifting entry point of take-option, called with a malformed discriminant
+extern "C" int32_t take_option(int32_t discriminant, int32_t payload);
+
+uint32_t exports::discriminants::TakeOption(std::optional<uint32_t> a) {
+    return a.value_or(0);
+}
+
+uint32_t exports::discriminants::TakeResult(std::expected<uint32_t, uint8_t> a) {
+    return a.has_value() ? a.value() : 1000 + a.error();
+}
+
+void exports::discriminants::TrapOnOption() {
+    assert(take_option(1, 42) == 42);
+    take_option(2, 42);
+    // not reached, the lift traps
+    assert(false);
+}
@@ -0,0 +1,43 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/empty_lists");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl EmptyListsImports for MyImports {
+    fn host_sizes(&mut self, a: Vec<u32>, b: Vec<String>, s: String) -> u32 {
+        assert!(s.is_empty());
+        assert!(b.iter().all(|s| s.is_empty()));
+        (a.len() + b.len() + s.len()) as u32
+    }
+}
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "empty_lists",
+        |linker| EmptyLists::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| EmptyLists::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: EmptyLists, store: &mut Store<crate::Wasi<MyImports>>) -> anyhow::Result<()> {
+    assert_eq!(exports.call_empty_list(&mut *store)?, Vec::<u32>::new());
+    assert_eq!(exports.call_empty_string(&mut *store)?, "");
+    assert_eq!(
+        exports.call_empty_strings(&mut *store)?,
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        exports.call_echo(&mut *store, &[], "")?,
+        (Vec::new(), String::new())
+    );
+    assert_eq!(
+        exports.call_echo(&mut *store, &[1, 2], "x")?,
+        (vec![1, 2], "x".to_string())
+    );
+    exports.call_test_imports(&mut *store)?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+#include <assert.h>
+#include <empty_lists_cpp.h>
+
+wit::vector<uint32_t> exports::empty_lists::EmptyList() {
+    return wit::vector<uint32_t>::allocate(0);
+}
+
+wit::string exports::empty_lists::EmptyString() { return wit::string::from_view(""); }
+
+wit::vector<wit::string> exports::empty_lists::EmptyStrings() {
+    return wit::vector<wit::string>::allocate(0);
+}
+
+// the host passes empty values with the pointers of its choice, which
+// are never freed
+std::tuple<wit::vector<uint8_t>, wit::string>
+exports::empty_lists::Echo(wit::vector<uint8_t> &&a, wit::string &&s) {
+    return std::make_tuple(std::move(a), std::move(s));
+}
+
+void exports::empty_lists::TestImports() {
+    uint32_t numbers[1] = {1};
+    std::string_view names[1] = {""};
+    assert(::empty_lists::HostSizes(wit::span<uint32_t const>(numbers, 0),
+                                    wit::span<std::string_view const>(names, 0),
+                                    std::string_view()) == 0);
+    // an empty string within a list
+    assert(::empty_lists::HostSizes(wit::span<uint32_t const>(numbers, 1),
+                                    wit::span<std::string_view const>(names, 1),
+                                    std::string_view("", 0)) == 2);
+}
@@ -0,0 +1,2 @@
+// the same implementation, lowering empty values as aligned dangling pointers
+#include "wasm.cpp"
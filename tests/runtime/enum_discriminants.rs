@@ -0,0 +1,34 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/enum_discriminants");
+
+use test::enum_discriminants::imports::Color;
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::enum_discriminants::imports::Host for MyImports {
+    fn next(&mut self, c: Color) -> Color {
+        match c {
+            Color::Red => Color::Green,
+            Color::Green => Color::Blue,
+            Color::Blue => Color::Red,
+        }
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "enum_discriminants",
+        |linker| EnumDiscriminants::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| EnumDiscriminants::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: EnumDiscriminants, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    Ok(())
+}
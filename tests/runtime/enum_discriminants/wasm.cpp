@@ -0,0 +1,17 @@
+#include <assert.h>
+#include <enum_discriminants_cpp.h>
+
+void exports::enum_discriminants::TestImports() {
+  using namespace test::enum_discriminants::imports;
+  assert(to_discriminant(Color::kRed) == 0);
+  assert(to_discriminant(Color::kBlue) == 2);
+  assert(Color_from_discriminant(1) == Color::kGreen);
+  // out of range integers are no color
+  assert(!Color_from_discriminant(3).has_value());
+  assert(!Color_from_discriminant(0xffffffff).has_value());
+
+  for (uint32_t d = 0; d < 3; ++d) {
+    Color c = Next(Color_from_discriminant(d).value());
+    assert(to_discriminant(c) == (d + 1) % 3);
+  }
+}
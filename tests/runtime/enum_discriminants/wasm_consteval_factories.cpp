@@ -0,0 +1,24 @@
+#include <assert.h>
+#include <enum_discriminants_cpp.h>
+
+using namespace test::enum_discriminants::imports;
+
+// compiled with C++20
+static_assert(__cpp_consteval >= 201811L, "consteval support");
+
+// an immediate function building on the factories
+consteval uint32_t discriminant_sum() {
+  return to_discriminant(make_Color_red()) + to_discriminant(make_Color_green()) +
+         to_discriminant(make_Color_blue());
+}
+static_assert(discriminant_sum() == 3);
+static_assert(make_Color_blue() == Color::kBlue);
+
+template <Color C> constexpr uint32_t tag = to_discriminant(C);
+static_assert(tag<make_Color_green()> == 1);
+
+void exports::enum_discriminants::TestImports() {
+  constexpr Color first = make_Color_red();
+  assert(Next(first) == make_Color_green());
+  assert(Next(make_Color_blue()) == first);
+}
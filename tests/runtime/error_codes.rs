@@ -0,0 +1,38 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/error_codes");
+
+use test::error_codes::test::MyErrno;
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::error_codes::test::Host for MyImports {
+    fn checked_sqrt(&mut self, a: i32) -> Result<i32, MyErrno> {
+        if a < 0 {
+            return Err(MyErrno::A);
+        }
+        Ok((a as f64).sqrt() as i32)
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "error_codes",
+        |linker| ErrorCodes::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| ErrorCodes::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: ErrorCodes, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_error_codes_test();
+    assert_eq!(test.call_checked_sqrt(&mut *store, 16)?, Ok(4));
+    assert_eq!(test.call_checked_sqrt(&mut *store, -1)?, Err(MyErrno::A));
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+#include <assert.h>
+#include <error_codes_cpp.h>
+#include <string.h>
+
+void exports::error_codes::TestImports() {
+    using ::test::error_codes::test::CheckedSqrt;
+    using ::test::error_codes::test::MyErrno;
+    std::error_code code = MyErrno::kB;
+    assert(code.message() == "b");
+    assert(!strcmp(code.category().name(), "my-errno"));
+    assert(code == make_error_code(MyErrno::kB));
+    assert(code != make_error_code(MyErrno::kA));
+    assert(CheckedSqrt(9).value() == 3);
+    std::error_code failed = CheckedSqrt(-4).error();
+    assert(failed == MyErrno::kA);
+    assert(failed.message() == "a");
+}
+
+std::expected<int32_t, ::test::error_codes::test::MyErrno> exports::test::error_codes::test::CheckedSqrt(int32_t a) {
+    if (a < 0) {
+        return std::unexpected(::test::error_codes::test::MyErrno::kA);
+    }
+    int32_t root = 0;
+    while ((root + 1) * (root + 1) <= a) {
+        ++root;
+    }
+    return root;
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/flag_iteration");
+
+use test::flag_iteration::test::Permissions;
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::flag_iteration::test::Host for MyImports {
+    fn get_permissions(&mut self) -> Permissions {
+        Permissions::WRITE | Permissions::DELETE
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "flag_iteration",
+        |linker| FlagIteration::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| FlagIteration::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: FlagIteration, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    assert_eq!(exports.call_test_imports(&mut *store)?, 2);
+
+    Ok(())
+}
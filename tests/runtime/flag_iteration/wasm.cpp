@@ -0,0 +1,15 @@
+#include <assert.h>
+#include <flag_iteration_cpp.h>
+#include <vector>
+
+uint32_t exports::flag_iteration::TestImports() {
+    using ::test::flag_iteration::test::Permissions;
+    std::vector<Permissions> collected;
+    for (auto flag : ::test::flag_iteration::test::GetPermissions()) {
+        collected.push_back(flag);
+    }
+    assert(collected.size() == 2);
+    assert(collected[0] == Permissions::kWrite);
+    assert(collected[1] == Permissions::kDelete);
+    return uint32_t(collected.size());
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/lowering_buffer");
+
+use test::lowering_buffer::test::Entry;
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::lowering_buffer::test::Host for MyImports {
+    fn total(&mut self, entries: Vec<Entry>) -> u32 {
+        entries.iter().map(|e| e.value).sum()
+    }
+
+    fn count_bytes(&mut self, names: Vec<String>) -> u32 {
+        names.iter().map(|n| n.len() as u32).sum()
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "lowering_buffer",
+        |linker| LoweringBuffer::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| LoweringBuffer::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: LoweringBuffer, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    Ok(())
+}
@@ -0,0 +1,75 @@
+#include <assert.h>
+#include <lowering_buffer_cpp.h>
+#include <string.h>
+
+// allocator hook: a bump allocator counting allocations, replacing the
+// allocator of wasi-libc (memory is never reused, which is fine for a test)
+static size_t allocations = 0;
+alignas(16) static unsigned char heap[1 << 20];
+static size_t heap_used = 0;
+
+extern "C" void *aligned_alloc(size_t align, size_t size) {
+    ++allocations;
+    if (align < 16) {
+        align = 16;
+    }
+    // the size of the block is stored in front of it for realloc
+    size_t start = (heap_used + 16 + align - 1) & ~(align - 1);
+    assert(start + size <= sizeof(heap));
+    *(size_t *)(heap + start - sizeof(size_t)) = size;
+    heap_used = start + size;
+    return heap + start;
+}
+extern "C" void *malloc(size_t size) { return aligned_alloc(16, size); }
+extern "C" void free(void *) {}
+extern "C" void *calloc(size_t n, size_t size) {
+    void *result = malloc(n * size);
+    memset(result, 0, n * size);
+    return result;
+}
+extern "C" void *realloc(void *ptr, size_t size) {
+    void *result = malloc(size);
+    if (ptr) {
+        size_t old = *(size_t *)((unsigned char *)ptr - sizeof(size_t));
+        memcpy(result, ptr, old < size ? old : size);
+    }
+    return result;
+}
+extern "C" int posix_memalign(void **result, size_t align, size_t size) {
+    *result = aligned_alloc(align, size);
+    return 0;
+}
+
+namespace imports = test::lowering_buffer::test;
+
+static uint32_t total(size_t n) {
+    // allocated by the first call
+    static imports::Entry entries[] = {{wit::string::from_view("a"), 1},
+                                    {wit::string::from_view("b"), 2},
+                                    {wit::string::from_view("c"), 3},
+                                    {wit::string::from_view("d"), 4}};
+    assert(n <= 4);
+    return imports::Total(wit::span<imports::Entry const>(entries, n));
+}
+
+static uint32_t count_bytes(size_t n) {
+    std::string_view names[] = {"one", "two", "three"};
+    assert(n <= 3);
+    return imports::CountBytes(wit::span<std::string_view const>(names, n));
+}
+
+void exports::lowering_buffer::TestImports() {
+    // warmup, the buffers grow to the largest list
+    assert(total(2) == 3);
+    assert(total(4) == 10);
+    assert(count_bytes(3) == 11);
+
+    size_t before = allocations;
+    for (int i = 0; i < 100; ++i) {
+        assert(total(4) == 10);
+        assert(total(1) == 1);
+        assert(total(0) == 0);
+        assert(count_bytes(2) == 6);
+    }
+    assert(allocations == before);
+}
@@ -13,13 +13,31 @@ use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiView};
 use wit_component::{ComponentEncoder, StringEncoding};
 use wit_parser::{Resolve, WorldId, WorldItem};
 
+mod adopt_buffer;
+mod bare_results;
+mod big_returns;
+mod bool_lists;
+mod borrow_lists;
+mod bulk_lists;
+mod debug_dump;
+mod discriminants;
+mod empty_lists;
+mod enum_discriminants;
+mod error_codes;
+mod flag_iteration;
 mod flavorful;
 mod lists;
+mod lowering_buffer;
 mod many_arguments;
+mod nan_canon;
+mod nested_lists;
 mod numbers;
 mod options;
+mod ordered_records;
 mod other_dependencies;
+mod owned_borrows;
 mod ownership;
+mod record_lists;
 mod records;
 mod resource_aggregates;
 mod resource_alias;
@@ -29,17 +47,40 @@ mod resource_borrow_import;
 mod resource_borrow_in_record;
 mod resource_borrow_simple;
 mod resource_floats;
+mod resource_identity;
 mod resource_import_and_export;
 mod resource_into_inner;
+mod resource_record_drops;
+mod resource_reset;
+mod resource_spawn;
+mod resource_statics;
 mod resource_with_lists;
 mod resources;
+mod result_helpers;
 mod results;
 mod rust_xcrate;
+mod sbo_lists;
+mod small_ints;
 mod smoke;
+mod span_results;
+mod string_bytes;
+mod string_code_points;
+mod string_compare;
+mod string_concat;
+mod string_moves;
 mod strings;
+mod try_call;
+mod tuples_wide;
 mod type_section_suffix;
+mod variant_setters;
 mod variants;
+mod vector_append;
+mod vector_copies;
+mod vector_data;
+mod vector_into_std;
+mod vector_params;
 mod versions;
+mod wide_strings;
 
 struct MyCtx {}
 
@@ -279,6 +320,44 @@ fn tests(name: &str, dir_name: &str) -> Result<Vec<PathBuf>> {
             let snake = world_name.replace("-", "_");
             let mut files = Default::default();
             let mut opts = wit_bindgen_cpp::Opts::default();
+            if let Some(path) = path.file_name().and_then(|s| s.to_str()) {
+                if path.contains("optional") {
+                    opts.bare_error = wit_bindgen_cpp::BareError::Optional;
+                }
+                if path.contains("wit_result") {
+                    opts.wit_result = true;
+                }
+                if path.contains("error_code") {
+                    opts.error_code = true;
+                }
+                if path.contains("vector_overloads") {
+                    opts.vector_overloads = true;
+                }
+                if path.contains("reuse_lowering_buffer") {
+                    opts.reuse_lowering_buffer = true;
+                }
+                if path.contains("span_byte_results") {
+                    opts.span_byte_results = true;
+                }
+                if path.contains("empty_ptr_dangling") {
+                    opts.empty_ptr = wit_bindgen_cpp::EmptyPtr::Dangling;
+                }
+                if path.contains("debug_dump") {
+                    opts.debug_dump = true;
+                }
+                if path.contains("try_call") {
+                    opts.try_call = true;
+                }
+                if path.contains("sbo") {
+                    opts.sbo = 4;
+                }
+                if path.contains("consteval_factories") {
+                    opts.consteval_factories = true;
+                }
+                if path.contains("derive_ord") {
+                    opts.derive_ord = true;
+                }
+            }
             opts.build().generate(&resolve, world, &mut files).unwrap();
 
             for (file, contents) in files.iter() {
@@ -291,6 +370,13 @@ fn tests(name: &str, dir_name: &str) -> Result<Vec<PathBuf>> {
             ));
             // Test both C mode and C++ mode.
             let compiler = "bin/clang++";
+            // immediate functions and operator<=> need C++20
+            let std = match path.file_name().and_then(|s| s.to_str()) {
+                Some(name) if name.contains("consteval") || name.contains("derive_ord") => {
+                    "-std=c++20"
+                }
+                _ => "-std=c++17",
+            };
             let mut cmd = Command::new(sdk.join(compiler));
             let out_wasm = out_dir.join(format!(
                 "cpp-{}.wasm",
@@ -310,7 +396,7 @@ fn tests(name: &str, dir_name: &str) -> Result<Vec<PathBuf>> {
                 //                .arg("-Werror")
                 .arg("-Wno-unused-parameter")
                 .arg("-mexec-model=reactor")
-                .arg("-std=c++17")
+                .arg(std)
                 .arg("-g")
                 .arg("-o")
                 .arg(&out_wasm);
@@ -0,0 +1,45 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/nan_canon");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl NanCanonImports for MyImports {
+    fn bits_f32(&mut self, a: f32) -> u32 {
+        a.to_bits()
+    }
+
+    fn bits_f64(&mut self, a: f64) -> u64 {
+        a.to_bits()
+    }
+}
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "nan_canon",
+        |linker| NanCanon::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| NanCanon::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: NanCanon, store: &mut Store<crate::Wasi<MyImports>>) -> anyhow::Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    // a signaling NaN is lifted as the canonical NaN
+    let signaling = f32::from_bits(0x7fa0_0001);
+    assert_eq!(
+        exports.call_lifted_bits_f32(&mut *store, signaling)?,
+        0x7fc0_0000
+    );
+    let signaling = f64::from_bits(0x7ff4_0000_0000_0001);
+    assert_eq!(
+        exports.call_lifted_bits_f64(&mut *store, signaling)?,
+        0x7ff8_0000_0000_0000
+    );
+    assert_eq!(exports.call_lifted_bits_f32(&mut *store, 0.5)?, 0x3f00_0000);
+
+    Ok(())
+}
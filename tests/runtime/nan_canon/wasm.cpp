@@ -0,0 +1,40 @@
+#include <assert.h>
+#include <nan_canon_cpp.h>
+#include <string.h>
+
+static float f32_from_bits(uint32_t bits) {
+    float f;
+    memcpy(&f, &bits, sizeof(f));
+    return f;
+}
+
+static double f64_from_bits(uint64_t bits) {
+    double d;
+    memcpy(&d, &bits, sizeof(d));
+    return d;
+}
+
+template <class T, class U> static U to_bits(T value) {
+    U bits;
+    memcpy(&bits, &value, sizeof(bits));
+    return bits;
+}
+
+void exports::nan_canon::TestImports() {
+    // signaling NaNs with a payload are lowered as the canonical NaN
+    assert(::nan_canon::BitsF32(f32_from_bits(0x7fa00001u)) == 0x7fc00000u);
+    assert(::nan_canon::BitsF32(f32_from_bits(0xffc00000u)) == 0x7fc00000u);
+    assert(::nan_canon::BitsF64(f64_from_bits(0x7ff4000000000001ull)) ==
+           0x7ff8000000000000ull);
+    // other values keep their bits
+    assert(::nan_canon::BitsF32(-1.5f) == 0xbfc00000u);
+    assert(::nan_canon::BitsF64(2.0) == 0x4000000000000000ull);
+}
+
+uint32_t exports::nan_canon::LiftedBitsF32(float a) {
+    return to_bits<float, uint32_t>(a);
+}
+
+uint64_t exports::nan_canon::LiftedBitsF64(double a) {
+    return to_bits<double, uint64_t>(a);
+}
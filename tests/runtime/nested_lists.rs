@@ -0,0 +1,49 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/nested_lists");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::nested_lists::test::Host for MyImports {
+    fn jagged_roundtrip(&mut self, a: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+        assert_eq!(a, [vec![1], vec![], vec![2, 3, 4]]);
+        a
+    }
+
+    fn deep_count(&mut self, a: Vec<Vec<Vec<String>>>) -> u32 {
+        a.iter().flatten().map(|words| words.len() as u32).sum()
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "nested_lists",
+        |linker| NestedLists::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| NestedLists::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: NestedLists, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let jagged = vec![vec![], vec![7, 8], vec![9], vec![10, 11, 12, 13]];
+    let result = exports
+        .test_nested_lists_test()
+        .call_jagged_roundtrip(&mut *store, &jagged)?;
+    assert_eq!(result, jagged);
+
+    let words = vec![
+        vec![vec!["x".to_string()], vec![]],
+        vec![vec!["y".to_string(), "z".to_string()]],
+    ];
+    let count = exports
+        .test_nested_lists_test()
+        .call_deep_count(&mut *store, &words)?;
+    assert_eq!(count, 3);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+#include <assert.h>
+#include <nested_lists_cpp.h>
+#include <string_view>
+#include <vector>
+
+void exports::nested_lists::TestImports() {
+  uint32_t row0[] = {1};
+  uint32_t row2[] = {2, 3, 4};
+  std::vector<wit::span<uint32_t const>> rows{
+      wit::span<uint32_t const>(row0, 1),
+      wit::span<uint32_t const>(row2, 0),
+      wit::span<uint32_t const>(row2, 3),
+  };
+  auto result = ::test::nested_lists::test::JaggedRoundtrip(
+      wit::span<wit::span<uint32_t const> const>(rows.data(), rows.size()));
+  assert(result.size() == 3);
+  assert(result[0].size() == 1 && result[0][0] == 1);
+  assert(result[1].size() == 0);
+  assert(result[2].size() == 3 && result[2][2] == 4);
+
+  std::string_view words[] = {"a", "bc"};
+  std::vector<wit::span<std::string_view const>> inner{
+      wit::span<std::string_view const>(words, 2),
+      wit::span<std::string_view const>(words, 0),
+  };
+  std::vector<wit::span<wit::span<std::string_view const> const>> outer{
+      wit::span<wit::span<std::string_view const> const>(inner.data(), inner.size()),
+  };
+  assert(::test::nested_lists::test::DeepCount(
+             wit::span<wit::span<wit::span<std::string_view const> const> const>(
+                 outer.data(), outer.size())) == 2);
+}
+
+wit::vector<wit::vector<uint32_t>>
+exports::test::nested_lists::test::JaggedRoundtrip(
+    wit::vector<wit::vector<uint32_t>> &&a) {
+  return std::move(a);
+}
+
+uint32_t exports::test::nested_lists::test::DeepCount(
+    wit::vector<wit::vector<wit::vector<wit::string>>> &&a) {
+  uint32_t count = 0;
+  for (size_t i = 0; i < a.size(); ++i) {
+    for (size_t j = 0; j < a[i].size(); ++j) {
+      count += a[i][j].size();
+    }
+  }
+  return count;
+}
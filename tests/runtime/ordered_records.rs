@@ -0,0 +1,64 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/ordered_records");
+
+use test::ordered_records::test::Person;
+
+fn person(name: &str, age: u32) -> Person {
+    Person {
+        name: name.to_string(),
+        age,
+    }
+}
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::ordered_records::test::Host for MyImports {
+    fn sorted(&mut self, mut people: Vec<Person>) -> Vec<Person> {
+        // byte wise like the generated operator<=>
+        people.sort_by(|a, b| (&a.name, a.age).cmp(&(&b.name, b.age)));
+        people
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "ordered_records",
+        |linker| OrderedRecords::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| OrderedRecords::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: OrderedRecords, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let people = [
+        person("zoe", 3),
+        person("Zoe", 3),
+        person("äbc", 1),
+        person("abc", 2),
+        person("abc", 1),
+        person("zoe", 3),
+        person("ab", 40),
+    ];
+    let result = exports
+        .test_ordered_records_test()
+        .call_sorted(&mut *store, &people)?;
+    let expected = [
+        ("Zoe", 3),
+        ("ab", 40),
+        ("abc", 1),
+        ("abc", 2),
+        ("zoe", 3),
+        ("äbc", 1),
+    ];
+    assert_eq!(result.len(), expected.len());
+    for (p, (name, age)) in result.iter().zip(expected) {
+        assert_eq!((p.name.as_str(), p.age), (name, age));
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#include <assert.h>
+#include <ordered_records_cpp.h>
+#include <set>
+#include <string_view>
+#include <vector>
+
+using ::test::ordered_records::test::Person;
+
+static Person person(std::string_view name, uint32_t age) {
+    return Person{wit::string::from_view(name), age};
+}
+
+void exports::ordered_records::TestImports() {
+    // byte wise: upper case before lower case, a prefix first
+    std::set<Person> people;
+    people.insert(person("alice", 30));
+    people.insert(person("Bob", 25));
+    people.insert(person("alice", 7));
+    people.insert(person("al", 99));
+    people.insert(person("", 1));
+    people.insert(person("Bob", 25));
+    assert(people.size() == 5);
+    std::vector<std::pair<std::string_view, uint32_t>> expected = {
+        {"", 1}, {"Bob", 25}, {"al", 99}, {"alice", 7}, {"alice", 30}};
+    size_t i = 0;
+    for (auto const &p : people) {
+        assert(p.name.get_view() == expected[i].first);
+        assert(p.age == expected[i].second);
+        ++i;
+    }
+    assert(person("alice", 7) < person("alice", 30));
+    assert(person("alice", 7) == person("alice", 7));
+
+    // the host sorts like std::set
+    std::vector<Person> unsorted;
+    for (auto it = people.rbegin(); it != people.rend(); ++it) {
+        unsorted.push_back(person(it->name.get_view(), it->age));
+    }
+    auto result = ::test::ordered_records::test::Sorted(
+        wit::span<Person const>(unsorted.data(), unsorted.size()));
+    assert(result.size() == people.size());
+    i = 0;
+    for (auto const &p : people) {
+        assert(result[i] == p);
+        ++i;
+    }
+}
+
+wit::vector<Person> exports::test::ordered_records::test::Sorted(wit::vector<Person> &&people) {
+    std::set<Person> ordered;
+    for (size_t i = 0; i < people.size(); ++i) {
+        ordered.insert(std::move(people[i]));
+    }
+    auto result = wit::vector<Person>::with_capacity(ordered.size());
+    while (!ordered.empty()) {
+        result.push_back_unchecked(std::move(ordered.extract(ordered.begin()).value()));
+    }
+    return result;
+}
@@ -0,0 +1,26 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/owned_borrows");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "owned_borrows",
+        |_| Ok(()),
+        |store, component, linker| OwnedBorrows::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(instance: OwnedBorrows, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    let test = instance.test_owned_borrows_test();
+    let thing = test.thing().call_constructor(&mut *store, 7)?;
+    assert_eq!(test.thing().call_value(&mut *store, thing)?, 7);
+    assert_eq!(test.call_get_value(&mut *store, thing)?, 7);
+    thing.resource_drop(&mut *store)?;
+
+    // the guest passes an owned thing where a borrow is expected
+    assert_eq!(test.call_get_owned_value(&mut *store, 41)?, 42);
+
+    Ok(())
+}
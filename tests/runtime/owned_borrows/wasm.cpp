@@ -0,0 +1,26 @@
+#include <map>
+#include <owned_borrows_cpp.h>
+
+namespace test = exports::test::owned_borrows::test;
+
+// the generated class has no members of its own
+static std::map<test::Thing const *, uint32_t> values;
+
+test::Thing::Thing(uint32_t v) { values[this] = v; }
+
+void test::Thing::Dtor(test::Thing *self) {
+    values.erase(self);
+    delete self;
+}
+
+uint32_t test::Thing::Value() { return values[this]; }
+
+uint32_t test::GetValue(std::reference_wrapper<const test::Thing> v) {
+    return values[&v.get()];
+}
+
+uint32_t test::GetOwnedValue(uint32_t v) {
+    test::Thing::Owned thing = test::Thing::New(v + 1);
+    // the owned handle converts to the borrow
+    return test::GetValue(thing);
+}
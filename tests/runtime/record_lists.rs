@@ -0,0 +1,55 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/record_lists");
+
+use test::record_lists::test::Point;
+
+fn point(i: usize) -> Point {
+    Point {
+        x: i as i32,
+        y: -(i as i32) * 2,
+        label: format!("point {i}"),
+    }
+}
+
+fn assert_points(points: &[Point]) {
+    assert_eq!(points.len(), 1000);
+    for (i, p) in points.iter().enumerate() {
+        let expected = point(i);
+        assert_eq!(p.x, expected.x);
+        assert_eq!(p.y, expected.y);
+        assert_eq!(p.label, expected.label);
+    }
+}
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::record_lists::test::Host for MyImports {
+    fn point_roundtrip(&mut self, a: Vec<Point>) -> Vec<Point> {
+        assert_points(&a);
+        a
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "record_lists",
+        |linker| RecordLists::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| RecordLists::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: RecordLists, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let points = (0..1000).map(point).collect::<Vec<_>>();
+    let result = exports
+        .test_record_lists_test()
+        .call_point_roundtrip(&mut *store, &points)?;
+    assert_points(&result);
+    Ok(())
+}
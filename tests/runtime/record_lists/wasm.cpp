@@ -0,0 +1,26 @@
+#include <assert.h>
+#include <record_lists_cpp.h>
+#include <string>
+#include <vector>
+
+using ::test::record_lists::test::Point;
+
+void exports::record_lists::TestImports() {
+    std::vector<Point> points;
+    for (size_t i = 0; i < 1000; ++i) {
+        std::string label = "point " + std::to_string(i);
+        points.push_back(Point{int32_t(i), -int32_t(i) * 2, wit::string::from_view(label)});
+    }
+    auto result = ::test::record_lists::test::PointRoundtrip(
+        wit::span<Point const>(points.data(), points.size()));
+    assert(result.size() == points.size());
+    for (size_t i = 0; i < result.size(); ++i) {
+        assert(result[i].x == points[i].x);
+        assert(result[i].y == points[i].y);
+        assert(result[i].label.get_view() == points[i].label.get_view());
+    }
+}
+
+wit::vector<Point> exports::test::record_lists::test::PointRoundtrip(wit::vector<Point> &&a) {
+    return std::move(a);
+}
@@ -0,0 +1,60 @@
+use wasmtime::{component::Resource, Store};
+
+wasmtime::component::bindgen!(in "tests/runtime/resource_identity");
+
+use test::resource_identity::test::{Host, HostThing, Thing};
+
+#[derive(Default)]
+pub struct MyImports {
+    next: u32,
+}
+
+impl HostThing for MyImports {
+    fn new(&mut self, _id: u32) -> Resource<Thing> {
+        self.next += 1;
+        Resource::new_own(self.next)
+    }
+
+    fn same(&mut self, this: Resource<Thing>, other: Resource<Thing>) -> bool {
+        this.rep() == other.rep()
+    }
+
+    fn drop(&mut self, _: Resource<Thing>) -> wasmtime::Result<()> {
+        Ok(())
+    }
+}
+
+impl Host for MyImports {
+    fn is_same(&mut self, a: Resource<Thing>, b: Resource<Thing>) -> bool {
+        a.rep() == b.rep()
+    }
+}
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "resource_identity",
+        |linker| ResourceIdentity::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| ResourceIdentity::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(
+    instance: ResourceIdentity,
+    store: &mut Store<crate::Wasi<MyImports>>,
+) -> anyhow::Result<()> {
+    instance.call_test_imports(&mut *store)?;
+
+    let exports = instance.test_resource_identity_test();
+    let thing = exports.thing();
+    let a = thing.call_constructor(&mut *store, 1)?;
+    let b = thing.call_constructor(&mut *store, 2)?;
+    assert!(thing.call_same(&mut *store, a, a)?);
+    assert!(!thing.call_same(&mut *store, a, b)?);
+    assert!(exports.call_is_same(&mut *store, a, a)?);
+    assert!(!exports.call_is_same(&mut *store, a, b)?);
+    a.resource_drop(&mut *store)?;
+    b.resource_drop(&mut *store)?;
+    Ok(())
+}
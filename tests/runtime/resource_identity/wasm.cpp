@@ -0,0 +1,38 @@
+#include <assert.h>
+#include <resource_identity_cpp.h>
+
+namespace test_exports = exports::test::resource_identity::test;
+
+test_exports::Thing::Thing(uint32_t) {}
+
+void test_exports::Thing::Dtor(test_exports::Thing *self) { delete self; }
+
+bool test_exports::Thing::Same(std::reference_wrapper<const Thing> other) {
+    return *this == other;
+}
+
+bool test_exports::IsSame(std::reference_wrapper<const Thing> a,
+                          std::reference_wrapper<const Thing> b) {
+    return a == b;
+}
+
+void exports::resource_identity::TestImports() {
+    using namespace ::test::resource_identity::test;
+    Thing a(1);
+    Thing b(2);
+    assert(a.Same(a));
+    assert(!a.Same(b));
+    assert(IsSame(a, a));
+    assert(!IsSame(a, b));
+
+    // a borrow is the resource it was lent from, another is not
+    std::reference_wrapper<const Thing> borrowed = a;
+    assert(a == borrowed);
+    assert(borrowed != b);
+
+    test_exports::Thing::Owned owned = test_exports::Thing::New(3);
+    test_exports::Thing::Owned other = test_exports::Thing::New(3);
+    std::reference_wrapper<const test_exports::Thing> lent = *owned;
+    assert(owned == lent);
+    assert(other != lent);
+}
@@ -0,0 +1,52 @@
+use wasmtime::{component::Resource, Store};
+
+wasmtime::component::bindgen!(in "tests/runtime/resource_record_drops");
+
+use test::resource_record_drops::test::{Host, HostThing, Thing};
+
+#[derive(Default)]
+pub struct MyImports {
+    names: Vec<String>,
+    dropped: Vec<String>,
+    drops: usize,
+}
+
+impl HostThing for MyImports {
+    fn new(&mut self, name: String) -> Resource<Thing> {
+        self.names.push(name);
+        Resource::new_own(self.names.len() as u32 - 1)
+    }
+
+    fn drop(&mut self, thing: Resource<Thing>) -> wasmtime::Result<()> {
+        self.dropped.push(self.names[thing.rep() as usize].clone());
+        self.drops += 1;
+        Ok(())
+    }
+}
+
+impl Host for MyImports {
+    fn take_dropped(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.dropped)
+    }
+}
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "resource_record_drops",
+        |linker| ResourceRecordDrops::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| ResourceRecordDrops::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(
+    instance: ResourceRecordDrops,
+    store: &mut Store<crate::Wasi<MyImports>>,
+) -> anyhow::Result<()> {
+    instance.call_test_imports(&mut *store)?;
+    // every thing was dropped exactly once
+    assert_eq!(store.data().0.drops, 10);
+    assert_eq!(store.data().0.names.len(), 10);
+    Ok(())
+}
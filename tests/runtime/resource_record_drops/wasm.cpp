@@ -0,0 +1,48 @@
+#include <assert.h>
+#include <resource_record_drops_cpp.h>
+
+namespace imports = test::resource_record_drops::test;
+
+static void expect_dropped(std::initializer_list<std::string_view> names) {
+  wit::vector<wit::string> dropped = imports::TakeDropped();
+  assert(dropped.size() == names.size());
+  size_t i = 0;
+  for (std::string_view name : names) {
+    assert(dropped[i++].get_view() == name);
+  }
+}
+
+void exports::resource_record_drops::TestImports() {
+  // members are dropped in reverse declaration order
+  {
+    imports::Pair pair{imports::Thing("a"), imports::Thing("b")};
+  }
+  expect_dropped({"b", "a"});
+
+  // moving transfers both handles, they are dropped once
+  {
+    imports::Pair pair{imports::Thing("c"), imports::Thing("d")};
+    imports::Pair moved = std::move(pair);
+    assert(!pair.first && !pair.second);
+    expect_dropped({});
+  }
+  expect_dropped({"d", "c"});
+
+  // assigning drops the previous things first
+  {
+    imports::Pair target{imports::Thing("e"), imports::Thing("f")};
+    imports::Pair source{imports::Thing("g"), imports::Thing("h")};
+    target = std::move(source);
+    expect_dropped({"e", "f"});
+  }
+  // `source` holds nothing anymore
+  expect_dropped({"h", "g"});
+
+  // resetting one member leaves the other to the destructor
+  {
+    imports::Pair pair{imports::Thing("i"), imports::Thing("j")};
+    pair.first.reset();
+    expect_dropped({"i"});
+  }
+  expect_dropped({"j"});
+}
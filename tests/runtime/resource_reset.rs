@@ -0,0 +1,49 @@
+use wasmtime::{component::Resource, Store};
+
+wasmtime::component::bindgen!(in "tests/runtime/resource_reset");
+
+use test::resource_reset::test::{Host, HostThing, Thing};
+
+#[derive(Default)]
+pub struct MyImports {
+    values: Vec<u32>,
+    drops: u32,
+}
+
+impl HostThing for MyImports {
+    fn new(&mut self, v: u32) -> Resource<Thing> {
+        self.values.push(v);
+        Resource::new_own(self.values.len() as u32 - 1)
+    }
+
+    fn get(&mut self, thing: Resource<Thing>) -> u32 {
+        self.values[thing.rep() as usize]
+    }
+
+    fn drop(&mut self, _: Resource<Thing>) -> wasmtime::Result<()> {
+        self.drops += 1;
+        Ok(())
+    }
+}
+
+impl Host for MyImports {}
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "resource_reset",
+        |linker| ResourceReset::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| ResourceReset::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(
+    instance: ResourceReset,
+    store: &mut Store<crate::Wasi<MyImports>>,
+) -> anyhow::Result<()> {
+    instance.call_test_imports(&mut *store)?;
+    // reset dropped the thing, its destructor didn't drop it again
+    assert_eq!(store.data().0.drops, 1);
+    Ok(())
+}
@@ -0,0 +1,18 @@
+#include <assert.h>
+#include <resource_reset_cpp.h>
+
+namespace imports = test::resource_reset::test;
+
+void exports::resource_reset::TestImports() {
+    imports::Thing thing(7);
+    assert(thing.has_value());
+    assert(thing);
+    assert(thing.Get() == 7);
+
+    thing.reset();
+    assert(!thing.has_value());
+    assert(!thing);
+    // resetting again drops nothing
+    thing.reset();
+    // neither does the destructor of the reset thing
+}
@@ -0,0 +1,39 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/resource_spawn");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "resource_spawn",
+        |_| Ok(()),
+        |store, component, linker| ResourceSpawn::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(instance: ResourceSpawn, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    let test = instance.test_resource_spawn_test();
+    let node = test.node();
+    let root = node.call_constructor(&mut *store, 0)?;
+    let child = node.call_spawn(&mut *store, root)?;
+    let grandchild = node.call_spawn(&mut *store, child)?;
+    assert_eq!(node.call_depth(&mut *store, grandchild)?, 2);
+    let siblings = node.call_spawn_many(&mut *store, child, 3)?;
+    assert_eq!(siblings.len(), 3);
+    for sibling in siblings.iter() {
+        assert_eq!(node.call_depth(&mut *store, *sibling)?, 2);
+    }
+    assert_eq!(test.call_live(&mut *store)?, 6);
+
+    // children outlive their parents
+    root.resource_drop(&mut *store)?;
+    child.resource_drop(&mut *store)?;
+    assert_eq!(node.call_depth(&mut *store, grandchild)?, 2);
+    grandchild.resource_drop(&mut *store)?;
+    for sibling in siblings {
+        sibling.resource_drop(&mut *store)?;
+    }
+    assert_eq!(test.call_live(&mut *store)?, 0);
+    Ok(())
+}
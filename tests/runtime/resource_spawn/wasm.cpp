@@ -0,0 +1,27 @@
+#include <map>
+#include <resource_spawn_cpp.h>
+
+namespace test = exports::test::resource_spawn::test;
+
+static std::map<test::Node const *, uint32_t> depths;
+
+test::Node::Node(uint32_t depth) { depths[this] = depth; }
+
+void test::Node::Dtor(test::Node *self) {
+    depths.erase(self);
+    delete self;
+}
+
+uint32_t test::Node::Depth() { return depths[this]; }
+
+test::Node::Owned test::Node::Spawn() { return New(depths[this] + 1); }
+
+wit::vector<test::Node::Owned> test::Node::SpawnMany(uint32_t count) {
+    auto children = wit::vector<Owned>::allocate(count);
+    for (uint32_t i = 0; i < count; ++i) {
+        children.initialize(i, Spawn());
+    }
+    return children;
+}
+
+uint32_t test::Live() { return uint32_t(depths.size()); }
@@ -0,0 +1,70 @@
+use wasmtime::{component::Resource, Store};
+
+wasmtime::component::bindgen!(in "tests/runtime/resource_statics");
+
+use test::resource_statics::counters::{Counter, Host, HostCounter};
+
+#[derive(Default)]
+pub struct MyImports {
+    values: Vec<u32>,
+}
+
+impl MyImports {
+    fn push(&mut self, value: u32) -> Resource<Counter> {
+        self.values.push(value);
+        Resource::new_own(self.values.len() as u32 - 1)
+    }
+}
+
+impl HostCounter for MyImports {
+    fn new(&mut self, start: u32) -> Resource<Counter> {
+        self.push(start)
+    }
+
+    fn from_string(&mut self, s: String) -> Resource<Counter> {
+        self.push(s.parse().unwrap())
+    }
+
+    fn zero(&mut self) -> Resource<Counter> {
+        self.push(0)
+    }
+
+    fn value(&mut self, counter: Resource<Counter>) -> u32 {
+        self.values[counter.rep() as usize]
+    }
+
+    fn drop(&mut self, _: Resource<Counter>) -> wasmtime::Result<()> {
+        Ok(())
+    }
+}
+
+impl Host for MyImports {}
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "resource_statics",
+        |linker| ResourceStatics::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| ResourceStatics::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(
+    instance: ResourceStatics,
+    store: &mut Store<crate::Wasi<MyImports>>,
+) -> anyhow::Result<()> {
+    instance.call_test_imports(&mut *store)?;
+
+    let counter = instance.test_resource_statics_counters().counter();
+    let parsed = counter.call_from_string(&mut *store, "123")?;
+    assert_eq!(counter.call_value(&mut *store, parsed)?, 123);
+    let zero = counter.call_zero(&mut *store)?;
+    assert_eq!(counter.call_value(&mut *store, zero)?, 0);
+    let constructed = counter.call_constructor(&mut *store, 5)?;
+    assert_eq!(counter.call_value(&mut *store, constructed)?, 5);
+    parsed.resource_drop(&mut *store)?;
+    zero.resource_drop(&mut *store)?;
+    constructed.resource_drop(&mut *store)?;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+#include <assert.h>
+#include <map>
+#include <resource_statics_cpp.h>
+
+namespace counters = exports::test::resource_statics::counters;
+
+static std::map<counters::Counter const *, uint32_t> values;
+
+counters::Counter::Counter(uint32_t start) { values[this] = start; }
+
+void counters::Counter::Dtor(counters::Counter *self) {
+    values.erase(self);
+    delete self;
+}
+
+counters::Counter::Owned counters::Counter::FromString(wit::string &&s) {
+    uint32_t value = 0;
+    for (char c : s.get_view()) {
+        value = value * 10 + (c - '0');
+    }
+    return New(value);
+}
+
+counters::Counter::Owned counters::Counter::Zero() { return New(0); }
+
+uint32_t counters::Counter::Value() { return values[this]; }
+
+void exports::resource_statics::TestImports() {
+    using ::test::resource_statics::counters::Counter;
+    Counter parsed = Counter::FromString("42");
+    assert(parsed.Value() == 42);
+    Counter zero = Counter::Zero();
+    assert(zero.Value() == 0);
+    Counter constructed(7);
+    assert(constructed.Value() == 7);
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/result_helpers");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::result_helpers::test::Host for MyImports {
+    fn checked_div(&mut self, a: u32, b: u32) -> Result<u32, u8> {
+        a.checked_div(b).ok_or(1)
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "result_helpers",
+        |linker| ResultHelpers::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| ResultHelpers::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: ResultHelpers, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_result_helpers_test();
+    assert_eq!(test.call_checked_div(&mut *store, 8, 4)?, Ok(2));
+    assert_eq!(test.call_checked_div(&mut *store, 8, 0)?, Err(1));
+
+    Ok(())
+}
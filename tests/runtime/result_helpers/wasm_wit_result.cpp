@@ -0,0 +1,22 @@
+#include <assert.h>
+#include <result_helpers_cpp.h>
+
+void exports::result_helpers::TestImports() {
+    using ::test::result_helpers::test::CheckedDiv;
+    assert(CheckedDiv(7, 2).value_or(0) == 3);
+    // division by zero is an error
+    assert(CheckedDiv(7, 0).value_or(42) == 42);
+    assert(CheckedDiv(7, 0).error_or(0) == 1);
+    assert(CheckedDiv(7, 2).error_or(0) == 0);
+    auto doubled = CheckedDiv(9, 3).map([](uint32_t v) { return v * 2.0; });
+    assert(doubled.value() == 6.0);
+    auto message = CheckedDiv(1, 0).map_err([](uint8_t code) { return code == 1; });
+    assert(!message.has_value() && message.error());
+}
+
+wit::result<uint32_t, uint8_t> exports::test::result_helpers::test::CheckedDiv(uint32_t a, uint32_t b) {
+    if (b == 0) {
+        return std::unexpected<uint8_t>(1);
+    }
+    return a / b;
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/sbo_lists");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::sbo_lists::imports::Host for MyImports {
+    fn names(&mut self, count: u32) -> Vec<String> {
+        (0..count).map(|i| format!("name{i}")).collect()
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "sbo_lists",
+        |linker| SboLists::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| SboLists::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: SboLists, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    assert_eq!(exports.call_numbers(&mut *store, 3)?, [0, 10, 20]);
+    assert_eq!(
+        exports.call_numbers(&mut *store, 6)?,
+        [0, 10, 20, 30, 40, 50]
+    );
+    assert_eq!(exports.call_numbers(&mut *store, 0)?, []);
+    Ok(())
+}
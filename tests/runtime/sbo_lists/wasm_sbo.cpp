@@ -0,0 +1,56 @@
+#include <assert.h>
+#include <stdlib.h>
+// counts the allocations of strings and vectors
+#define WIT_CUSTOM_ALLOCATOR
+#include <sbo_lists_cpp.h>
+
+static size_t allocations = 0;
+extern "C" void *wit_malloc(size_t size) {
+  ++allocations;
+  return malloc(size);
+}
+extern "C" void wit_free(void *ptr) { free(ptr); }
+
+// generated with `--sbo=4`
+static_assert(WIT_VECTOR_INLINE == 4, "inline capacity");
+
+wit::vector<uint32_t> exports::sbo_lists::Numbers(uint32_t count) {
+  wit::vector<uint32_t> result = wit::vector<uint32_t>::with_capacity(count);
+  for (uint32_t i = 0; i < count; ++i) {
+    result.push_back_unchecked(i * 10);
+  }
+  // moved to the heap when lowered
+  return result;
+}
+
+void exports::sbo_lists::TestImports() {
+  using test::sbo_lists::imports::Names;
+
+  // the lifted strings keep their canonical ABI memory, the vector of a
+  // short list is inline
+  size_t before = allocations;
+  wit::vector<wit::string> names = Names(3);
+  assert(allocations == before);
+  assert(names.size() == 3);
+  assert(names[2].get_view() == "name2");
+
+  // stays inline when moved
+  wit::vector<wit::string> moved = std::move(names);
+  assert(allocations == before);
+  assert(moved[0].get_view() == "name0");
+
+  before = allocations;
+  wit::vector<wit::string> many = Names(5);
+  assert(allocations == before + 1);
+  assert(many[4].get_view() == "name4");
+
+  // appending within the inline capacity
+  uint32_t values[] = {1, 2, 3};
+  before = allocations;
+  wit::vector<uint32_t> small(nullptr, 0);
+  small.append_range(wit::span<uint32_t>(values, 3));
+  assert(allocations == before);
+  small.append_range(wit::span<uint32_t>(values, 3));
+  assert(allocations == before + 1);
+  assert(small.size() == 6 && small[5] == 3);
+}
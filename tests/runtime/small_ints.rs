@@ -0,0 +1,56 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/small_ints");
+
+use test::small_ints::test::Widths;
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::small_ints::test::Host for MyImports {
+    fn roundtrip(&mut self, x: Widths) -> Widths {
+        x
+    }
+
+    fn pair(&mut self, a: u8, b: i8) -> (u8, i8) {
+        (a, b)
+    }
+
+    fn signed(&mut self, x: Vec<i8>) -> Vec<i8> {
+        x
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "small_ints",
+        |linker| SmallInts::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| SmallInts::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: SmallInts, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_small_ints_test();
+    let extremes = Widths {
+        a: u8::MAX,
+        b: i8::MIN,
+        c: u16::MAX,
+        d: i16::MIN,
+    };
+    assert_eq!(test.call_roundtrip(&mut *store, extremes)?, extremes);
+    assert_eq!(
+        test.call_pair(&mut *store, u8::MAX, i8::MIN)?,
+        (u8::MAX, i8::MIN)
+    );
+    assert_eq!(test.call_pair(&mut *store, 128, i8::MAX)?, (128, i8::MAX));
+    assert_eq!(
+        test.call_signed(&mut *store, &[i8::MIN, -1, 0, i8::MAX])?,
+        [i8::MIN, -1, 0, i8::MAX]
+    );
+    Ok(())
+}
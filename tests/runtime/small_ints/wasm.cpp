@@ -0,0 +1,45 @@
+#include <assert.h>
+#include <limits>
+#include <small_ints_cpp.h>
+
+void exports::small_ints::TestImports() {
+  using namespace ::test::small_ints::test;
+  Widths extremes{std::numeric_limits<uint8_t>::max(),
+                  std::numeric_limits<int8_t>::min(),
+                  std::numeric_limits<uint16_t>::max(),
+                  std::numeric_limits<int16_t>::min()};
+  Widths back = Roundtrip(extremes);
+  assert(back.a == 255);
+  assert(back.b == -128);
+  assert(back.c == 65535);
+  assert(back.d == -32768);
+
+  auto pair = Pair(255, -128);
+  assert(std::get<0>(pair) == 255);
+  assert(std::get<1>(pair) == -128);
+  pair = Pair(128, 127);
+  assert(std::get<0>(pair) == 128);
+  assert(std::get<1>(pair) == 127);
+
+  int8_t values[] = {-128, -1, 0, 127};
+  wit::vector<int8_t> signed_ = Signed(wit::span<int8_t const>(values, 4));
+  assert(signed_.size() == 4);
+  for (size_t i = 0; i < 4; ++i) {
+    assert(signed_[i] == values[i]);
+  }
+}
+
+::test::small_ints::test::Widths exports::test::small_ints::test::Roundtrip(
+    ::test::small_ints::test::Widths x) {
+  return x;
+}
+
+std::tuple<uint8_t, int8_t> exports::test::small_ints::test::Pair(uint8_t a,
+                                                                  int8_t b) {
+  return std::tuple<uint8_t, int8_t>(a, b);
+}
+
+wit::vector<int8_t>
+exports::test::small_ints::test::Signed(wit::vector<int8_t> &&x) {
+  return std::move(x);
+}
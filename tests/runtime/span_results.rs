@@ -0,0 +1,23 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/span_results");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "span_results",
+        |_| Ok(()),
+        |store, component, linker| SpanResults::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(instance: SpanResults, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    let expected = |n: u32| -> Vec<u8> { (0..n).map(|i| (i * 7 + (i >> 8)) as u8).collect() };
+    assert_eq!(instance.call_bytes(&mut *store, 0)?, Vec::<u8>::new());
+    assert_eq!(instance.call_bytes(&mut *store, 5)?, expected(5));
+    // a 1MB view must arrive unchanged
+    let n = 1 << 20;
+    assert_eq!(instance.call_bytes(&mut *store, n)?, expected(n));
+    Ok(())
+}
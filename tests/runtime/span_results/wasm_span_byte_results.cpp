@@ -0,0 +1,13 @@
+#include <span_results_cpp.h>
+
+static uint8_t buffer[1 << 20];
+
+wit::span<uint8_t const> exports::span_results::Bytes(uint32_t n) {
+    if (n > sizeof(buffer)) {
+        n = sizeof(buffer);
+    }
+    for (uint32_t i = 0; i < n; ++i) {
+        buffer[i] = uint8_t(i * 7 + (i >> 8));
+    }
+    return wit::span<uint8_t const>(buffer, n);
+}
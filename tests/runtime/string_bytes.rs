@@ -0,0 +1,25 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/string_bytes");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "string_bytes",
+        |_| Ok(()),
+        |store, component, linker| StringBytes::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(instance: StringBytes, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    assert_eq!(instance.call_byte_sum(&mut *store, "")?, 0);
+    assert_eq!(instance.call_byte_sum(&mut *store, "Latin")?, 504);
+    assert_eq!(instance.call_byte_at(&mut *store, "Latin", 0)?, b'L');
+    assert_eq!(instance.call_byte_at(&mut *store, "Latin", 4)?, b'n');
+
+    // the last call, an out of range `at()` traps the instance
+    assert!(instance.call_byte_at(&mut *store, "Latin", 5).is_err());
+
+    Ok(())
+}
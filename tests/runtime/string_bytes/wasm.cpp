@@ -0,0 +1,16 @@
+#include <assert.h>
+#include <string_bytes_cpp.h>
+
+uint32_t exports::string_bytes::ByteSum(wit::string &&s) {
+    uint32_t sum = 0;
+    for (size_t i = 0; i < s.size(); ++i) {
+        assert(s[i] == s.at(i));
+        sum += s[i];
+    }
+    return sum;
+}
+
+uint8_t exports::string_bytes::ByteAt(wit::string &&s, uint32_t index) {
+    // traps if the index is out of range
+    return s.at(index);
+}
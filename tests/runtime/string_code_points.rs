@@ -0,0 +1,31 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/string_code_points");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "string_code_points",
+        |_| Ok(()),
+        |store, component, linker| StringCodePoints::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(instance: StringCodePoints, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    assert_eq!(
+        instance.call_code_points(&mut *store, "")?,
+        Vec::<u32>::new()
+    );
+    // four, one and four bytes
+    assert_eq!(
+        instance.call_code_points(&mut *store, "🚀 𠈄")?,
+        [0x1f680, 0x20, 0x20204]
+    );
+    // two and three bytes
+    assert_eq!(
+        instance.call_code_points(&mut *store, "ä€")?,
+        [0xe4, 0x20ac]
+    );
+    Ok(())
+}
@@ -0,0 +1,10 @@
+#include <string_code_points_cpp.h>
+
+wit::vector<uint32_t> exports::string_code_points::CodePoints(wit::string &&s) {
+    auto result = wit::vector<uint32_t>::allocate(s.size_codepoints());
+    size_t n = 0;
+    for (char32_t c : s.code_points()) {
+        result.initialize(n++, uint32_t(c));
+    }
+    return result;
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/string_compare");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::string_compare::imports::Host for MyImports {
+    fn greeting(&mut self) -> String {
+        "hello".to_string()
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "string_compare",
+        |linker| StringCompare::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| StringCompare::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: StringCompare, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+#include <assert.h>
+#include <string_compare_cpp.h>
+#include <string>
+
+void exports::string_compare::TestImports() {
+  wit::string s = test::string_compare::imports::Greeting();
+  assert(s == "hello");
+  assert("hello" == s);
+  assert(s != "hell");
+  assert(s != "hello world");
+  assert(s == std::string("hello"));
+  assert(std::string_view("hello") == s);
+#if __cplusplus > 202001L
+  assert(s < "help");
+  assert("hell" < s);
+  assert(s >= std::string("hello"));
+#endif
+}
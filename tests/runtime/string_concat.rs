@@ -0,0 +1,39 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/string_concat");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::string_concat::imports::Host for MyImports {
+    fn roundtrip(&mut self, s: String) -> String {
+        s
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "string_concat",
+        |linker| StringConcat::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| StringConcat::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: StringConcat, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let parts = [
+        "a".to_string(),
+        "bc".to_string(),
+        "".to_string(),
+        "d".to_string(),
+    ];
+    assert_eq!(exports.call_join(&mut *store, &parts, ", ")?, "a, bc, , d");
+    assert_eq!(exports.call_join(&mut *store, &[], "-")?, "");
+    let many = vec!["xy".to_string(); 200];
+    assert_eq!(exports.call_join(&mut *store, &many, "")?, "xy".repeat(200));
+    Ok(())
+}
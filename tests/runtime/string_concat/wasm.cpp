@@ -0,0 +1,38 @@
+#include <assert.h>
+#include <string_concat_cpp.h>
+
+void exports::string_concat::TestImports() {
+  using namespace test::string_concat::imports;
+  wit::string s = wit::string::from_view("hello");
+  s += ", ";
+  s += wit::string::from_view("world");
+  assert(s.get_view() == "hello, world");
+  // appending a part of itself
+  s += s.substr(0, 5);
+  assert(s.get_view() == "hello, worldhello");
+
+  wit::string built = wit::string::from_view("a") + "b" + "c";
+  for (int i = 0; i < 100; ++i) {
+    built += "0123456789";
+  }
+  assert(built.size() == 1003);
+  wit::string back = Roundtrip(built.get_view());
+  assert(back.get_view() == built.get_view());
+
+  wit::string copy = back + "!";
+  assert(copy.size() == 1004);
+  assert(back.size() == 1003);
+  assert(Roundtrip(copy.get_view()).get_view() == copy.get_view());
+}
+
+wit::string exports::string_concat::Join(wit::vector<wit::string> &&parts,
+                                         wit::string &&separator) {
+  wit::string result(nullptr, 0);
+  for (size_t i = 0; i < parts.size(); ++i) {
+    if (i > 0) {
+      result += separator;
+    }
+    result += parts[i];
+  }
+  return result;
+}
@@ -0,0 +1,20 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/string_moves");
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "string_moves",
+        |_| Ok(()),
+        |store, component, linker| StringMoves::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: StringMoves, store: &mut Store<crate::Wasi<()>>) -> Result<()> {
+    let large = "moved 🚀 ".repeat(4096);
+    assert_eq!(exports.call_roundtrip(&mut *store, &large)?, large);
+    Ok(())
+}
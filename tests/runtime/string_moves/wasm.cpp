@@ -0,0 +1,31 @@
+#include <assert.h>
+#include <new>
+#include <stdlib.h>
+#include <string>
+#include <string_moves_cpp.h>
+
+// counts the allocations made through operator new (std::string uses it)
+static size_t new_calls = 0;
+
+void *operator new(size_t size) {
+  ++new_calls;
+  void *result = malloc(size);
+  if (!result) abort();
+  return result;
+}
+
+void operator delete(void *ptr) noexcept { free(ptr); }
+
+void operator delete(void *ptr, size_t) noexcept { free(ptr); }
+
+wit::string exports::string_moves::Roundtrip(wit::string &&str) {
+  std::string owned(str.get_view());
+  assert(owned.size() > 1024);
+  size_t before = new_calls;
+  // the only allocation is the single malloc for the result
+  wit::string result = wit::string::from_string(std::move(owned));
+  assert(new_calls == before);
+  assert(owned.capacity() < 1024);
+  assert(result.size() == str.size());
+  return result;
+}
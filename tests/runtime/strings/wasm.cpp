@@ -15,6 +15,14 @@ void exports::strings::TestImports() {
 
   wit::string str2 = test::strings::imports::ReturnUnicode();
   assert_str(str2.get_view(), "🚀🚀🚀 𠈄𓀀");
+  assert(str2.size_bytes() == 21);
+  assert(str2.size_codepoints() == 6);
+  // each rocket takes four bytes
+  assert_str(str2.substr(4, 8), "🚀🚀");
+  assert_str(str2.substr(13), "𠈄𓀀");
+  assert_str(str2.substr(12, 1), " ");
+  assert(str2.substr(21).empty());
+  assert(str2.substr(17, 100) == "𓀀");
 }
 
 wit::string exports::strings::ReturnEmpty() {
@@ -24,5 +32,6 @@ wit::string exports::strings::ReturnEmpty() {
 
 wit::string exports::strings::Roundtrip(wit::string &&str) {
   assert(str.size() > 0);
+  assert(str.size_codepoints() <= str.size_bytes());
   return std::move(str);
 }
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/try_call");
+
+use test::try_call::test::Shape;
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::try_call::test::Host for MyImports {
+    fn maybe(&mut self, n: u32) -> Option<u32> {
+        (n > 0).then_some(n)
+    }
+
+    fn shapes(&mut self, n: u32) -> Vec<Shape> {
+        (0..n)
+            .map(|i| {
+                if i % 2 == 0 {
+                    Shape::Circle(i)
+                } else {
+                    Shape::Square(i)
+                }
+            })
+            .collect()
+    }
+
+    fn count(&mut self) -> u32 {
+        42
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "try_call",
+        |linker| TryCall::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| TryCall::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+// wasmtime only lowers well-formed values, so this covers the successful
+// path of the `Try` wrappers, malformed ones are checked by the codegen tests
+fn run_test(exports: TryCall, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+#include <assert.h>
+#include <try_call_cpp.h>
+
+void exports::try_call::TestImports() {
+  using namespace test::try_call::test;
+
+  auto some = TryMaybe(3);
+  assert(some.has_value());
+  assert(some->value() == 3);
+  auto none = TryMaybe(0);
+  assert(none.has_value());
+  assert(!none->has_value());
+
+  auto shapes = TryShapes(3);
+  assert(shapes.has_value());
+  assert(shapes->size() == 3);
+  for (size_t i = 0; i < shapes->size(); ++i) {
+    Shape const &shape = (*shapes)[i];
+    assert(shape.variants.index() == i % 2);
+    if (i % 2 == 0) {
+      assert(std::get<Shape::Circle>(shape.variants).value == i);
+    } else {
+      assert(std::get<Shape::Square>(shape.variants).value == i);
+    }
+  }
+
+  // the trapping functions are still there
+  assert(Maybe(4).value() == 4);
+  assert(Shapes(0).size() == 0);
+  // nothing fails to lift, so there is no wrapper
+  assert(Count() == 42);
+}
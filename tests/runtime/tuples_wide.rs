@@ -0,0 +1,35 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/tuples_wide");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::tuples_wide::test::Host for MyImports {
+    fn roundtrip(&mut self, a: (u8, u64, f32, String)) -> (u8, u64, f32, String) {
+        (a.0 + 1, a.1 + 1, a.2 * 2.0, a.3 + "!")
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "tuples_wide",
+        |linker| TuplesWide::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| TuplesWide::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: TuplesWide, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_tuples_wide_test();
+    let a = (7, u64::MAX - 1, 1.5, "wide".to_string());
+    assert_eq!(
+        test.call_roundtrip(&mut *store, (a.0, a.1, a.2, &a.3))?,
+        (8, u64::MAX, 3.0, "wide!".to_string())
+    );
+    Ok(())
+}
@@ -0,0 +1,18 @@
+#include <assert.h>
+#include <tuples_wide_cpp.h>
+
+std::tuple<uint8_t, uint64_t, float, wit::string>
+exports::test::tuples_wide::test::Roundtrip(std::tuple<uint8_t, uint64_t, float, wit::string> a) {
+    std::string text = std::get<3>(a).to_string() + "!";
+    return std::tuple<uint8_t, uint64_t, float, wit::string>(
+        std::get<0>(a) + 1, std::get<1>(a) + 1, std::get<2>(a) * 2, wit::string::from_view(text));
+}
+
+void exports::tuples_wide::TestImports() {
+    auto result = ::test::tuples_wide::test::Roundtrip(
+        std::tuple<uint8_t, uint64_t, float, std::string_view>(1, 0x100000000ull, 0.25f, "tuple"));
+    assert(std::get<0>(result) == 2);
+    assert(std::get<1>(result) == 0x100000001ull);
+    assert(std::get<2>(result) == 0.5f);
+    assert(std::get<3>(result).get_view() == "tuple!");
+}
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/variant_setters");
+
+use test::variant_setters::imports::Payload;
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::variant_setters::imports::Host for MyImports {
+    fn describe(&mut self, p: Payload) -> String {
+        match p {
+            Payload::Text(s) => format!("text {s}"),
+            Payload::Number(n) => format!("number {n}"),
+            Payload::Bytes(b) => format!("bytes {}", b.len()),
+            Payload::Nothing => "nothing".to_string(),
+        }
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "variant_setters",
+        |linker| VariantSetters::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| VariantSetters::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: VariantSetters, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    Ok(())
+}
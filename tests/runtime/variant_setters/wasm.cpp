@@ -0,0 +1,56 @@
+#include <assert.h>
+#include <set>
+#include <stdlib.h>
+// tracks the allocations of strings and vectors
+#define WIT_CUSTOM_ALLOCATOR
+#include <variant_setters_cpp.h>
+
+// lifted strings come from cabi_realloc and aren't tracked
+static std::set<void *> allocated;
+extern "C" void *wit_malloc(size_t size) {
+  void *ptr = malloc(size);
+  allocated.insert(ptr);
+  return ptr;
+}
+extern "C" void wit_free(void *ptr) {
+  allocated.erase(ptr);
+  free(ptr);
+}
+
+void exports::variant_setters::TestImports() {
+  using namespace test::variant_setters::imports;
+  {
+    Payload p{Payload::Nothing{}};
+    p.set_text(wit::string::from_view("hello"));
+    assert(std::holds_alternative<Payload::Text>(p.variants));
+    assert(allocated.size() == 1);
+
+    // the previous string is freed when switching the case
+    p.set_number(42);
+    assert(allocated.empty());
+    assert(std::get<Payload::Number>(p.variants).value == 42);
+    assert(Describe(std::move(p)).get_view() == "number 42");
+
+    wit::vector<uint8_t> bytes = wit::vector<uint8_t>::allocate(3);
+    for (uint8_t i = 0; i < 3; ++i) {
+      bytes[i] = i + 1;
+    }
+    p.set_bytes(std::move(bytes));
+    assert(allocated.size() == 1);
+    p.set_text(wit::string::from_view("again"));
+    assert(allocated.size() == 1);
+    assert(Describe(std::move(p)).get_view() == "text again");
+    assert(allocated.empty());
+
+    p.set_nothing();
+    assert(std::holds_alternative<Payload::Nothing>(p.variants));
+    assert(Describe(std::move(p)).get_view() == "nothing");
+
+    p.set_bytes(wit::vector<uint8_t>::allocate(2));
+    assert(Describe(std::move(p)).get_view() == "bytes 2");
+    p.set_text(wit::string::from_view("dropped"));
+    assert(allocated.size() == 1);
+  }
+  // destroying the variant frees the active payload
+  assert(allocated.empty());
+}
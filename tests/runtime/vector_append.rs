@@ -0,0 +1,40 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/vector_append");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "vector_append",
+        |_| Ok(()),
+        |store, component, linker| VectorAppend::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: VectorAppend, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    assert_eq!(
+        exports.call_concat(&mut *store, &[1, 2], &[3], &[4, 5, 6])?,
+        [1, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(exports.call_concat(&mut *store, &[], &[], &[7])?, [7]);
+    assert_eq!(
+        exports.call_concat(&mut *store, &[], &[], &[])?,
+        Vec::<u32>::new()
+    );
+
+    let nested = exports.call_concat_nested(
+        &mut *store,
+        &[b"ab".to_vec()],
+        &[],
+        &[b"c".to_vec(), b"".to_vec(), b"def".to_vec()],
+    )?;
+    assert_eq!(
+        nested,
+        [b"ab".to_vec(), b"c".to_vec(), b"".to_vec(), b"def".to_vec()]
+    );
+
+    assert_eq!(exports.call_doubled(&mut *store, &[8, 9])?, [8, 9, 8, 9]);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+#include <assert.h>
+#include <vector_append_cpp.h>
+
+wit::vector<uint32_t> exports::vector_append::Concat(wit::vector<uint32_t> &&a,
+                                                     wit::vector<uint32_t> &&b,
+                                                     wit::vector<uint32_t> &&c) {
+    wit::vector<uint32_t> result = std::move(a);
+    result.append(b);
+    result.append_range(c.get_view());
+    return result;
+}
+
+wit::vector<wit::vector<uint8_t>>
+exports::vector_append::ConcatNested(wit::vector<wit::vector<uint8_t>> &&a,
+                                     wit::vector<wit::vector<uint8_t>> &&b,
+                                     wit::vector<wit::vector<uint8_t>> &&c) {
+    wit::vector<wit::vector<uint8_t>> result = std::move(a);
+    result.append(b);
+    result.append(c);
+    return result;
+}
+
+wit::vector<uint32_t> exports::vector_append::Doubled(wit::vector<uint32_t> &&a) {
+    size_t length = a.size();
+    a.append(a);
+    assert(a.size() == 2 * length);
+    return std::move(a);
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/vector_copies");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::vector_copies::test::Host for MyImports {
+    fn copy_list(&mut self, a: Vec<u32>) -> Vec<u32> {
+        a
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "vector_copies",
+        |linker| VectorCopies::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| VectorCopies::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: VectorCopies, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_vector_copies_test();
+    let large = (0..100_000).collect::<Vec<u32>>();
+    assert_eq!(test.call_copy_list(&mut *store, &large)?, large);
+
+    Ok(())
+}
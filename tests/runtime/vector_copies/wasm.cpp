@@ -0,0 +1,47 @@
+#include <assert.h>
+#include <vector>
+#include <vector_copies_cpp.h>
+
+static size_t copies = 0;
+
+// counts its copies to show that a copy assignment copies each element once
+struct Counted {
+    uint32_t value;
+    Counted(uint32_t v) : value(v) {}
+    Counted(Counted const &b) : value(b.value) { ++copies; }
+};
+
+void exports::vector_copies::TestImports() {
+    std::vector<uint32_t> numbers(10000);
+    for (uint32_t i = 0; i < numbers.size(); ++i) {
+        numbers[i] = i * 7;
+    }
+    wit::vector<uint32_t> a = ::test::vector_copies::test::CopyList(numbers);
+    wit::vector<uint32_t> b = wit::vector<uint32_t>::allocate(0);
+    b = a;
+    assert(b.size() == numbers.size());
+    assert(b.data() != a.data());
+    for (size_t i = 0; i < b.size(); ++i) {
+        assert(b[i] == numbers[i] && a[i] == numbers[i]);
+    }
+    wit::vector<uint32_t> const &same = b;
+    b = same;
+    assert(b.size() == numbers.size() && b[9999] == 9999 * 7);
+
+    auto counted = wit::vector<Counted>::allocate(10000);
+    for (uint32_t i = 0; i < counted.size(); ++i) {
+        counted.initialize(i, Counted(i));
+    }
+    auto target = wit::vector<Counted>::allocate(0);
+    copies = 0;
+    target = counted;
+    // a single pass over the elements into one allocation
+    assert(copies == counted.size());
+    assert(target.size() == counted.size() && target[1234].value == 1234);
+}
+
+wit::vector<uint32_t> exports::test::vector_copies::test::CopyList(wit::vector<uint32_t> &&a) {
+    wit::vector<uint32_t> result = wit::vector<uint32_t>::allocate(0);
+    result = a;
+    return result;
+}
@@ -0,0 +1,25 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/vector_data");
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "vector_data",
+        |_| Ok(()),
+        |store, component, linker| VectorData::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: VectorData, store: &mut Store<crate::Wasi<()>>) -> anyhow::Result<()> {
+    assert_eq!(exports.call_checksum(&mut *store, &[])?, 0);
+    assert_eq!(exports.call_checksum(&mut *store, &[5, 1, 1000])?, 3007);
+    assert_eq!(
+        exports.call_increment(&mut *store, &[0, 1, 254])?,
+        [1, 2, 255]
+    );
+    assert_eq!(exports.call_increment(&mut *store, &[])?, Vec::<u8>::new());
+
+    Ok(())
+}
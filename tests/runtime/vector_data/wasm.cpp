@@ -0,0 +1,33 @@
+#include <assert.h>
+#include <stddef.h>
+#include <vector_data_cpp.h>
+
+// C style functions taking pointer and length
+extern "C" {
+static uint32_t weighted_sum(uint16_t const *values, size_t count) {
+    uint32_t sum = 0;
+    for (size_t i = 0; i < count; ++i) {
+        sum += uint32_t(i + 1) * values[i];
+    }
+    return sum;
+}
+
+static void increment_all(uint8_t *bytes, size_t count) {
+    for (size_t i = 0; i < count; ++i) {
+        ++bytes[i];
+    }
+}
+}
+
+uint32_t exports::vector_data::Checksum(wit::vector<uint16_t> &&a) {
+    wit::vector<uint16_t> const &values = a;
+    return weighted_sum(values.data(), values.size());
+}
+
+wit::vector<uint8_t> exports::vector_data::Increment(wit::vector<uint8_t> &&a) {
+    uint8_t *data = a.data();
+    increment_all(data, a.size());
+    // modifying the elements keeps the storage in place
+    assert(a.data() == data);
+    return std::move(a);
+}
@@ -0,0 +1,34 @@
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/vector_into_std");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl VectorIntoStdImports for MyImports {
+    fn squares(&mut self, n: u32) -> Vec<u32> {
+        (0..n).map(|i| i * i).collect()
+    }
+
+    fn names(&mut self, n: u32) -> Vec<String> {
+        (0..n).map(|i| format!("name{i}")).collect()
+    }
+}
+
+#[test]
+fn run() -> anyhow::Result<()> {
+    crate::run_test(
+        "vector_into_std",
+        |linker| VectorIntoStd::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| VectorIntoStd::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(
+    exports: VectorIntoStd,
+    store: &mut Store<crate::Wasi<MyImports>>,
+) -> anyhow::Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    Ok(())
+}
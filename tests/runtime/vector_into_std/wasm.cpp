@@ -0,0 +1,20 @@
+#include <assert.h>
+#include <vector>
+#include <vector_into_std_cpp.h>
+
+void exports::vector_into_std::TestImports() {
+    wit::vector<uint32_t> squares = ::vector_into_std::Squares(4);
+    std::vector<uint32_t> values = std::move(squares).into_std();
+    assert((values == std::vector<uint32_t>{0, 1, 4, 9}));
+    // the list gave up its storage, destroying it frees nothing twice
+    assert(squares.size() == 0);
+    assert(squares.data() == nullptr);
+
+    assert(::vector_into_std::Squares(0).into_std().empty());
+
+    // elements owning memory are moved over
+    std::vector<wit::string> names = ::vector_into_std::Names(2).into_std();
+    assert(names.size() == 2);
+    assert(names[0].get_view() == "name0");
+    assert(names[1].get_view() == "name1");
+}
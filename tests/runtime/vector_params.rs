@@ -0,0 +1,45 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/vector_params");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::vector_params::test::Host for MyImports {
+    fn count_true(&mut self, a: Vec<bool>) -> u32 {
+        a.iter().filter(|b| **b).count() as u32
+    }
+
+    fn total_length(&mut self, a: Vec<String>, b: Vec<u32>) -> u32 {
+        a.iter().map(|s| s.len() as u32).sum::<u32>() + b.iter().sum::<u32>()
+    }
+
+    fn sum(&mut self, a: Vec<u32>) -> u32 {
+        a.iter().sum()
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "vector_params",
+        |linker| VectorParams::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| VectorParams::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: VectorParams, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+
+    let test = exports.test_vector_params_test();
+    assert_eq!(test.call_count_true(&mut *store, &[true, true, false])?, 2);
+    assert_eq!(
+        test.call_total_length(&mut *store, &["ab".to_string()], &[1, 2])?,
+        5
+    );
+    assert_eq!(test.call_sum(&mut *store, &[4, 5])?, 9);
+
+    Ok(())
+}
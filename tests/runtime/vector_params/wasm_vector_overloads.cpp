@@ -0,0 +1,39 @@
+#include <assert.h>
+#include <vector_params_cpp.h>
+
+void exports::vector_params::TestImports() {
+    using namespace ::test::vector_params::test;
+    assert(CountTrue(std::vector<bool>{true, false, true, true}) == 3);
+    assert(CountTrue(std::vector<bool>{}) == 0);
+    std::vector<std::string> words{"hello", "", "world!"};
+    std::vector<uint32_t> numbers{1, 2, 3};
+    assert(TotalLength(words, numbers) == 17);
+    assert(Sum(numbers) == 6);
+}
+
+uint32_t exports::test::vector_params::test::CountTrue(wit::vector<bool> &&a) {
+    uint32_t count = 0;
+    for (bool b : a.get_view()) {
+        count += b;
+    }
+    return count;
+}
+
+uint32_t exports::test::vector_params::test::TotalLength(wit::vector<wit::string> &&a, wit::vector<uint32_t> &&b) {
+    uint32_t total = 0;
+    for (auto const &s : a.get_view()) {
+        total += s.size();
+    }
+    for (uint32_t n : b.get_view()) {
+        total += n;
+    }
+    return total;
+}
+
+uint32_t exports::test::vector_params::test::Sum(wit::vector<uint32_t> &&a) {
+    uint32_t sum = 0;
+    for (uint32_t n : a.get_view()) {
+        sum += n;
+    }
+    return sum;
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use wasmtime::Store;
+
+wasmtime::component::bindgen!(in "tests/runtime/wide_strings");
+
+#[derive(Default)]
+pub struct MyImports;
+
+impl test::wide_strings::imports::Host for MyImports {
+    fn roundtrip(&mut self, s: String) -> String {
+        assert_eq!(s, "🚀🚀🚀 𠈄𓀀");
+        s
+    }
+}
+
+#[test]
+fn run() -> Result<()> {
+    crate::run_test(
+        "wide_strings",
+        |linker| WideStrings::add_to_linker(linker, |x| &mut x.0),
+        |store, component, linker| WideStrings::instantiate(store, component, linker),
+        run_test,
+    )
+}
+
+fn run_test(exports: WideStrings, store: &mut Store<crate::Wasi<MyImports>>) -> Result<()> {
+    exports.call_test_imports(&mut *store)?;
+    for s in ["", "latin", "äöü €", "🚀🚀🚀 𠈄𓀀"] {
+        assert_eq!(exports.call_widen(&mut *store, s)?, s);
+    }
+    // wasm32 has a 32 bit wchar_t
+    assert_eq!(exports.call_wide_length(&mut *store, "🚀🚀🚀 𠈄𓀀")?, 6);
+    Ok(())
+}
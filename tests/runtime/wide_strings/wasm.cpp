@@ -0,0 +1,30 @@
+#include <assert.h>
+#include <wide_strings_cpp.h>
+
+// the unicode content of the strings test, emoji outside of the BMP
+static std::wstring const unicode = L"🚀🚀🚀 𠈄𓀀";
+
+wit::string exports::wide_strings::Widen(wit::string &&s) {
+  std::wstring wide = wit::to_wstring(s.get_view());
+  return wit::string::from_view(wit::to_utf8(wide));
+}
+
+uint32_t exports::wide_strings::WideLength(wit::string &&s) {
+  return uint32_t(wit::to_wstring(s.get_view()).size());
+}
+
+void exports::wide_strings::TestImports() {
+  using test::wide_strings::imports::Roundtrip;
+  std::string utf8 = wit::to_utf8(unicode);
+  assert(utf8 == "🚀🚀🚀 𠈄𓀀");
+  wit::string result = Roundtrip(utf8);
+  assert(wit::to_wstring(result.get_view()) == unicode);
+
+  // one code unit per code point with 32 bit wchar_t, else surrogate pairs
+  assert(unicode.size() == (sizeof(wchar_t) == 2 ? 11 : 6));
+
+  // an unpaired surrogate can't be passed as UTF-8
+  std::wstring broken = L"a";
+  broken += wchar_t(0xd800);
+  assert(wit::to_utf8(broken) == "a�");
+}